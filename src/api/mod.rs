@@ -1 +1,2 @@
-pub mod router;
\ No newline at end of file
+pub mod router;
+pub mod routes;
\ No newline at end of file
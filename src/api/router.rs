@@ -1,13 +1,18 @@
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use lambda_runtime::Error;
 use serde_json::{json, Value};
 use tracing::{info, error};
 
-use crate::errors::ApiError;
+use crate::api::routes::{self, Endpoint, PathParams};
+use crate::errors::{ApiError, ApiErrorResponse};
 use crate::handlers::BattleHandler;
 use crate::types::*;
 
 pub struct Router {
     battle_handler: BattleHandler,
+    admin_key: Option<String>, // Admin endpoints are disabled when unset
+    enable_cors: bool, // Attach Access-Control-* headers; off for backend-only deployments
+    test_endpoints_enabled: bool, // Serve `/test/*` harness endpoints; off unless explicitly enabled
 }
 
 impl Router {
@@ -19,12 +24,47 @@ impl Router {
         let battle_handler = BattleHandler::new_with_real_db(table_name).await
             .map_err(|e| format!("Failed to initialize battle handler: {}", e))?;
 
-        Ok(Router { battle_handler })
+        let admin_key = std::env::var("ADMIN_API_KEY").ok()
+            .filter(|key| !key.is_empty());
+
+        let enable_cors = std::env::var("ENABLE_CORS")
+            .map(|value| !matches!(value.to_lowercase().as_str(), "false" | "0" | "no"))
+            .unwrap_or(true);
+
+        let test_endpoints_enabled = std::env::var("TEST_ENDPOINTS_ENABLED")
+            .map(|value| matches!(value.to_lowercase().as_str(), "true" | "1" | "yes"))
+            .unwrap_or(false);
+
+        Ok(Router { battle_handler, admin_key, enable_cors, test_endpoints_enabled })
+    }
+
+    /// Router over an existing handler, e.g. one backed by the in-memory database
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn with_handler(battle_handler: BattleHandler, admin_key: Option<String>) -> Self {
+        Router { battle_handler, admin_key, enable_cors: true, test_endpoints_enabled: false }
+    }
+
+    /// Override the `ENABLE_CORS` setting
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn with_cors(mut self, enable_cors: bool) -> Self {
+        self.enable_cors = enable_cors;
+        self
+    }
+
+    /// Override the `TEST_ENDPOINTS_ENABLED` setting
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn with_test_endpoints(mut self, test_endpoints_enabled: bool) -> Self {
+        self.test_endpoints_enabled = test_endpoints_enabled;
+        self
     }
 
     pub async fn call(&self, event: lambda_runtime::LambdaEvent<Value>) -> Result<Value, Error> {
         let (payload, _context) = event.into_parts();
-        
+        self.handle(payload).await
+    }
+
+    /// Route an API Gateway v2 payload and build the HTTP response envelope
+    pub async fn handle(&self, payload: Value) -> Result<Value, Error> {
         // Extract HTTP method and path from the Lambda event (API Gateway v2 format)
         let method = payload.get("requestContext")
             .and_then(|ctx| ctx.get("http"))
@@ -42,192 +82,708 @@ impl Router {
         info!("Processing {} {}", method, path);
 
         // Route the request
-        let response = match (method, path) {
+        let Some((route, params)) = routes::find_route(method, path) else {
+            // The 404 envelope is already complete, so skip the 200 wrapping below
+            return Ok(self.not_found());
+        };
+        // Disabled test endpoints look the same as unknown paths
+        if route.endpoint.is_test_only() && !self.test_endpoints_enabled {
+            return Ok(self.not_found());
+        }
+        let msgpack = route.endpoint.supports_msgpack() && accepts_msgpack(&payload);
+        let is_admin = self.is_admin(&payload);
+        let caller = authenticated_player_id(&payload);
+        let response = match route.endpoint {
+            Endpoint::Index => Ok(json!({ "endpoints": routes::route_index() })),
             // MVP Endpoints
-            ("GET", "/available_teams") => self.get_available_teams().await,
-            ("GET", "/npc_opponents") => self.get_npc_opponents().await, 
-            ("POST", "/battles") => self.create_mvp_battle(payload).await,
-            ("POST", path) if path.starts_with("/battles/") && path.ends_with("/action") => {
-                self.submit_action(payload).await
+            Endpoint::AvailableTeams => self.get_available_teams().await,
+            Endpoint::AvailableTeamsFull => self.get_available_teams_full().await,
+            Endpoint::NpcOpponents => self.get_npc_opponents().await,
+            Endpoint::GameStates => self.get_game_states().await,
+            Endpoint::CreateMvpBattle => self.create_mvp_battle(payload).await,
+            Endpoint::CreateBattle => self.create_battle(payload).await,
+            Endpoint::Quickplay => self.quickplay(payload).await,
+            Endpoint::RecentBattles => self.get_recent_battles(payload).await,
+            Endpoint::TeamMatchup => self.get_team_matchup(payload).await,
+            Endpoint::TeamPreview => self.preview_team(payload).await,
+            Endpoint::TeamValidate => self.validate_team(payload).await,
+            Endpoint::Effectiveness => self.get_effectiveness(payload).await,
+            Endpoint::CreateMatch => self.create_match(payload).await,
+            Endpoint::BatchEvents => self.get_batch_events(payload).await,
+            Endpoint::AbandonAll => self.abandon_all_battles(&params, &payload).await,
+            Endpoint::PlayerBattles => self.list_player_battles(&params, &payload).await,
+            Endpoint::PlayerHistory => self.get_player_history(&params, &payload).await,
+            Endpoint::GetMatch => self.get_match(&params).await,
+            Endpoint::SpeciesInfo => self.get_species_info(&params).await,
+            Endpoint::LearnableMoves => self.get_learnable_moves(&params).await,
+            Endpoint::MoveInfo => self.get_move_info(&params).await,
+            Endpoint::BattleByNumber => self.get_battle_by_number(&params, &payload).await,
+            Endpoint::SubmitAction => self.submit_action(&params, payload).await,
+            Endpoint::Forfeit => self.forfeit(&params, payload).await,
+            Endpoint::Rematch => self.rematch(&params, payload).await,
+            Endpoint::BattleState => self.get_battle_state(&params, payload).await,
+            Endpoint::ValidActions => self.get_valid_actions(&params, payload).await,
+            Endpoint::MovePp => self.get_move_pp(&params, payload).await,
+            Endpoint::TeamInfo => self.get_team_info(&params, payload).await,
+            Endpoint::BattleEvents => self.get_battle_events(&params, payload).await,
+            Endpoint::TurnLog => self.get_turn_log(&params, payload).await,
+            Endpoint::TickStatus => self.get_tick_status(&params, payload).await,
+            Endpoint::BattleResult => self.get_battle_result(&params, payload).await,
+            Endpoint::ShowdownLog => self.get_showdown_log(&params, payload).await,
+            Endpoint::BattleSetup => self.get_battle_setup(&params, payload).await,
+            Endpoint::DamageReport => self.get_damage_report(&params, payload).await,
+            Endpoint::BattleEstimate => self.get_battle_estimate(&params, payload).await,
+            Endpoint::BattleReplay => self.get_battle_replay(&params, payload).await,
+            Endpoint::BattleChanges => self.get_battle_changes(&params, payload).await,
+            Endpoint::Spectate => self.get_spectator_view(&params, payload).await,
+            Endpoint::PauseBattle => self.set_paused(&params, payload, true).await,
+            Endpoint::ResumeBattle => self.set_paused(&params, payload, false).await,
+            Endpoint::DeleteBattle => {
+                return match self.delete_battle(&params, &payload).await {
+                    Ok(()) => Ok(self.no_content()),
+                    Err(e) => Ok(self.caller_error_response(e, is_admin, caller.as_ref())),
+                };
             }
-            ("GET", path) if path.starts_with("/battles/") && path.contains("/state") => {
-                self.get_battle_state(payload).await
-            }
-            ("GET", path) if path.starts_with("/battles/") && path.contains("/valid_actions") => {
-                self.get_valid_actions(payload).await
-            }
-            ("GET", path) if path.starts_with("/battles/") && path.contains("/team_info") => {
-                self.get_team_info(payload).await
-            }
-            ("GET", path) if path.starts_with("/battles/") && path.contains("/events") => {
-                self.get_battle_events(payload).await
-            }
-            ("GET", "/health") => Ok(json!({
+            // Admin Endpoints
+            Endpoint::MoveUsage => self.get_move_usage(payload).await,
+            Endpoint::ActionQueue => self.get_action_queue(&params, payload).await,
+            Endpoint::SetBattleState => self.set_battle_state(&params, &payload).await,
+            // Test Endpoints
+            Endpoint::Playout => self.playout(payload).await,
+            Endpoint::Health => Ok(json!({
                 "status": "healthy",
                 "timestamp": chrono::Utc::now().to_rfc3339()
             })),
-            _ => Ok(self.not_found()),
         };
 
         match response {
+            Ok(body) if msgpack => Ok(self.msgpack_response(&body)),
             Ok(body) => Ok(json!({
                 "statusCode": 200,
-                "headers": {
-                    "Content-Type": "application/json",
-                    "Access-Control-Allow-Origin": "*",
-                    "Access-Control-Allow-Methods": "GET,POST,PUT,DELETE,OPTIONS",
-                    "Access-Control-Allow-Headers": "Content-Type,Authorization"
-                },
+                "headers": self.headers(Some("application/json")),
                 "body": serde_json::to_string(&body).unwrap_or_else(|_| "{}".to_string())
             })),
-            Err(e) => {
-                error!("API Error: {}", e);
-                Ok(json!({
-                    "statusCode": 500,
-                    "headers": {
-                        "Content-Type": "application/json"
-                    },
-                    "body": serde_json::to_string(&ApiError::InternalError { message: e.to_string() }.to_response())
-                        .unwrap_or_else(|_| r#"{"error":"InternalError","message":"Unknown error"}"#.to_string())
-                }))
-            }
+            Err(e) => Ok(self.caller_error_response(e, is_admin, caller.as_ref())),
         }
     }
 
     // MVP Endpoint implementations
-    async fn get_available_teams(&self) -> Result<Value, anyhow::Error> {
+    async fn get_available_teams(&self) -> Result<Value, ApiError> {
         let response = self.battle_handler.get_available_teams().await?;
-        Ok(serde_json::to_value(response)?)
+        json_body(response)
+    }
+
+    async fn get_available_teams_full(&self) -> Result<Value, ApiError> {
+        let response = self.battle_handler.get_available_teams_full().await?;
+        json_body(response)
     }
 
-    async fn get_npc_opponents(&self) -> Result<Value, anyhow::Error> {
+    async fn get_npc_opponents(&self) -> Result<Value, ApiError> {
         let response = self.battle_handler.get_npc_opponents().await?;
-        Ok(serde_json::to_value(response)?)
+        json_body(response)
     }
 
-    async fn create_mvp_battle(&self, payload: Value) -> Result<Value, anyhow::Error> {
-        let body = payload.get("body")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("Missing request body"))?;
+    async fn get_game_states(&self) -> Result<Value, ApiError> {
+        let response = self.battle_handler.get_game_states().await?;
+        json_body(response)
+    }
 
-        let request: CreateMvpBattleRequest = serde_json::from_str(body)
-            .map_err(|e| anyhow::anyhow!("Invalid request format: {}", e))?;
+    async fn create_mvp_battle(&self, payload: Value) -> Result<Value, ApiError> {
+        let request: CreateMvpBattleRequest = parse_body(&payload)?;
 
         let response = self.battle_handler.create_mvp_battle(request).await?;
-        Ok(serde_json::to_value(response)?)
+        json_body(response)
     }
 
-    async fn submit_action(&self, payload: Value) -> Result<Value, anyhow::Error> {
-        // Extract battle_id from path
-        let raw_path = payload.get("rawPath")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("Missing path"))?;
-        let path = raw_path.strip_prefix("/prod").unwrap_or(raw_path);
-        
-        let battle_id_str = path
-            .strip_prefix("/battles/")
-            .and_then(|s| s.strip_suffix("/action"))
-            .ok_or_else(|| anyhow::anyhow!("Invalid path format"))?;
+    async fn create_battle(&self, payload: Value) -> Result<Value, ApiError> {
+        let request: CreateBattleRequest = parse_body(&payload)?;
 
-        let battle_id = BattleId(battle_id_str.parse()
-            .map_err(|e| anyhow::anyhow!("Invalid battle_id: {}", e))?);
+        let response = self.battle_handler.create_battle(request).await?;
+        json_body(response)
+    }
 
-        let body = payload.get("body")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("Missing request body"))?;
+    async fn playout(&self, payload: Value) -> Result<Value, ApiError> {
+        let request: PlayoutRequest = parse_body(&payload)?;
+
+        let response = self.battle_handler.playout(request).await?;
+        json_body(response)
+    }
+
+    async fn quickplay(&self, payload: Value) -> Result<Value, ApiError> {
+        let request: QuickplayRequest = parse_body(&payload)?;
+
+        let response = self.battle_handler.quickplay(request).await?;
+        json_body(response)
+    }
+
+    async fn get_recent_battles(&self, payload: Value) -> Result<Value, ApiError> {
+        const DEFAULT_LIMIT: usize = 20;
+        const MAX_LIMIT: usize = 100;
+
+        let limit = parse_query_param::<usize>(&payload, "limit")?
+            .unwrap_or(DEFAULT_LIMIT)
+            .clamp(1, MAX_LIMIT);
+
+        let response = self.battle_handler.get_recent_battles(limit).await?;
+        json_body(response)
+    }
+
+    async fn get_team_matchup(&self, payload: Value) -> Result<Value, ApiError> {
+        let request: TeamMatchupRequest = parse_body(&payload)?;
+
+        let response = self.battle_handler.get_team_matchup(request).await?;
+        json_body(response)
+    }
+
+    async fn abandon_all_battles(&self, params: &PathParams, payload: &Value) -> Result<Value, ApiError> {
+        let player_id = PlayerId(path_param(params, "id")?.to_string());
+
+        let authenticated_player_id = authenticated_player_id(payload);
+
+        let request = AbandonAllRequest { player_id, authenticated_player_id };
+        let response = self.battle_handler.abandon_all_battles(request).await?;
+        json_body(response)
+    }
+
+    async fn list_player_battles(&self, params: &PathParams, payload: &Value) -> Result<Value, ApiError> {
+        const DEFAULT_LIMIT: usize = 50;
+        const MAX_LIMIT: usize = 100;
+
+        let player_id = PlayerId(path_param(params, "id")?.to_string());
+        let limit = parse_query_param::<usize>(payload, "limit")?
+            .unwrap_or(DEFAULT_LIMIT)
+            .clamp(1, MAX_LIMIT);
+
+        let authenticated_player_id = authenticated_player_id(payload);
+
+        let request = ListPlayerBattlesRequest { player_id, limit, authenticated_player_id };
+        let response = self.battle_handler.list_player_battles(request).await?;
+        json_body(response)
+    }
+
+    async fn get_player_history(&self, params: &PathParams, payload: &Value) -> Result<Value, ApiError> {
+        const DEFAULT_LIMIT: usize = 20;
+        const MAX_LIMIT: usize = 100;
+
+        let player_id = PlayerId(path_param(params, "id")?.to_string());
+        let limit = parse_query_param::<usize>(payload, "limit")?
+            .unwrap_or(DEFAULT_LIMIT)
+            .clamp(1, MAX_LIMIT);
+        let cursor = parse_query_param::<HistoryCursor>(payload, "cursor")?;
+
+        let authenticated_player_id = authenticated_player_id(payload);
+
+        let request = PlayerHistoryRequest { player_id, limit, cursor, authenticated_player_id };
+        let response = self.battle_handler.get_player_history(request).await?;
+        json_body(response)
+    }
 
-        let mut action_request: SubmitActionRequest = serde_json::from_str(body)
-            .map_err(|e| anyhow::anyhow!("Invalid request format: {}", e))?;
+    async fn get_batch_events(&self, payload: Value) -> Result<Value, ApiError> {
+        let request: BatchEventsRequest = parse_body(&payload)?;
+
+        let response = self.battle_handler.get_batch_events(request).await?;
+        json_body(response)
+    }
+
+    async fn create_match(&self, payload: Value) -> Result<Value, ApiError> {
+        let request: CreateMatchRequest = parse_body(&payload)?;
+
+        let response = self.battle_handler.create_match(request).await?;
+        json_body(response)
+    }
+
+    async fn get_match(&self, params: &PathParams) -> Result<Value, ApiError> {
+        let match_id_str = path_param(params, "id")?;
+
+        let match_id = MatchId(match_id_str.parse()
+            .map_err(|e| ApiError::bad_request(format!("Invalid match_id: {}", e)))?);
+
+        let response = self.battle_handler.get_match(match_id).await?;
+        json_body(response)
+    }
+
+    async fn validate_team(&self, payload: Value) -> Result<Value, ApiError> {
+        let request: TeamValidationRequest = parse_body(&payload)?;
+
+        let response = self.battle_handler.validate_team(request).await?;
+        json_body(response)
+    }
+
+    async fn preview_team(&self, payload: Value) -> Result<Value, ApiError> {
+        let request: TeamPreviewRequest = parse_body(&payload)?;
+
+        let response = self.battle_handler.preview_team(request).await?;
+        json_body(response)
+    }
+
+    async fn get_species_info(&self, params: &PathParams) -> Result<Value, ApiError> {
+        let species_str = path_param(params, "species")?;
+
+        let species = parse_enum_name(species_str)
+            .map_err(|_| ApiError::SpeciesNotFound { species: species_str.to_string() })?;
+
+        let response = self.battle_handler.get_species_info(species).await?;
+        json_body(response)
+    }
+
+    async fn get_move_info(&self, params: &PathParams) -> Result<Value, ApiError> {
+        let move_str = path_param(params, "move")?;
+
+        let move_ = parse_enum_name(move_str)
+            .map_err(|_| ApiError::MoveNotFound { move_name: move_str.to_string() })?;
+
+        let response = self.battle_handler.get_move_info(move_).await?;
+        json_body(response)
+    }
+
+    async fn get_learnable_moves(&self, params: &PathParams) -> Result<Value, ApiError> {
+        let species_str = path_param(params, "species")?;
+
+        let species = parse_enum_name(species_str)
+            .map_err(|_| ApiError::SpeciesNotFound { species: species_str.to_string() })?;
+
+        let response = self.battle_handler.get_learnable_moves(species).await?;
+        json_body(response)
+    }
+
+    async fn get_battle_by_number(&self, params: &PathParams, payload: &Value) -> Result<Value, ApiError> {
+        let battle_number = path_param(params, "n")?
+            .parse::<u64>()
+            .map_err(|_| ApiError::bad_request("Invalid battle number"))?;
+
+        let authenticated_player_id = authenticated_player_id(payload);
+
+        let request = GetBattleByNumberRequest { battle_number, authenticated_player_id, is_admin: self.is_admin(payload) };
+        let response = self.battle_handler.get_battle_by_number(request).await?;
+        json_body(response)
+    }
+
+    async fn delete_battle(&self, params: &PathParams, payload: &Value) -> Result<(), ApiError> {
+        let battle_id = battle_id_param(params)?;
+        // Deleting is destructive, so the player must be named rather than defaulting to the MVP player
+        let player_id = query_param(payload, "player_id")
+            .map(|s| PlayerId(s.to_string()))
+            .ok_or_else(|| ApiError::bad_request("player_id is required"))?;
+        let authenticated_player_id = authenticated_player_id(payload);
+
+        let request = DeleteBattleRequest { battle_id, player_id, authenticated_player_id };
+        self.battle_handler.delete_battle(request).await
+    }
+
+    async fn get_effectiveness(&self, payload: Value) -> Result<Value, ApiError> {
+        let required = |name: &str| query_param(&payload, name)
+            .ok_or_else(|| ApiError::bad_request(format!("Missing {} query parameter", name)));
+
+        let move_type_str = required("move_type")?;
+        let defender_str = required("defender_species")?;
+
+        let move_type = parse_enum_name(move_type_str)
+            .map_err(|_| ApiError::bad_request(format!("Unknown move_type: {}", move_type_str)))?;
+        let defender_species = parse_enum_name(defender_str)
+            .map_err(|_| ApiError::bad_request(format!("Unknown defender_species: {}", defender_str)))?;
+
+        let response = self.battle_handler.get_effectiveness(move_type, defender_species).await?;
+        json_body(response)
+    }
+
+    async fn submit_action(&self, params: &PathParams, payload: Value) -> Result<Value, ApiError> {
+        let battle_id = battle_id_param(params)?;
+
+        let mut action_request: SubmitActionRequest = parse_body(&payload)?;
 
         // Override battle_id from URL
         action_request.battle_id = battle_id;
 
+        // Authenticated identity comes from headers only, so the handler can cross-check the body
+        action_request.authenticated_player_id = authenticated_player_id(&payload);
+
         let response = self.battle_handler.submit_action(action_request).await?;
-        Ok(serde_json::to_value(response)?)
+        json_body(response)
     }
 
-    async fn get_battle_state(&self, payload: Value) -> Result<Value, anyhow::Error> {
-        let (battle_id, player_id) = self.extract_battle_and_player_from_path(payload)?;
-        
-        let request = GetBattleStateRequest { battle_id, player_id };
+    async fn forfeit(&self, params: &PathParams, payload: Value) -> Result<Value, ApiError> {
+        let (battle_id, player_id) = self.extract_battle_and_player(params, &payload)?;
+
+        let authenticated_player_id = authenticated_player_id(&payload);
+
+        let request = ForfeitRequest { battle_id, player_id, authenticated_player_id };
+        let response = self.battle_handler.forfeit(request).await?;
+        json_body(response)
+    }
+
+    async fn rematch(&self, params: &PathParams, payload: Value) -> Result<Value, ApiError> {
+        let (battle_id, player_id) = self.extract_battle_and_player(params, &payload)?;
+
+        let authenticated_player_id = authenticated_player_id(&payload);
+
+        let request = RematchRequest { battle_id, player_id, authenticated_player_id };
+        let response = self.battle_handler.rematch(request).await?;
+        json_body(response)
+    }
+
+    async fn get_battle_state(&self, params: &PathParams, payload: Value) -> Result<Value, ApiError> {
+        let (battle_id, player_id) = self.extract_battle_and_player(params, &payload)?;
+
+        let strict_info = query_param(&payload, "info_mode").is_some_and(|v| v == "strict");
+        let debug = query_param(&payload, "debug").is_some_and(|v| v == "true");
+        let observer_token = query_param(&payload, "observer_token").map(str::to_string);
+
+        let request = GetBattleStateRequest {
+            battle_id, player_id, strict_info, debug, is_admin: self.is_admin(&payload), observer_token,
+        };
         let response = self.battle_handler.get_battle_state(request).await?;
-        Ok(serde_json::to_value(response)?)
+        json_body(response)
     }
 
-    async fn get_valid_actions(&self, payload: Value) -> Result<Value, anyhow::Error> {
-        let (battle_id, player_id) = self.extract_battle_and_player_from_path(payload)?;
+    async fn get_valid_actions(&self, params: &PathParams, payload: Value) -> Result<Value, ApiError> {
+        let (battle_id, player_id) = self.extract_battle_and_player(params, &payload)?;
         
         let request = GetValidActionsRequest { battle_id, player_id };
         let response = self.battle_handler.get_valid_actions(request).await?;
-        Ok(serde_json::to_value(response)?)
+        json_body(response)
+    }
+
+    async fn get_move_pp(&self, params: &PathParams, payload: Value) -> Result<Value, ApiError> {
+        let (battle_id, player_id) = self.extract_battle_and_player(params, &payload)?;
+
+        let request = GetMovePpRequest { battle_id, player_id };
+        let response = self.battle_handler.get_move_pp(request).await?;
+        json_body(response)
     }
 
-    async fn get_team_info(&self, payload: Value) -> Result<Value, anyhow::Error> {
-        let (battle_id, player_id) = self.extract_battle_and_player_from_path(payload)?;
+    async fn get_team_info(&self, params: &PathParams, payload: Value) -> Result<Value, ApiError> {
+        let (battle_id, player_id) = self.extract_battle_and_player(params, &payload)?;
         
         let request = GetTeamInfoRequest { battle_id, player_id };
         let response = self.battle_handler.get_team_info(request).await?;
-        Ok(serde_json::to_value(response)?)
+        json_body(response)
     }
 
-    async fn get_battle_events(&self, payload: Value) -> Result<Value, anyhow::Error> {
-        let (battle_id, player_id) = self.extract_battle_and_player_from_path(payload.clone())?;
-        
-        // Extract last_turns query parameter
-        let query_params = payload.get("queryStringParameters")
-            .and_then(|v| v.as_object());
-        
-        let last_turns = query_params
-            .and_then(|params| params.get("last_turns"))
-            .and_then(|v| v.as_str())
-            .and_then(|s| s.parse::<u32>().ok());
+    async fn get_battle_events(&self, params: &PathParams, payload: Value) -> Result<Value, ApiError> {
+        let (battle_id, player_id) = self.extract_battle_and_player(params, &payload)?;
         
-        let request = GetBattleEventsRequest { battle_id, player_id, last_turns };
+        let last_turns = parse_query_param::<u32>(&payload, "last_turns")?;
+
+        // Extract comma-separated event_types query parameter
+        let event_types = query_param(&payload, "event_types")
+            .map(|s| s.split(',').map(|t| t.trim().to_string()).filter(|t| !t.is_empty()).collect());
+
+        let since_turn = parse_query_param::<u32>(&payload, "since_turn")?;
+
+        let include_markers = query_param(&payload, "include_markers").is_some_and(|v| v == "true");
+
+        let offset = parse_query_param::<usize>(&payload, "offset")?;
+        let limit = parse_query_param::<usize>(&payload, "limit")?;
+
+        let request = GetBattleEventsRequest {
+            battle_id, player_id, last_turns, since_turn, event_types, include_markers, offset, limit,
+        };
         let response = self.battle_handler.get_battle_events(request).await?;
-        Ok(serde_json::to_value(response)?)
+        json_body(response)
     }
 
-    // Helper method to extract battle_id and player_id from path and query params
-    fn extract_battle_and_player_from_path(&self, payload: Value) -> Result<(BattleId, PlayerId), anyhow::Error> {
-        // Extract battle_id from path
-        let raw_path = payload.get("rawPath")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("Missing path"))?;
-        let path = raw_path.strip_prefix("/prod").unwrap_or(raw_path);
-        
-        let battle_id_str = path
-            .strip_prefix("/battles/")
-            .and_then(|s| s.split('/').next())
-            .ok_or_else(|| anyhow::anyhow!("Invalid path format"))?;
+    async fn get_tick_status(&self, params: &PathParams, payload: Value) -> Result<Value, ApiError> {
+        let (battle_id, player_id) = self.extract_battle_and_player(params, &payload)?;
 
-        let battle_id = BattleId(battle_id_str.parse()
-            .map_err(|e| anyhow::anyhow!("Invalid battle_id: {}", e))?);
+        let request = GetTickStatusRequest { battle_id, player_id };
+        let response = self.battle_handler.get_tick_status(request).await?;
+        json_body(response)
+    }
 
-        // Extract player_id from query parameters
-        let query_params = payload.get("queryStringParameters")
-            .and_then(|v| v.as_object());
+    async fn get_showdown_log(&self, params: &PathParams, payload: Value) -> Result<Value, ApiError> {
+        // Public endpoint: the player_id query parameter is ignored
+        let (battle_id, _player_id) = self.extract_battle_and_player(params, &payload)?;
 
-        let player_id = query_params
-            .and_then(|params| params.get("player_id"))
-            .and_then(|v| v.as_str())
+        let response = self.battle_handler.get_showdown_log(battle_id).await?;
+        json_body(response)
+    }
+
+    async fn get_battle_result(&self, params: &PathParams, payload: Value) -> Result<Value, ApiError> {
+        // Public endpoint: the player_id query parameter is ignored
+        let (battle_id, _player_id) = self.extract_battle_and_player(params, &payload)?;
+
+        let response = self.battle_handler.get_battle_result(battle_id).await?;
+        json_body(response)
+    }
+
+    async fn get_battle_setup(&self, params: &PathParams, payload: Value) -> Result<Value, ApiError> {
+        let (battle_id, player_id) = self.extract_battle_and_player(params, &payload)?;
+
+        let request = GetBattleSetupRequest { battle_id, player_id };
+        let response = self.battle_handler.get_battle_setup(request).await?;
+        json_body(response)
+    }
+
+    async fn get_damage_report(&self, params: &PathParams, payload: Value) -> Result<Value, ApiError> {
+        let (battle_id, player_id) = self.extract_battle_and_player(params, &payload)?;
+
+        let request = GetDamageReportRequest { battle_id, player_id };
+        let response = self.battle_handler.get_damage_report(request).await?;
+        json_body(response)
+    }
+
+    async fn get_battle_estimate(&self, params: &PathParams, payload: Value) -> Result<Value, ApiError> {
+        let (battle_id, player_id) = self.extract_battle_and_player(params, &payload)?;
+
+        let request = GetBattleEstimateRequest { battle_id, player_id };
+        let response = self.battle_handler.get_battle_estimate(request).await?;
+        json_body(response)
+    }
+
+    async fn get_battle_replay(&self, params: &PathParams, payload: Value) -> Result<Value, ApiError> {
+        let (battle_id, player_id) = self.extract_battle_and_player(params, &payload)?;
+
+        let request = GetBattleReplayRequest { battle_id, player_id };
+        let response = self.battle_handler.replay_battle(request).await?;
+        json_body(response)
+    }
+
+    async fn get_spectator_view(&self, params: &PathParams, payload: Value) -> Result<Value, ApiError> {
+        let (battle_id, player_id) = self.extract_battle_and_player(params, &payload)?;
+
+        let request = GetSpectatorViewRequest { battle_id, player_id, is_admin: self.is_admin(&payload) };
+        let response = self.battle_handler.get_spectator_view(request).await?;
+        json_body(response)
+    }
+
+    async fn get_battle_changes(&self, params: &PathParams, payload: Value) -> Result<Value, ApiError> {
+        let (battle_id, player_id) = self.extract_battle_and_player(params, &payload)?;
+        let since_turn = parse_query_param::<u32>(&payload, "since_turn")?
+            .ok_or_else(|| ApiError::bad_request("since_turn is required"))?;
+
+        let request = GetBattleChangesRequest { battle_id, player_id, since_turn };
+        let response = self.battle_handler.get_battle_changes(request).await?;
+        json_body(response)
+    }
+
+    async fn set_paused(&self, params: &PathParams, payload: Value, paused: bool) -> Result<Value, ApiError> {
+        let (battle_id, player_id) = self.extract_battle_and_player(params, &payload)?;
+
+        let request = SetPausedRequest { battle_id, player_id, paused };
+        let response = self.battle_handler.set_paused(request).await?;
+        json_body(response)
+    }
+
+    async fn get_turn_log(&self, params: &PathParams, payload: Value) -> Result<Value, ApiError> {
+        let (battle_id, player_id) = self.extract_battle_and_player(params, &payload)?;
+
+        let turn_number = path_param(params, "n")?
+            .parse::<u32>()
+            .map_err(|e| ApiError::bad_request(format!("Invalid turn number: {}", e)))?;
+
+        let request = GetTurnLogRequest { battle_id, player_id, turn_number };
+        let response = self.battle_handler.get_turn_log(request).await?;
+        json_body(response)
+    }
+
+    async fn get_move_usage(&self, payload: Value) -> Result<Value, ApiError> {
+        self.require_admin(&payload)?;
+
+        let response = self.battle_handler.get_move_usage().await?;
+        json_body(response)
+    }
+
+    async fn get_action_queue(&self, params: &PathParams, payload: Value) -> Result<Value, ApiError> {
+        self.require_admin(&payload)?;
+
+        let battle_id = battle_id_param(params)?;
+
+        let request = GetActionQueueRequest { battle_id };
+        let response = self.battle_handler.get_action_queue(request).await?;
+        json_body(response)
+    }
+
+    async fn set_battle_state(&self, params: &PathParams, payload: &Value) -> Result<Value, ApiError> {
+        self.require_admin(payload)?;
+
+        let battle_id = battle_id_param(params)?;
+
+        let mut request: SetBattleStateRequest = parse_body(payload)?;
+        request.battle_id = battle_id;
+
+        let response = self.battle_handler.set_battle_state(request).await?;
+        json_body(response)
+    }
+
+    // Helper method to check the x-admin-key header against ADMIN_API_KEY
+    fn require_admin(&self, payload: &Value) -> Result<(), ApiError> {
+        if self.is_admin(payload) {
+            Ok(())
+        } else {
+            Err(ApiError::AuthRequired)
+        }
+    }
+
+    // Helper method for endpoints that show admins more without requiring the key
+    fn is_admin(&self, payload: &Value) -> bool {
+        let provided = payload.get("headers")
+            .and_then(|h| h.get("x-admin-key"))
+            .and_then(|v| v.as_str());
+
+        matches!((&self.admin_key, provided), (Some(expected), Some(provided)) if expected == provided)
+    }
+
+    // Helper method to extract battle_id from the path params and player_id from query params
+    fn extract_battle_and_player(&self, params: &PathParams, payload: &Value) -> Result<(BattleId, PlayerId), ApiError> {
+        let battle_id = battle_id_param(params)?;
+
+        // Extract player_id from query parameters
+        let player_id = query_param(payload, "player_id")
             .map(|s| PlayerId(s.to_string()))
             .unwrap_or(PlayerId("player_1".to_string())); // Default to player_1 for MVP
 
         Ok((battle_id, player_id))
     }
 
+    // Error envelope for a handler error; participant ids are logged in full but redacted for strangers
+    fn caller_error_response(&self, e: ApiError, is_admin: bool, caller: Option<&PlayerId>) -> Value {
+        error!("API Error: {}", e);
+        error_envelope(e.redact_participants(is_admin, caller))
+    }
+
+    // Error envelope carrying the error's own status code and response body
+    fn error_response(&self, e: ApiError) -> Value {
+        error!("API Error: {}", e);
+        error_envelope(e)
+    }
+
+    // Response headers, with the CORS headers unless they are disabled
+    fn headers(&self, content_type: Option<&str>) -> Value {
+        let mut headers = serde_json::Map::new();
+        if let Some(content_type) = content_type {
+            headers.insert("Content-Type".to_string(), json!(content_type));
+        }
+        if self.enable_cors {
+            headers.insert("Access-Control-Allow-Origin".to_string(), json!("*"));
+            headers.insert("Access-Control-Allow-Methods".to_string(), json!("GET,POST,PUT,DELETE,OPTIONS"));
+            headers.insert("Access-Control-Allow-Headers".to_string(), json!("Content-Type,Authorization"));
+        }
+        Value::Object(headers)
+    }
+
+    // Success envelope with a MessagePack body; API Gateway needs binary bodies base64-encoded
+    fn msgpack_response(&self, body: &Value) -> Value {
+        match rmp_serde::to_vec_named(body) {
+            Ok(bytes) => json!({
+                "statusCode": 200,
+                "headers": self.headers(Some("application/msgpack")),
+                "body": BASE64.encode(bytes),
+                "isBase64Encoded": true
+            }),
+            Err(e) => self.error_response(ApiError::InternalError {
+                message: format!("Failed to encode MessagePack response: {}", e),
+            }),
+        }
+    }
+
+    fn no_content(&self) -> Value {
+        json!({
+            "statusCode": 204,
+            "headers": self.headers(None),
+            "body": ""
+        })
+    }
+
     fn not_found(&self) -> Value {
         json!({
             "statusCode": 404,
             "headers": {
                 "Content-Type": "application/json"
             },
-            "body": serde_json::to_string(&ApiError::BadRequest { message: "Endpoint not found".to_string() }.to_response())
-                .unwrap_or_else(|_| r#"{"error":"NotFound","message":"Endpoint not found"}"#.to_string())
+            "body": serde_json::to_string(&ApiErrorResponse {
+                error: "NOT_FOUND".to_string(),
+                message: "Endpoint not found".to_string(),
+                status_code: 404,
+            }).unwrap_or_else(|_| r#"{"error":"NOT_FOUND","message":"Endpoint not found"}"#.to_string())
         })
     }
 }
 
 pub async fn create_router() -> Result<Router, Error> {
     Router::new().await
-}
\ No newline at end of file
+}
+
+/// A parameter the matched route pattern captured
+fn path_param<'a>(params: &'a PathParams, name: &str) -> Result<&'a str, ApiError> {
+    params.get(name).ok_or_else(|| ApiError::bad_request("Invalid path format"))
+}
+
+/// JSON envelope carrying the error's own status code and response body
+fn error_envelope(e: ApiError) -> Value {
+    json!({
+        "statusCode": e.status_code(),
+        "headers": {
+            "Content-Type": "application/json"
+        },
+        "body": serde_json::to_string(&e.to_response())
+            .unwrap_or_else(|_| r#"{"error":"InternalError","message":"Unknown error"}"#.to_string())
+    })
+}
+
+/// The battle id captured as `{id}`
+fn battle_id_param(params: &PathParams) -> Result<BattleId, ApiError> {
+    Ok(BattleId(path_param(params, "id")?.parse()
+        .map_err(|e| ApiError::bad_request(format!("Invalid battle_id: {}", e)))?))
+}
+
+/// Whether the client's `Accept` header lists MessagePack; errors are always sent as JSON
+fn accepts_msgpack(payload: &Value) -> bool {
+    payload.get("headers")
+        .and_then(|h| h.get("accept"))
+        .and_then(|v| v.as_str())
+        .is_some_and(|accept| accept.split(',').any(|media_type| {
+            media_type.split(';').next().unwrap_or("").trim().eq_ignore_ascii_case("application/msgpack")
+        }))
+}
+
+/// A query string parameter; an empty value counts as absent
+fn query_param<'a>(payload: &'a Value, name: &str) -> Option<&'a str> {
+    payload.get("queryStringParameters")
+        .and_then(|params| params.get(name))
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+}
+
+/// The caller's player id from the `x-player-id` header, which handlers cross-check against the request
+fn authenticated_player_id(payload: &Value) -> Option<PlayerId> {
+    payload.get("headers")
+        .and_then(|h| h.get("x-player-id"))
+        .and_then(|v| v.as_str())
+        .map(|id| PlayerId(id.to_string()))
+}
+
+/// A typed query string parameter; absent or empty is None, any other value must parse
+fn parse_query_param<T>(payload: &Value, name: &str) -> Result<Option<T>, ApiError>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    query_param(payload, name)
+        .map(|s| s.parse().map_err(|e| ApiError::bad_request(format!("Invalid {}: {}", name, e))))
+        .transpose()
+}
+
+/// Deserialize the JSON request body
+fn parse_body<T: serde::de::DeserializeOwned>(payload: &Value) -> Result<T, ApiError> {
+    let body = payload.get("body")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| ApiError::bad_request("Missing request body"))?;
+
+    serde_json::from_str(body)
+        .map_err(|e| ApiError::bad_request(format!("Invalid request format: {}", e)))
+}
+
+/// Serialize a handler response as the envelope body; failing here is a server bug, not a bad request
+fn json_body<T: serde::Serialize>(response: T) -> Result<Value, ApiError> {
+    serde_json::to_value(response)
+        .map_err(|e| ApiError::InternalError { message: format!("Failed to serialize response: {}", e) })
+}
+
+/// Parse an engine enum from its serde name, also accepting lowercase input (e.g. "pikachu" for "Pikachu")
+fn parse_enum_name<T: serde::de::DeserializeOwned>(name: &str) -> Result<T, serde_json::Error> {
+    let mut chars = name.chars();
+    let canonical: String = chars.next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default();
+    serde_json::from_value(Value::String(canonical))
+}
@@ -0,0 +1,293 @@
+use serde::Serialize;
+
+/// Handler a matched route dispatches to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endpoint {
+    Index,
+    AvailableTeams,
+    AvailableTeamsFull,
+    NpcOpponents,
+    GameStates,
+    CreateMvpBattle,
+    CreateBattle,
+    Quickplay,
+    RecentBattles,
+    TeamMatchup,
+    TeamPreview,
+    TeamValidate,
+    Effectiveness,
+    CreateMatch,
+    BatchEvents,
+    AbandonAll,
+    PlayerBattles,
+    PlayerHistory,
+    GetMatch,
+    SpeciesInfo,
+    LearnableMoves,
+    MoveInfo,
+    BattleByNumber,
+    SubmitAction,
+    Forfeit,
+    Rematch,
+    BattleState,
+    ValidActions,
+    MovePp,
+    TeamInfo,
+    BattleEvents,
+    TurnLog,
+    TickStatus,
+    BattleResult,
+    ShowdownLog,
+    BattleSetup,
+    DamageReport,
+    BattleEstimate,
+    BattleReplay,
+    BattleChanges,
+    Spectate,
+    PauseBattle,
+    ResumeBattle,
+    DeleteBattle,
+    MoveUsage,
+    ActionQueue,
+    SetBattleState,
+    Playout,
+    Health,
+}
+
+impl Endpoint {
+    /// Endpoints whose verbose responses may be sent as MessagePack when the client accepts it
+    pub fn supports_msgpack(self) -> bool {
+        matches!(self, Endpoint::BattleState | Endpoint::BattleEvents | Endpoint::BatchEvents | Endpoint::TurnLog)
+    }
+
+    /// Test harness endpoints, served only when `TEST_ENDPOINTS_ENABLED` is set
+    pub fn is_test_only(self) -> bool {
+        matches!(self, Endpoint::Playout)
+    }
+}
+
+/// One entry of the route table
+#[derive(Debug, Clone, Copy)]
+pub struct Route {
+    pub method: &'static str,
+    pub path: &'static str, // Path pattern; `{name}` matches one non-empty segment, `{name:uuid}`/`{name:int}` also check its form
+    pub description: &'static str,
+    pub admin: bool, // Requires the `x-admin-key` header
+    pub endpoint: Endpoint,
+}
+
+const fn route(
+    method: &'static str,
+    path: &'static str,
+    description: &'static str,
+    endpoint: Endpoint,
+) -> Route {
+    Route { method, path, description, admin: false, endpoint }
+}
+
+const fn admin_route(
+    method: &'static str,
+    path: &'static str,
+    description: &'static str,
+    endpoint: Endpoint,
+) -> Route {
+    Route { method, path, description, admin: true, endpoint }
+}
+
+/// Every route the API serves, tried in order; the first match wins
+/// Literal paths come before patterns whose parameters they would otherwise fill
+pub const ROUTES: &[Route] = &[
+    route("GET", "/",
+        "List the available endpoints", Endpoint::Index),
+    // MVP Endpoints
+    route("GET", "/available_teams",
+        "List prefab teams", Endpoint::AvailableTeams),
+    route("GET", "/available_teams/full",
+        "List prefab teams with their full rosters", Endpoint::AvailableTeamsFull),
+    route("GET", "/npc_opponents",
+        "List AI opponents with difficulty levels", Endpoint::NpcOpponents),
+    route("GET", "/game_states",
+        "List every battle game state with its meaning", Endpoint::GameStates),
+    route("POST", "/battles",
+        "Create a battle between a player and an NPC", Endpoint::CreateMvpBattle),
+    route("POST", "/battles/custom",
+        "Create a two-player battle with fully custom teams", Endpoint::CreateBattle),
+    route("POST", "/battles/quickplay",
+        "Create an NPC battle and submit the first action in one write", Endpoint::Quickplay),
+    route("GET", "/battles/recent",
+        "Public feed of recently finished battles", Endpoint::RecentBattles),
+    route("POST", "/teams/matchup",
+        "Compare two teams' type matchups before battling", Endpoint::TeamMatchup),
+    route("POST", "/teams/preview",
+        "Preview a custom team's stats and movesets", Endpoint::TeamPreview),
+    route("POST", "/teams/validate",
+        "Check a custom team is legal without creating anything, listing every problem", Endpoint::TeamValidate),
+    route("GET", "/effectiveness",
+        "Type effectiveness of an attacking type against a species", Endpoint::Effectiveness),
+    route("POST", "/matches",
+        "Create a best-of-N match and its first game", Endpoint::CreateMatch),
+    route("POST", "/events/batch",
+        "Events for several battles at once", Endpoint::BatchEvents),
+    route("POST", "/players/{id}/abandon_all",
+        "Forfeit every unfinished battle the player is in", Endpoint::AbandonAll),
+    route("GET", "/players/{id}/battles",
+        "List the player's battles with their timestamps", Endpoint::PlayerBattles),
+    route("GET", "/players/{id}/history",
+        "The player's finished battles with results, newest first, paginated by cursor", Endpoint::PlayerHistory),
+    route("GET", "/matches/{id:uuid}",
+        "Series score and per-game results", Endpoint::GetMatch),
+    route("GET", "/species/{species}",
+        "A species' types and base stats", Endpoint::SpeciesInfo),
+    route("GET", "/species/{species}/moves",
+        "List a species' learnable moves", Endpoint::LearnableMoves),
+    route("GET", "/moves/{move}",
+        "A move's type, power, accuracy and PP", Endpoint::MoveInfo),
+    route("GET", "/battles/by-number/{n:int}",
+        "Resolve a short battle number to its battle id, for its participants", Endpoint::BattleByNumber),
+    route("POST", "/battles/{id:uuid}/action",
+        "Submit a player action and process the turn", Endpoint::SubmitAction),
+    route("POST", "/battles/{id:uuid}/forfeit",
+        "Forfeit immediately and get the final battle state", Endpoint::Forfeit),
+    route("POST", "/battles/{id:uuid}/rematch",
+        "Start a new NPC battle with a finished battle's setup", Endpoint::Rematch),
+    route("GET", "/battles/{id:uuid}/state",
+        "Current battle state for a player", Endpoint::BattleState),
+    route("GET", "/battles/{id:uuid}/valid_actions",
+        "List available moves and switches", Endpoint::ValidActions),
+    route("GET", "/battles/{id:uuid}/pp",
+        "PP remaining on the active Pokemon's moves", Endpoint::MovePp),
+    route("GET", "/battles/{id:uuid}/team_info",
+        "The player's team details", Endpoint::TeamInfo),
+    route("GET", "/battles/{id:uuid}/events",
+        "Turn logs as seen by the requesting player", Endpoint::BattleEvents),
+    route("GET", "/battles/{id:uuid}/turns/{n:int}",
+        "A single turn's log by turn number", Endpoint::TurnLog),
+    route("GET", "/battles/{id:uuid}/tick",
+        "Poll whether an asynchronously submitted turn has resolved", Endpoint::TickStatus),
+    route("GET", "/battles/{id:uuid}/result",
+        "Public outcome of a finished battle", Endpoint::BattleResult),
+    route("GET", "/battles/{id:uuid}/showdown_log",
+        "A finished battle in Pokemon Showdown protocol format, for replay viewers", Endpoint::ShowdownLog),
+    route("GET", "/battles/{id:uuid}/setup",
+        "The setup the battle was created with", Endpoint::BattleSetup),
+    route("GET", "/battles/{id:uuid}/damage_report",
+        "Per-Pokemon damage dealt and received in a finished battle", Endpoint::DamageReport),
+    route("GET", "/battles/{id:uuid}/estimate",
+        "Rough estimate of turns remaining from the damage dealt so far", Endpoint::BattleEstimate),
+    route("GET", "/battles/{id:uuid}/replay",
+        "Re-simulate the battle from its recorded turns and check it matches the stored state", Endpoint::BattleReplay),
+    route("GET", "/battles/{id:uuid}/changes",
+        "Only the Pokemon whose HP, status, PP or active status changed since a turn", Endpoint::BattleChanges),
+    route("GET", "/battles/{id:uuid}/spectate",
+        "Both full teams for a finished battle, e.g. for a replay UI", Endpoint::Spectate),
+    route("POST", "/battles/{id:uuid}/pause",
+        "Pause a battle; actions are rejected until it is resumed", Endpoint::PauseBattle),
+    route("POST", "/battles/{id:uuid}/resume",
+        "Resume a paused battle", Endpoint::ResumeBattle),
+    route("DELETE", "/battles/{id:uuid}",
+        "Delete a battle", Endpoint::DeleteBattle),
+    // Admin Endpoints
+    admin_route("GET", "/analytics/move_usage",
+        "Aggregate move usage counts across all battles", Endpoint::MoveUsage),
+    admin_route("GET", "/admin/battles/{id:uuid}/action_queue",
+        "Queued actions and why the turn has or has not resolved", Endpoint::ActionQueue),
+    admin_route("POST", "/admin/battles/{id:uuid}/set_state",
+        "Apply a JSON merge patch to the battle state", Endpoint::SetBattleState),
+    // Test Endpoints
+    route("POST", "/test/playout",
+        "Play a seeded MVP battle to the end and return its full log", Endpoint::Playout),
+    route("GET", "/health",
+        "Health check", Endpoint::Health),
+];
+
+/// Parameters captured from a request path by a route pattern
+#[derive(Debug, Default, PartialEq)]
+pub struct PathParams(Vec<(&'static str, String)>);
+
+impl PathParams {
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.iter()
+            .find(|(param, _)| *param == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Whether a path segment fits a parameter's declared form
+fn param_accepts(kind: Option<&str>, value: &str) -> bool {
+    match kind {
+        Some("uuid") => uuid::Uuid::parse_str(value).is_ok(),
+        Some("int") => value.parse::<u64>().is_ok(),
+        _ => !value.is_empty(),
+    }
+}
+
+/// Match a path segment by segment against a pattern, capturing its `{name}` segments
+/// Malformed paths (wrong segment count, trailing slash, ill-formed parameter) match nothing
+pub fn match_path(pattern: &'static str, path: &str) -> Option<PathParams> {
+    let mut pattern_segments = pattern.split('/');
+    let mut path_segments = path.split('/');
+    let mut params = Vec::new();
+
+    loop {
+        match (pattern_segments.next(), path_segments.next()) {
+            (None, None) => return Some(PathParams(params)),
+            (Some(expected), Some(actual)) => {
+                match expected.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+                    Some(spec) => {
+                        let (name, kind) = match spec.split_once(':') {
+                            Some((name, kind)) => (name, Some(kind)),
+                            None => (spec, None),
+                        };
+                        if !param_accepts(kind, actual) {
+                            return None;
+                        }
+                        params.push((name, actual.to_string()));
+                    }
+                    None if expected == actual => {}
+                    None => return None,
+                }
+            }
+            // Different segment counts never match
+            _ => return None,
+        }
+    }
+}
+
+/// First route matching the request, with the parameters it captured
+pub fn find_route(method: &str, path: &str) -> Option<(&'static Route, PathParams)> {
+    ROUTES.iter()
+        .filter(|route| route.method == method)
+        .find_map(|route| match_path(route.path, path).map(|params| (route, params)))
+}
+
+/// Public description of a route for the endpoint index
+#[derive(Debug, Serialize)]
+pub struct RouteInfo {
+    pub method: &'static str,
+    pub path: String, // Pattern without parameter forms, e.g. "/battles/{id}/state"
+    pub description: &'static str,
+    pub admin: bool,
+}
+
+/// Endpoint index served at `GET /`, in route table order
+pub fn route_index() -> Vec<RouteInfo> {
+    ROUTES.iter()
+        .map(|route| RouteInfo {
+            method: route.method,
+            path: display_path(route.path),
+            description: route.description,
+            admin: route.admin,
+        })
+        .collect()
+}
+
+/// Route pattern as documented, with `{id:uuid}` shown as `{id}`
+pub fn display_path(pattern: &str) -> String {
+    pattern.split('/')
+        .map(|segment| match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+            Some(spec) => format!("{{{}}}", spec.split(':').next().unwrap_or(spec)),
+            None => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
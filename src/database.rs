@@ -1,60 +1,128 @@
 use aws_sdk_dynamodb::{Client, Error as DynamoError};
-use aws_sdk_dynamodb::types::AttributeValue;
+use aws_sdk_dynamodb::types::{AttributeValue, KeysAndAttributes};
 use serde_json;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::OnceCell;
 
-use crate::types::{BattleId, PlayerId, StoredBattle};
+use crate::engine;
+use crate::errors::ApiError;
+use crate::types::{BattleId, HistoryCursor, MatchId, PlayerId, StoredBattle, StoredMatch, TurnLog, BATTLE_TTL_SECONDS};
 use async_trait::async_trait;
 
 
+/// Sparse GSI over finished battles: partition `finished_feed`, sort `last_updated`
+const FINISHED_INDEX_NAME: &str = "finished-battles-index";
+const FINISHED_FEED_VALUE: &str = "finished";
+
+/// Counter item sharing the battles table; its `next_number` attribute is incremented with `ADD`
+const BATTLE_NUMBER_COUNTER_KEY: &str = "__battle_number_counter";
+/// Stats item sharing the battles table; one `move_<Name>` Number attribute per move
+const MOVE_USAGE_STATS_KEY: &str = "__move_usage_stats";
+const MOVE_USAGE_ATTRIBUTE_PREFIX: &str = "move_";
+/// GSI over `battle_number` (Number) for short-id lookups
+const BATTLE_NUMBER_INDEX_NAME: &str = "battle-number-index";
+/// Number attribute the table's TTL is configured on; DynamoDB deletes battles some time after it passes
+const EXPIRES_AT_ATTRIBUTE: &str = "expires_at";
+/// BatchGetItem accepts at most this many keys per request
+const BATCH_GET_LIMIT: usize = 100;
+/// Rounds of retrying keys DynamoDB returned as unprocessed before giving up
+const BATCH_GET_MAX_ATTEMPTS: usize = 5;
+/// GSIs over each player slot, so a player's battles can be found whichever side they are on
+const PLAYER_INDEXES: [(&str, &str); 2] = [("player1-index", "player1_id"), ("player2-index", "player2_id")];
+/// Sparse GSIs over finished battles per player slot: partition `playerN_history`, sort `last_updated`
+/// The partition attribute copies the slot's player id and is written only once the battle ends
+const PLAYER_HISTORY_INDEXES: [(&str, &str); 2] = [
+    ("player1-history-index", "player1_history"),
+    ("player2-history-index", "player2_history"),
+];
+/// Match items share the battles table under `match_<uuid>` keys, serialized in `match_data`
+const MATCH_KEY_PREFIX: &str = "match_";
+
 #[async_trait]
 pub trait Db: Send + Sync {
     async fn create_battle(&self, battle: &StoredBattle) -> Result<(), anyhow::Error>;
     async fn get_battle(&self, battle_id: BattleId) -> Result<Option<StoredBattle>, anyhow::Error>;
+    /// Load several battles in as few round trips as possible; missing battles are omitted
+    async fn get_battles(&self, battle_ids: &[BattleId]) -> Result<Vec<StoredBattle>, anyhow::Error>;
+    /// Optimistic: fails with `ApiError::Conflict` unless the stored battle is still at `battle.version`
     async fn update_battle(&self, battle: &StoredBattle) -> Result<(), anyhow::Error>;
-    // async fn delete_battle(&self, battle_id: BattleId) -> Result<(), anyhow::Error>; // Optional for tests
+    /// Most recently finished battles, newest first
+    async fn list_recent_finished(&self, limit: usize) -> Result<Vec<StoredBattle>, anyhow::Error>;
+    /// Idempotent: deleting a battle that does not exist succeeds
+    async fn delete_battle(&self, battle_id: BattleId) -> Result<(), anyhow::Error>;
+    /// Atomically allocate the next short battle number, starting at 1
+    async fn next_battle_number(&self) -> Result<u64, anyhow::Error>;
+    async fn get_battle_by_number(&self, battle_number: u64) -> Result<Option<StoredBattle>, anyhow::Error>;
+    /// Up to `limit` battles the player takes part in, in either player slot
+    async fn list_player_battles(&self, player_id: &PlayerId, limit: usize) -> Result<Vec<BattleId>, anyhow::Error>;
+    /// Up to `limit` of the player's finished battles after `before`, newest first by `HistoryCursor` order
+    async fn list_player_history(
+        &self,
+        player_id: &PlayerId,
+        limit: usize,
+        before: Option<&HistoryCursor>,
+    ) -> Result<Vec<StoredBattle>, anyhow::Error>;
+    /// Atomically add to the aggregate per-move usage counts
+    async fn increment_move_usage(&self, move_counts: &HashMap<String, u64>) -> Result<(), anyhow::Error>;
+    async fn get_move_usage(&self) -> Result<HashMap<String, u64>, anyhow::Error>;
+    async fn create_match(&self, stored_match: &StoredMatch) -> Result<(), anyhow::Error>;
+    async fn get_match(&self, match_id: MatchId) -> Result<Option<StoredMatch>, anyhow::Error>;
+    async fn update_match(&self, stored_match: &StoredMatch) -> Result<(), anyhow::Error>;
 }
 
 pub struct Database {
-    client: Client,
+    client: &'static Client, // Process-wide; see `shared_client`
     table_name: String,
 }
 
+/// DynamoDB client shared by every `Database` the process creates
+/// Loading the AWS config walks the credential and region provider chains and the client
+/// owns the HTTP connection pool, so building them per invocation repeated that work on every
+/// request. Lambda keeps the process between warm invocations, so now only the first
+/// `Database::new` pays for it and later ones reuse the client and its open connections.
+/// The cold-vs-warm difference has not been measured on a deployed function yet.
+static SHARED_CLIENT: OnceCell<Client> = OnceCell::const_new();
+
+async fn shared_client() -> &'static Client {
+    SHARED_CLIENT.get_or_init(|| async {
+        let config = load_aws_config().await;
+        Client::new(&config)
+    }).await
+}
+
+/// AWS config from the environment's credential and region provider chains
+#[cfg(not(test))]
+async fn load_aws_config() -> aws_config::SdkConfig {
+    aws_config::load_from_env().await
+}
+
+/// Fixed config, so tests never probe the environment or IMDS for credentials
+#[cfg(test)]
+async fn load_aws_config() -> aws_config::SdkConfig {
+    aws_config::SdkConfig::builder()
+        .behavior_version(aws_config::BehaviorVersion::latest())
+        .region(aws_config::Region::new("us-east-1"))
+        .build()
+}
+
 impl Database {
     // The struct's own implementation block should only contain methods
     // that are NOT part of the Db trait, like the constructor and private helpers.
     pub async fn new(table_name: String) -> Result<Self, anyhow::Error> {
-        let config = aws_config::load_from_env().await;
-        let client = Client::new(&config);
-        
         Ok(Database {
-            client,
+            client: shared_client().await,
             table_name,
         })
     }
 
-    // `create_battle`, `get_battle`, etc. are now implemented in the `impl Db for Database` block below.
-
-    /// Delete a battle from the database (for cleanup) - This one can stay here if not in the trait
-    pub async fn delete_battle(&self, battle_id: BattleId) -> Result<(), anyhow::Error> {
-        self.client
-            .delete_item()
-            .table_name(&self.table_name)
-            .key("battle_id", AttributeValue::S(battle_id.to_string()))
-            .send()
-            .await
-            .map_err(|e| anyhow::anyhow!("Failed to delete battle: {}", e))?;
-
-        Ok(())
+    /// Whether both databases talk to DynamoDB through the same client
+    #[cfg(test)]
+    pub fn shares_client_with(&self, other: &Database) -> bool {
+        std::ptr::eq(self.client, other.client)
     }
 
-    /// List battles for a specific player (for potential future use) - This one can also stay
-    pub async fn list_player_battles(&self, player_id: &PlayerId) -> Result<Vec<BattleId>, anyhow::Error> {
-        // Note: This would require a GSI (Global Secondary Index) on player_id fields
-        // For now, this is a placeholder implementation
-        todo!("Implement GSI-based player battle lookup")
-    }
+    // `create_battle`, `get_battle`, etc. are now implemented in the `impl Db for Database` block below.
 
     /// Convert StoredBattle to DynamoDB item
     fn battle_to_item(&self, battle: &StoredBattle) -> Result<HashMap<String, AttributeValue>, anyhow::Error> {
@@ -66,6 +134,7 @@ impl Database {
         item.insert("player2_id".to_string(), AttributeValue::S(battle.player2_id.0.clone()));
         item.insert("created_at".to_string(), AttributeValue::N(battle.created_at.to_string()));
         item.insert("last_updated".to_string(), AttributeValue::N(battle.last_updated.to_string()));
+        item.insert(EXPIRES_AT_ATTRIBUTE.to_string(), AttributeValue::N(battle.expires_at().to_string()));
 
         let battle_state_json = serde_json::to_string(&battle.battle_state)
             .map_err(|e| anyhow::anyhow!("Failed to serialize battle state: {}", e))?;
@@ -75,6 +144,60 @@ impl Database {
             .map_err(|e| anyhow::anyhow!("Failed to serialize turn logs: {}", e))?;
         item.insert("turn_logs".to_string(), AttributeValue::S(turn_logs_json));
 
+        if let Some(initial_state) = &battle.initial_state {
+            let initial_state_json = serde_json::to_string(initial_state)
+                .map_err(|e| anyhow::anyhow!("Failed to serialize initial battle state: {}", e))?;
+            item.insert("initial_state".to_string(), AttributeValue::S(initial_state_json));
+        }
+
+        let rules_json = serde_json::to_string(&battle.rules)
+            .map_err(|e| anyhow::anyhow!("Failed to serialize battle rules: {}", e))?;
+        item.insert("rules".to_string(), AttributeValue::S(rules_json));
+
+        item.insert("resolution_pending".to_string(), AttributeValue::Bool(battle.resolution_pending));
+        item.insert("paused".to_string(), AttributeValue::Bool(battle.paused));
+        item.insert("engine_schema_version".to_string(), AttributeValue::N(battle.engine_schema_version.to_string()));
+        item.insert("version".to_string(), AttributeValue::N(battle.version.to_string()));
+
+        if let Some(last_resolved_turn) = battle.last_resolved_turn {
+            item.insert("last_resolved_turn".to_string(), AttributeValue::N(last_resolved_turn.to_string()));
+        }
+
+        if let Some(battle_number) = battle.battle_number {
+            item.insert("battle_number".to_string(), AttributeValue::N(battle_number.to_string()));
+        }
+
+        if let Some(match_id) = battle.match_id {
+            item.insert("match_id".to_string(), AttributeValue::S(match_id.to_string()));
+        }
+
+        if let Some(draw_reason) = &battle.draw_reason {
+            item.insert("draw_reason".to_string(), AttributeValue::S(draw_reason.clone()));
+        }
+
+        if !battle.observer_tokens.is_empty() {
+            let observer_tokens_json = serde_json::to_string(&battle.observer_tokens)
+                .map_err(|e| anyhow::anyhow!("Failed to serialize observer tokens: {}", e))?;
+            item.insert("observer_tokens".to_string(), AttributeValue::S(observer_tokens_json));
+        }
+
+        if let Some(battle_seed) = battle.battle_seed {
+            item.insert("battle_seed".to_string(), AttributeValue::N(battle_seed.to_string()));
+        }
+
+        // Only finished battles carry the feed and history keys, keeping those GSIs sparse
+        if battle.is_finished() {
+            item.insert("finished_feed".to_string(), AttributeValue::S(FINISHED_FEED_VALUE.to_string()));
+            item.insert("player1_history".to_string(), AttributeValue::S(battle.player1_id.0.clone()));
+            item.insert("player2_history".to_string(), AttributeValue::S(battle.player2_id.0.clone()));
+        }
+
+        if let Some(setup) = &battle.setup {
+            let setup_json = serde_json::to_string(setup)
+                .map_err(|e| anyhow::anyhow!("Failed to serialize battle setup: {}", e))?;
+            item.insert("setup".to_string(), AttributeValue::S(setup_json));
+        }
+
         Ok(item)
     }
 
@@ -112,6 +235,13 @@ impl Database {
             .and_then(|s| s.parse().ok())
             .ok_or_else(|| anyhow::anyhow!("Missing or invalid last_updated"))?;
 
+        // Check compatibility before deserializing, so an engine upgrade fails with a clear error
+        let engine_schema_version = item.get("engine_schema_version")
+            .and_then(|av| av.as_n().ok())
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_else(engine::legacy_engine_schema_version);
+        engine::check_engine_schema_version(battle_id, engine_schema_version)?;
+
         let battle_state_json = item.get("battle_state")
             .and_then(|av| av.as_s().ok())
             .ok_or_else(|| anyhow::anyhow!("Missing battle_state"))?;
@@ -119,15 +249,90 @@ impl Database {
         let battle_state = serde_json::from_str(battle_state_json)
             .map_err(|e| anyhow::anyhow!("Failed to deserialize battle state: {}", e))?;
 
-        let turn_logs = item.get("turn_logs")
+        let turn_logs: Vec<TurnLog> = item.get("turn_logs")
             .and_then(|v| v.as_s().ok())
             .and_then(|json| serde_json::from_str(json).ok())
             .unwrap_or_else(Vec::new);
 
+        let initial_state = item.get("initial_state")
+            .and_then(|v| v.as_s().ok())
+            .and_then(|json| serde_json::from_str(json).ok());
+
+        let setup = item.get("setup")
+            .and_then(|v| v.as_s().ok())
+            .and_then(|json| serde_json::from_str(json).ok());
+
+        let rules = item.get("rules")
+            .and_then(|v| v.as_s().ok())
+            .and_then(|json| serde_json::from_str(json).ok())
+            .unwrap_or_default();
+
+        let draw_reason = item.get("draw_reason")
+            .and_then(|v| v.as_s().ok())
+            .cloned();
+
+        let observer_tokens = item.get("observer_tokens")
+            .and_then(|v| v.as_s().ok())
+            .and_then(|json| serde_json::from_str(json).ok())
+            .unwrap_or_default();
+
+        // Battles saved before this was recorded fall back to their newest turn log
+        let last_resolved_turn = item.get("last_resolved_turn")
+            .and_then(|av| av.as_n().ok())
+            .and_then(|s| s.parse().ok())
+            .or_else(|| turn_logs.last().map(|log| log.turn_number));
+
+        let battle_number = item.get("battle_number")
+            .and_then(|av| av.as_n().ok())
+            .and_then(|s| s.parse().ok());
+
+        // Battles saved before versioning start at 0
+        let version = item.get("version")
+            .and_then(|av| av.as_n().ok())
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0);
+
+        let battle_seed = item.get("battle_seed")
+            .and_then(|av| av.as_n().ok())
+            .and_then(|s| s.parse().ok());
+
+        let match_id = item.get("match_id")
+            .and_then(|av| av.as_s().ok())
+            .and_then(|s| s.parse().ok())
+            .map(MatchId);
+
+        let resolution_pending = item.get("resolution_pending")
+            .and_then(|v| v.as_bool().ok())
+            .copied()
+            .unwrap_or(false);
+
+        let paused = item.get("paused")
+            .and_then(|v| v.as_bool().ok())
+            .copied()
+            .unwrap_or(false);
+
         Ok(StoredBattle {
-            battle_id, player1_id, player2_id, battle_state, turn_logs, created_at, last_updated,
+            battle_id, player1_id, player2_id, battle_state, turn_logs, initial_state, setup, rules, draw_reason,
+            resolution_pending, paused, battle_number, match_id, last_resolved_turn, engine_schema_version,
+            observer_tokens, battle_seed, version, created_at, last_updated,
         })
     }
+
+    fn match_key(match_id: MatchId) -> AttributeValue {
+        AttributeValue::S(format!("{}{}", MATCH_KEY_PREFIX, match_id))
+    }
+
+    /// Convert StoredMatch to DynamoDB item
+    fn match_to_item(&self, stored_match: &StoredMatch) -> Result<HashMap<String, AttributeValue>, anyhow::Error> {
+        let match_json = serde_json::to_string(stored_match)
+            .map_err(|e| anyhow::anyhow!("Failed to serialize match: {}", e))?;
+
+        let mut item = HashMap::new();
+        item.insert("battle_id".to_string(), Self::match_key(stored_match.match_id));
+        item.insert("match_data".to_string(), AttributeValue::S(match_json));
+        item.insert("last_updated".to_string(), AttributeValue::N(stored_match.last_updated.to_string()));
+        Ok(item)
+    }
 }
 
 #[async_trait]
@@ -154,29 +359,560 @@ impl Db for Database {
             .await
             .map_err(|e| anyhow::anyhow!("Failed to get battle: {}", e))?;
 
+        // The TTL deleter can lag by days, so expired rows that still exist read as not found
         match result.item {
-            Some(item) => Ok(Some(self.item_to_battle(item)?)),
+            Some(item) => Ok(Some(self.item_to_battle(item)?).filter(|battle| !battle.is_expired(now()))),
             None => Ok(None),
         }
     }
 
+    async fn get_battles(&self, battle_ids: &[BattleId]) -> Result<Vec<StoredBattle>, anyhow::Error> {
+        let mut battles = Vec::new();
+
+        for chunk in battle_ids.chunks(BATCH_GET_LIMIT) {
+            let keys = chunk.iter()
+                .map(|battle_id| HashMap::from([
+                    ("battle_id".to_string(), AttributeValue::S(battle_id.to_string())),
+                ]))
+                .collect();
+            let keys_and_attributes = KeysAndAttributes::builder()
+                .set_keys(Some(keys))
+                .build()
+                .map_err(|e| anyhow::anyhow!("Failed to build batch get: {}", e))?;
+            let mut request_items = HashMap::from([(self.table_name.clone(), keys_and_attributes)]);
+
+            // DynamoDB may hand back some keys unprocessed under load; retry just those
+            for attempt in 1..=BATCH_GET_MAX_ATTEMPTS {
+                let result = self.client
+                    .batch_get_item()
+                    .set_request_items(Some(request_items))
+                    .send()
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Failed to batch get battles: {}", e))?;
+
+                if let Some(items) = result.responses.and_then(|mut r| r.remove(&self.table_name)) {
+                    for item in items {
+                        let battle = self.item_to_battle(item)?;
+                        if !battle.is_expired(now()) {
+                            battles.push(battle);
+                        }
+                    }
+                }
+
+                match result.unprocessed_keys.filter(|keys| !keys.is_empty()) {
+                    Some(unprocessed) if attempt < BATCH_GET_MAX_ATTEMPTS => request_items = unprocessed,
+                    Some(_) => return Err(anyhow::anyhow!("Batch get left battles unprocessed after {} attempts", attempt)),
+                    None => break,
+                }
+            }
+        }
+
+        Ok(battles)
+    }
+
     async fn update_battle(&self, battle: &StoredBattle) -> Result<(), anyhow::Error> {
-        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64;
+        let timestamp = now();
         let battle_state_json = serde_json::to_string(&battle.battle_state)?;
         let turn_logs_json = serde_json::to_string(&battle.turn_logs)?;
 
-        self.client
+        let mut update_expression = format!(
+            "SET battle_state = :state, turn_logs = :logs, last_updated = :timestamp, resolution_pending = :pending, paused = :paused, version = :next_version, {} = :expires_at",
+            EXPIRES_AT_ATTRIBUTE,
+        );
+        if battle.is_finished() {
+            update_expression.push_str(", finished_feed = :feed, player1_history = :player1, player2_history = :player2");
+        }
+        if battle.draw_reason.is_some() {
+            update_expression.push_str(", draw_reason = :draw_reason");
+        }
+        // Written in the same update as the state and logs, so a resolved turn is never saved without its log
+        if battle.last_resolved_turn.is_some() {
+            update_expression.push_str(", last_resolved_turn = :last_resolved_turn");
+        }
+
+        let mut request = self.client
             .update_item()
             .table_name(&self.table_name)
             .key("battle_id", AttributeValue::S(battle.battle_id.to_string()))
-            .update_expression("SET battle_state = :state, turn_logs = :logs, last_updated = :timestamp")
+            .update_expression(update_expression)
             .expression_attribute_values(":state", AttributeValue::S(battle_state_json))
             .expression_attribute_values(":logs", AttributeValue::S(turn_logs_json))
             .expression_attribute_values(":timestamp", AttributeValue::N(timestamp.to_string()))
+            .expression_attribute_values(":expires_at", AttributeValue::N((timestamp + BATTLE_TTL_SECONDS).to_string()))
+            .expression_attribute_values(":pending", AttributeValue::Bool(battle.resolution_pending))
+            .expression_attribute_values(":paused", AttributeValue::Bool(battle.paused))
+            .expression_attribute_values(":version", AttributeValue::N(battle.version.to_string()))
+            .expression_attribute_values(":next_version", AttributeValue::N((battle.version + 1).to_string()))
+            // Items written before versioning have no `version` and accept the first versioned save
+            .condition_expression("attribute_exists(battle_id) AND (attribute_not_exists(version) OR version = :version)");
+
+        if battle.is_finished() {
+            request = request
+                .expression_attribute_values(":feed", AttributeValue::S(FINISHED_FEED_VALUE.to_string()))
+                .expression_attribute_values(":player1", AttributeValue::S(battle.player1_id.0.clone()))
+                .expression_attribute_values(":player2", AttributeValue::S(battle.player2_id.0.clone()));
+        }
+        if let Some(draw_reason) = &battle.draw_reason {
+            request = request.expression_attribute_values(":draw_reason", AttributeValue::S(draw_reason.clone()));
+        }
+        if let Some(last_resolved_turn) = battle.last_resolved_turn {
+            request = request.expression_attribute_values(":last_resolved_turn", AttributeValue::N(last_resolved_turn.to_string()));
+        }
+
+        // A failed condition means another writer saved (or deleted) the battle since it was loaded
+        request
+            .send()
+            .await
+            .map_err(|e| match e.into_service_error() {
+                err if err.is_conditional_check_failed_exception() => {
+                    anyhow::Error::new(ApiError::Conflict { battle_id: battle.battle_id })
+                }
+                err => anyhow::anyhow!("Failed to update battle: {}", err),
+            })?;
+        Ok(())
+    }
+
+    async fn list_recent_finished(&self, limit: usize) -> Result<Vec<StoredBattle>, anyhow::Error> {
+        // Expired rows the TTL deleter has not reached yet are excluded by the key condition,
+        // so they never take up any of the `limit`
+        let live_after = now() - BATTLE_TTL_SECONDS;
+        let result = self.client
+            .query()
+            .table_name(&self.table_name)
+            .index_name(FINISHED_INDEX_NAME)
+            .key_condition_expression("finished_feed = :feed AND last_updated > :live_after")
+            .expression_attribute_values(":feed", AttributeValue::S(FINISHED_FEED_VALUE.to_string()))
+            .expression_attribute_values(":live_after", AttributeValue::N(live_after.to_string()))
+            .scan_index_forward(false) // Newest last_updated first
+            .limit(limit as i32)
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to list recent battles: {}", e))?;
+
+        result.items
+            .unwrap_or_default()
+            .into_iter()
+            .map(|item| self.item_to_battle(item))
+            .collect()
+    }
+
+    async fn delete_battle(&self, battle_id: BattleId) -> Result<(), anyhow::Error> {
+        // delete_item is a no-op for missing keys, which gives idempotent deletes for free
+        self.client
+            .delete_item()
+            .table_name(&self.table_name)
+            .key("battle_id", AttributeValue::S(battle_id.to_string()))
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to delete battle: {}", e))?;
+
+        Ok(())
+    }
+
+    async fn next_battle_number(&self) -> Result<u64, anyhow::Error> {
+        // ADD creates the attribute at 0 on first use, so the first number handed out is 1
+        let result = self.client
+            .update_item()
+            .table_name(&self.table_name)
+            .key("battle_id", AttributeValue::S(BATTLE_NUMBER_COUNTER_KEY.to_string()))
+            .update_expression("ADD next_number :one")
+            .expression_attribute_values(":one", AttributeValue::N("1".to_string()))
+            .return_values(aws_sdk_dynamodb::types::ReturnValue::UpdatedNew)
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to allocate battle number: {}", e))?;
+
+        result.attributes
+            .as_ref()
+            .and_then(|attrs| attrs.get("next_number"))
+            .and_then(|av| av.as_n().ok())
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(|| anyhow::anyhow!("Battle number counter returned no value"))
+    }
+
+    async fn get_battle_by_number(&self, battle_number: u64) -> Result<Option<StoredBattle>, anyhow::Error> {
+        let result = self.client
+            .query()
+            .table_name(&self.table_name)
+            .index_name(BATTLE_NUMBER_INDEX_NAME)
+            .key_condition_expression("battle_number = :number")
+            .expression_attribute_values(":number", AttributeValue::N(battle_number.to_string()))
+            .limit(1)
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to look up battle number: {}", e))?;
+
+        let battle = result.items
+            .unwrap_or_default()
+            .into_iter()
+            .next()
+            .map(|item| self.item_to_battle(item))
+            .transpose()?;
+        Ok(battle.filter(|battle| !battle.is_expired(now())))
+    }
+
+    async fn list_player_battles(&self, player_id: &PlayerId, limit: usize) -> Result<Vec<BattleId>, anyhow::Error> {
+        let mut battle_ids = Vec::new();
+        let mut seen = HashSet::new();
+
+        for (index_name, attribute) in PLAYER_INDEXES {
+            let mut exclusive_start_key = None;
+            while battle_ids.len() < limit {
+                let result = self.client
+                    .query()
+                    .table_name(&self.table_name)
+                    .index_name(index_name)
+                    .key_condition_expression(format!("{} = :player", attribute))
+                    .expression_attribute_values(":player", AttributeValue::S(player_id.0.clone()))
+                    .limit((limit - battle_ids.len()) as i32)
+                    .set_exclusive_start_key(exclusive_start_key)
+                    .send()
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Failed to list player battles: {}", e))?;
+
+                for item in result.items.unwrap_or_default() {
+                    let battle_id = item.get("battle_id")
+                        .and_then(|av| av.as_s().ok())
+                        .and_then(|s| s.parse().ok())
+                        .map(BattleId)
+                        .ok_or_else(|| anyhow::anyhow!("Invalid battle_id in player index"))?;
+                    // A player battling themselves appears in both indexes
+                    if seen.insert(battle_id) {
+                        battle_ids.push(battle_id);
+                    }
+                }
+
+                match result.last_evaluated_key {
+                    Some(key) => exclusive_start_key = Some(key),
+                    None => break,
+                }
+            }
+        }
+
+        Ok(battle_ids)
+    }
+
+    async fn list_player_history(
+        &self,
+        player_id: &PlayerId,
+        limit: usize,
+        before: Option<&HistoryCursor>,
+    ) -> Result<Vec<StoredBattle>, anyhow::Error> {
+        let mut battles: Vec<StoredBattle> = Vec::new();
+
+        // Newest `limit` from each slot's index; the merged newest `limit` are among them
+        for (index_name, attribute) in PLAYER_HISTORY_INDEXES {
+            let mut found = 0;
+            let mut exclusive_start_key = None;
+            while found < limit {
+                let mut query = self.client
+                    .query()
+                    .table_name(&self.table_name)
+                    .index_name(index_name)
+                    .expression_attribute_values(":player", AttributeValue::S(player_id.0.clone()))
+                    .scan_index_forward(false) // Newest last_updated first
+                    .set_exclusive_start_key(exclusive_start_key);
+                // Same-second battles at the cursor are sorted out below, so the bound is inclusive
+                query = match before {
+                    Some(cursor) => query
+                        .key_condition_expression(format!("{} = :player AND last_updated <= :before", attribute))
+                        .expression_attribute_values(":before", AttributeValue::N(cursor.last_updated.to_string())),
+                    None => query.key_condition_expression(format!("{} = :player", attribute)),
+                };
+                let result = query
+                    .send()
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Failed to list player history: {}", e))?;
+
+                for item in result.items.unwrap_or_default() {
+                    let battle = self.item_to_battle(item)?;
+                    if battle.is_expired(now()) || before.is_some_and(|cursor| !cursor.precedes(&battle)) {
+                        continue;
+                    }
+                    found += 1;
+                    // A player battling themselves appears in both indexes
+                    if !battles.iter().any(|existing| existing.battle_id == battle.battle_id) {
+                        battles.push(battle);
+                    }
+                }
+
+                match result.last_evaluated_key {
+                    Some(key) => exclusive_start_key = Some(key),
+                    None => break,
+                }
+            }
+        }
+
+        battles.sort_by_key(|battle| std::cmp::Reverse(HistoryCursor::of(battle).key()));
+        battles.truncate(limit);
+        Ok(battles)
+    }
+
+    async fn increment_move_usage(&self, move_counts: &HashMap<String, u64>) -> Result<(), anyhow::Error> {
+        // One ADD per move; ADD creates missing attributes at 0, so no read is needed
+        let mut request = self.client
+            .update_item()
+            .table_name(&self.table_name)
+            .key("battle_id", AttributeValue::S(MOVE_USAGE_STATS_KEY.to_string()));
+
+        let mut additions = Vec::new();
+        for (i, (move_name, count)) in move_counts.iter().enumerate() {
+            additions.push(format!("#m{} :c{}", i, i));
+            request = request
+                .expression_attribute_names(format!("#m{}", i), format!("{}{}", MOVE_USAGE_ATTRIBUTE_PREFIX, move_name))
+                .expression_attribute_values(format!(":c{}", i), AttributeValue::N(count.to_string()));
+        }
+
+        request
+            .update_expression(format!("ADD {}", additions.join(", ")))
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to record move usage: {}", e))?;
+        Ok(())
+    }
+
+    async fn get_move_usage(&self) -> Result<HashMap<String, u64>, anyhow::Error> {
+        let result = self.client
+            .get_item()
+            .table_name(&self.table_name)
+            .key("battle_id", AttributeValue::S(MOVE_USAGE_STATS_KEY.to_string()))
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to get move usage: {}", e))?;
+
+        Ok(result.item
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(name, value)| {
+                let move_name = name.strip_prefix(MOVE_USAGE_ATTRIBUTE_PREFIX)?.to_string();
+                let count = value.as_n().ok()?.parse().ok()?;
+                Some((move_name, count))
+            })
+            .collect())
+    }
+
+    async fn create_match(&self, stored_match: &StoredMatch) -> Result<(), anyhow::Error> {
+        let item = self.match_to_item(stored_match)?;
+        self.client
+            .put_item()
+            .table_name(&self.table_name)
+            .set_item(Some(item))
+            .condition_expression("attribute_not_exists(battle_id)")
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to create match: {}", e))?;
+        Ok(())
+    }
+
+    async fn get_match(&self, match_id: MatchId) -> Result<Option<StoredMatch>, anyhow::Error> {
+        let result = self.client
+            .get_item()
+            .table_name(&self.table_name)
+            .key("battle_id", Self::match_key(match_id))
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to get match: {}", e))?;
+
+        result.item
+            .and_then(|item| item.get("match_data").and_then(|av| av.as_s().ok()).cloned())
+            .map(|json| serde_json::from_str(&json)
+                .map_err(|e| anyhow::anyhow!("Failed to deserialize match: {}", e)))
+            .transpose()
+    }
+
+    async fn update_match(&self, stored_match: &StoredMatch) -> Result<(), anyhow::Error> {
+        let item = self.match_to_item(stored_match)?;
+        self.client
+            .put_item()
+            .table_name(&self.table_name)
+            .set_item(Some(item))
             .condition_expression("attribute_exists(battle_id)")
             .send()
             .await
-            .map_err(|e| anyhow::anyhow!("Failed to update battle: {}", e))?;
+            .map_err(|e| anyhow::anyhow!("Failed to update match: {}", e))?;
+        Ok(())
+    }
+}
+
+/// Current Unix timestamp
+fn now() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64
+}
+
+/// In-memory `Db` implementation for tests, no AWS required
+#[cfg(any(test, feature = "test-utils"))]
+#[derive(Clone, Default)]
+pub struct InMemoryDb {
+    battles: std::sync::Arc<std::sync::Mutex<HashMap<BattleId, StoredBattle>>>,
+    battle_number_counter: std::sync::Arc<std::sync::atomic::AtomicU64>,
+    move_usage: std::sync::Arc<std::sync::Mutex<HashMap<String, u64>>>,
+    matches: std::sync::Arc<std::sync::Mutex<HashMap<MatchId, StoredMatch>>>,
+    concurrent_writes: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    interleave_loads: std::sync::Arc<std::sync::atomic::AtomicBool>,
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+impl InMemoryDb {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Have another writer save each battle just before the next `count` updates, so they conflict
+    pub fn simulate_concurrent_writes(&self, count: usize) {
+        self.concurrent_writes.store(count, std::sync::atomic::Ordering::SeqCst);
+    }
+
+    /// Yield after every battle load, so requests joined on one task all load before any of them saves
+    pub fn interleave_loads(&self) {
+        self.interleave_loads.store(true, std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+#[async_trait]
+impl Db for InMemoryDb {
+    async fn create_battle(&self, battle: &StoredBattle) -> Result<(), anyhow::Error> {
+        let mut battles = self.battles.lock().unwrap();
+        if battles.contains_key(&battle.battle_id) {
+            return Err(anyhow::anyhow!("Battle already exists"));
+        }
+        battles.insert(battle.battle_id, battle.clone());
         Ok(())
     }
-}
\ No newline at end of file
+
+    async fn get_battle(&self, battle_id: BattleId) -> Result<Option<StoredBattle>, anyhow::Error> {
+        // Expired battles read as not found, as they do in DynamoDB before the TTL deleter runs
+        let battle = {
+            let battles = self.battles.lock().unwrap();
+            match battles.get(&battle_id).filter(|battle| !battle.is_expired(now())) {
+                Some(battle) => {
+                    engine::check_engine_schema_version(battle_id, battle.engine_schema_version)?;
+                    Some(battle.clone())
+                }
+                None => None,
+            }
+        };
+        if self.interleave_loads.load(std::sync::atomic::Ordering::SeqCst) {
+            tokio::task::yield_now().await;
+        }
+        Ok(battle)
+    }
+
+    async fn get_battles(&self, battle_ids: &[BattleId]) -> Result<Vec<StoredBattle>, anyhow::Error> {
+        let battles = self.battles.lock().unwrap();
+        battle_ids.iter()
+            .filter_map(|battle_id| battles.get(battle_id))
+            .filter(|battle| !battle.is_expired(now()))
+            .map(|battle| {
+                engine::check_engine_schema_version(battle.battle_id, battle.engine_schema_version)?;
+                Ok(battle.clone())
+            })
+            .collect()
+    }
+
+    async fn update_battle(&self, battle: &StoredBattle) -> Result<(), anyhow::Error> {
+        let mut battles = self.battles.lock().unwrap();
+        let Some(stored) = battles.get_mut(&battle.battle_id) else {
+            return Err(anyhow::anyhow!("Battle not found"));
+        };
+        let simulated_write = self.concurrent_writes
+            .fetch_update(std::sync::atomic::Ordering::SeqCst, std::sync::atomic::Ordering::SeqCst, |n| n.checked_sub(1))
+            .is_ok();
+        if simulated_write {
+            stored.version += 1;
+        }
+        if stored.version != battle.version {
+            return Err(ApiError::Conflict { battle_id: battle.battle_id }.into());
+        }
+        *stored = StoredBattle { version: battle.version + 1, ..battle.clone() };
+        Ok(())
+    }
+
+    async fn list_recent_finished(&self, limit: usize) -> Result<Vec<StoredBattle>, anyhow::Error> {
+        let battles = self.battles.lock().unwrap();
+        let mut finished: Vec<StoredBattle> = battles.values()
+            .filter(|battle| battle.is_finished() && !battle.is_expired(now()))
+            .cloned()
+            .collect();
+        finished.sort_by(|a, b| b.last_updated.cmp(&a.last_updated));
+        finished.truncate(limit);
+        Ok(finished)
+    }
+    async fn delete_battle(&self, battle_id: BattleId) -> Result<(), anyhow::Error> {
+        self.battles.lock().unwrap().remove(&battle_id);
+        Ok(())
+    }
+
+    async fn next_battle_number(&self) -> Result<u64, anyhow::Error> {
+        Ok(self.battle_number_counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1)
+    }
+
+    async fn get_battle_by_number(&self, battle_number: u64) -> Result<Option<StoredBattle>, anyhow::Error> {
+        let battles = self.battles.lock().unwrap();
+        Ok(battles.values()
+            .find(|battle| battle.battle_number == Some(battle_number))
+            .filter(|battle| !battle.is_expired(now()))
+            .cloned())
+    }
+
+    async fn list_player_battles(&self, player_id: &PlayerId, limit: usize) -> Result<Vec<BattleId>, anyhow::Error> {
+        let battles = self.battles.lock().unwrap();
+        Ok(battles.values()
+            .filter(|battle| battle.player1_id == *player_id || battle.player2_id == *player_id)
+            .map(|battle| battle.battle_id)
+            .take(limit)
+            .collect())
+    }
+
+    async fn list_player_history(
+        &self,
+        player_id: &PlayerId,
+        limit: usize,
+        before: Option<&HistoryCursor>,
+    ) -> Result<Vec<StoredBattle>, anyhow::Error> {
+        let battles = self.battles.lock().unwrap();
+        let mut history: Vec<StoredBattle> = battles.values()
+            .filter(|battle| battle.player1_id == *player_id || battle.player2_id == *player_id)
+            .filter(|battle| battle.is_finished() && !battle.is_expired(now()))
+            .filter(|battle| before.is_none_or(|cursor| cursor.precedes(battle)))
+            .cloned()
+            .collect();
+        history.sort_by_key(|battle| std::cmp::Reverse(HistoryCursor::of(battle).key()));
+        history.truncate(limit);
+        Ok(history)
+    }
+
+    async fn increment_move_usage(&self, move_counts: &HashMap<String, u64>) -> Result<(), anyhow::Error> {
+        let mut usage = self.move_usage.lock().unwrap();
+        for (move_name, count) in move_counts {
+            *usage.entry(move_name.clone()).or_insert(0) += count;
+        }
+        Ok(())
+    }
+
+    async fn get_move_usage(&self) -> Result<HashMap<String, u64>, anyhow::Error> {
+        Ok(self.move_usage.lock().unwrap().clone())
+    }
+
+    async fn create_match(&self, stored_match: &StoredMatch) -> Result<(), anyhow::Error> {
+        let mut matches = self.matches.lock().unwrap();
+        if matches.contains_key(&stored_match.match_id) {
+            return Err(anyhow::anyhow!("Match already exists"));
+        }
+        matches.insert(stored_match.match_id, stored_match.clone());
+        Ok(())
+    }
+
+    async fn get_match(&self, match_id: MatchId) -> Result<Option<StoredMatch>, anyhow::Error> {
+        Ok(self.matches.lock().unwrap().get(&match_id).cloned())
+    }
+
+    async fn update_match(&self, stored_match: &StoredMatch) -> Result<(), anyhow::Error> {
+        let mut matches = self.matches.lock().unwrap();
+        if !matches.contains_key(&stored_match.match_id) {
+            return Err(anyhow::anyhow!("Match not found"));
+        }
+        matches.insert(stored_match.match_id, stored_match.clone());
+        Ok(())
+    }
+}
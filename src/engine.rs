@@ -6,13 +6,22 @@ use pokemon_adventure::{
             get_valid_actions, validate_player_action,
         },
     },
-    player::{BattlePlayer, PlayerAction},
-    pokemon::{PokemonInst, get_species_data},
+    player::{BattlePlayer, PlayerAction, PlayerType, PokemonCondition, StatType},
+    pokemon::{PokemonInst, PokemonType, StatusCondition, get_species_data},
     species::Species,
     moves::Move,
+    move_data::{get_move_data, MoveEffect},
 };
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::sync::OnceLock;
+
 use crate::errors::ApiError;
-use crate::types::{TeamPokemon, PlayerId, PrefabTeamInfo, NpcOpponentInfo};
+use crate::types::{
+    TeamPokemon, PlayerId, BattleId, PrefabTeamInfo, PrefabTeamDetail, PrefabPokemonInfo,
+    NpcOpponentInfo, GameStateInfo, MatchupPairing, BattleEventDto, ActionQueueSlot, AnnotatedAction,
+    BattleRules, LearnableMove, SwitchPreview, MoveAnalysis, PrivateEvent, TurnLog, TurnResolution, StatusDto,
+    ConditionDto, PokemonDamage, SpeciesInfo, BaseStatsInfo, MoveInfo, TeamError,
+};
 
 /// Pure engine functions - no I/O dependencies, just game logic
 
@@ -32,6 +41,81 @@ pub fn get_available_teams() -> Vec<PrefabTeamInfo> {
         .collect()
 }
 
+/// Get every prefab team with its complete roster
+/// Prefab data is compiled into the engine, so the converted list is built once and cached
+pub fn get_available_teams_full() -> &'static [PrefabTeamDetail] {
+    static TEAMS: OnceLock<Vec<PrefabTeamDetail>> = OnceLock::new();
+
+    TEAMS.get_or_init(|| {
+        pokemon_adventure::prefab_teams::get_prefab_teams()
+            .into_iter()
+            .map(|team| PrefabTeamDetail {
+                id: team.id,
+                name: team.name,
+                description: team.description,
+                pokemon: team.pokemon.into_iter()
+                    .map(|p| PrefabPokemonInfo {
+                        species: p.species,
+                        level: p.level,
+                        moves: p.moves,
+                    })
+                    .collect(),
+            })
+            .collect()
+    })
+}
+
+/// A prefab team that failed the startup self-check
+/// Critical failures mean the team is listed but cannot start a battle at all
+#[derive(Debug, Clone)]
+pub struct PrefabValidationFailure {
+    pub team_id: String,
+    pub message: String,
+    pub critical: bool,
+}
+
+/// Check that every prefab team builds a battle player and passes custom team validation
+pub fn validate_prefab_teams() -> Vec<PrefabValidationFailure> {
+    let mut failures = Vec::new();
+
+    for team in pokemon_adventure::prefab_teams::get_prefab_teams() {
+        if let Err(e) = pokemon_adventure::prefab_teams::create_battle_player_from_prefab(
+            &team.id,
+            "player_1".to_string(),
+            "Prefab Check".to_string(),
+        ) {
+            failures.push(PrefabValidationFailure {
+                team_id: team.id,
+                message: e,
+                critical: true,
+            });
+            continue;
+        }
+
+        let team_config: Vec<TeamPokemon> = team.pokemon.into_iter()
+            .map(|p| TeamPokemon {
+                species: p.species,
+                level: p.level,
+                moves: p.moves,
+                nickname: None,
+                item: None,
+            })
+            .collect();
+
+        // An empty roster builds a player but can never send out a Pokemon
+        // Prefab movesets come from the engine itself, so only structure is checked
+        if let Err(e) = create_pokemon_team(&team_config, true) {
+            failures.push(PrefabValidationFailure {
+                team_id: team.id,
+                critical: team_config.is_empty(),
+                message: e.to_string(),
+            });
+        }
+    }
+
+    failures
+}
+
 /// Get available NPC opponents
 pub fn get_npc_opponents() -> Vec<NpcOpponentInfo> {
     vec![
@@ -56,13 +140,57 @@ pub fn get_npc_opponents() -> Vec<NpcOpponentInfo> {
     ]
 }
 
+/// Every engine game state, in the order a battle moves through them
+const GAME_STATES: [GameState; 8] = [
+    GameState::WaitingForActions,
+    GameState::TurnInProgress,
+    GameState::WaitingForPlayer1Replacement,
+    GameState::WaitingForPlayer2Replacement,
+    GameState::WaitingForBothReplacements,
+    GameState::Player1Win,
+    GameState::Player2Win,
+    GameState::Draw,
+];
+
+/// What a game state asks of clients
+/// Exhaustive so a new engine state has to be described before it compiles
+fn describe_game_state(state: GameState) -> &'static str {
+    match state {
+        GameState::WaitingForActions => "Both players choose their move or switch for the turn",
+        GameState::TurnInProgress => "The turn is resolving; no actions are accepted",
+        GameState::WaitingForPlayer1Replacement => "Player 1's active Pokemon fainted and they must switch in a replacement",
+        GameState::WaitingForPlayer2Replacement => "Player 2's active Pokemon fainted and they must switch in a replacement",
+        GameState::WaitingForBothReplacements => "Both active Pokemon fainted and both players must switch in a replacement",
+        GameState::Player1Win => "The battle is over and player 1 won",
+        GameState::Player2Win => "The battle is over and player 2 won",
+        GameState::Draw => "The battle is over with no winner",
+    }
+}
+
+/// Every game state as it appears in responses' `game_state`, with a description
+pub fn get_game_states() -> Vec<GameStateInfo> {
+    GAME_STATES.iter()
+        .map(|&state| GameStateInfo {
+            state,
+            description: describe_game_state(state).to_string(),
+            finished: matches!(state, GameState::Player1Win | GameState::Player2Win | GameState::Draw),
+        })
+        .collect()
+}
+
 /// Create a battle between player (using prefab team) and NPC
+/// The NPC uses a random team for its difficulty unless `opponent_team_id` names a prefab,
+/// and `opponent_level_cap` scales down any NPC Pokemon above the cap
 pub fn create_mvp_battle(
     battle_id: String,
     player_name: String,
     team_id: &str,
     opponent_id: &str,
+    opponent_team_id: Option<&str>,
+    opponent_level_cap: Option<u8>,
 ) -> Result<BattleState, ApiError> {
+    validate_player_name(&player_name)?;
+
     // Create player from prefab team
     let player = pokemon_adventure::prefab_teams::create_battle_player_from_prefab(
         team_id,
@@ -78,14 +206,86 @@ pub fn create_mvp_battle(
         _ => return Err(ApiError::validation_error(format!("Unknown opponent: {}", opponent_id))),
     };
 
-    let npc = pokemon_adventure::prefab_teams::create_random_npc_team(npc_difficulty)
-        .map_err(|e| ApiError::validation_error(e))?;
+    let mut npc = match opponent_team_id {
+        Some(npc_team_id) => {
+            let npc_name = get_npc_opponents()
+                .into_iter()
+                .find(|o| o.id == opponent_id)
+                .map(|o| o.name)
+                .unwrap_or_else(|| format!("NPC Trainer ({})", npc_difficulty));
+
+            let mut npc = pokemon_adventure::prefab_teams::create_battle_player_from_prefab(
+                npc_team_id,
+                "npc".to_string(),
+                npc_name,
+            ).map_err(|e| ApiError::validation_error(e))?;
+            npc.player_type = PlayerType::NPC;
+            npc
+        }
+        None => pokemon_adventure::prefab_teams::create_random_npc_team(npc_difficulty)
+            .map_err(|e| ApiError::validation_error(e))?,
+    };
+
+    if let Some(level_cap) = opponent_level_cap {
+        apply_level_cap(&mut npc, level_cap)?;
+    }
 
     // Create battle state
     let battle_state = BattleState::new(battle_id, player, npc);
     Ok(battle_state)
 }
 
+/// Display title for an MVP battle, e.g. "Venusaur Team vs Gym Leader Brock"
+pub fn mvp_battle_title(team_id: &str, opponent_id: &str) -> String {
+    let team_name = get_available_teams_full()
+        .iter()
+        .find(|team| team.id == team_id)
+        .map(|team| team.name.clone())
+        .unwrap_or_else(|| team_id.to_string());
+
+    let opponent_name = get_npc_opponents()
+        .into_iter()
+        .find(|opponent| opponent.id == opponent_id)
+        .map(|opponent| opponent.name)
+        .unwrap_or_else(|| opponent_id.to_string());
+
+    format!("{} vs {}", team_name, opponent_name)
+}
+
+/// Whether the battle has reached a terminal state
+pub fn is_battle_finished(battle_state: &BattleState) -> bool {
+    matches!(battle_state.game_state, GameState::Player1Win | GameState::Player2Win | GameState::Draw)
+}
+
+/// Player index of the winner, if the battle ended with one
+pub fn winner_index(battle_state: &BattleState) -> Option<usize> {
+    match battle_state.game_state {
+        GameState::Player1Win => Some(0),
+        GameState::Player2Win => Some(1),
+        _ => None,
+    }
+}
+
+/// Final result of a finished battle
+#[derive(Debug)]
+pub struct BattleOutcome {
+    pub result: GameState,
+    pub winner_player_id: Option<PlayerId>,
+}
+
+/// Get the outcome of a finished battle, or None while it is still in progress
+pub fn get_battle_outcome(battle_state: &BattleState) -> Option<BattleOutcome> {
+    if !is_battle_finished(battle_state) {
+        return None;
+    }
+
+    Some(BattleOutcome {
+        result: battle_state.game_state,
+        winner_player_id: winner_index(battle_state)
+            .map(|index| PlayerId(battle_state.players[index].player_id.clone())),
+    })
+}
+
 /// Create a new battle state from team configurations
 pub fn create_battle(
     battle_id: String,
@@ -93,10 +293,22 @@ pub fn create_battle(
     player1_team: &[TeamPokemon],
     player2_id: PlayerId,
     player2_team: &[TeamPokemon],
+    level_budget: Option<u32>,
+    allow_illegal_moves: bool,
 ) -> Result<BattleState, ApiError> {
+    // Validate level budgets for budget formats
+    if let Some(budget) = level_budget {
+        validate_level_budget(player1_team, budget)?;
+        validate_level_budget(player2_team, budget)?;
+    }
+
+    // Player ids double as display names in two-player battles
+    validate_player_name(&player1_id.0)?;
+    validate_player_name(&player2_id.0)?;
+
     // Validate and create teams
-    let team1 = create_pokemon_team(player1_team)?;
-    let team2 = create_pokemon_team(player2_team)?;
+    let team1 = create_pokemon_team(player1_team, allow_illegal_moves)?;
+    let team2 = create_pokemon_team(player2_team, allow_illegal_moves)?;
 
     // Create battle players
     let player1 = BattlePlayer::new(
@@ -116,191 +328,1290 @@ pub fn create_battle(
     Ok(battle_state)
 }
 
+/// Instantiate a team exactly as battle creation would, without starting a battle
+/// Move legality is left to battle creation and `validate_team`, so sandbox teams preview too
+pub fn preview_team(team_config: &[TeamPokemon]) -> Result<Vec<PokemonDetailView>, ApiError> {
+    Ok(create_pokemon_team(team_config, true)?
+        .iter()
+        .enumerate()
+        .map(|(slot_id, pokemon)| PokemonDetailView {
+            slot_id,
+            name: display_name(&pokemon.name),
+            species: pokemon.species,
+            level: pokemon.level,
+            current_hp: pokemon.current_hp(),
+            max_hp: pokemon.max_hp(),
+            stats: pokemon.stats.clone(),
+            moves: move_views(pokemon),
+            status: pokemon.status,
+            charging_move: None,
+        })
+        .collect())
+}
+
+/// Compare two teams by type matchup for every cross-team pairing
+pub fn team_matchup(
+    team_a: &[TeamPokemon],
+    team_b: &[TeamPokemon],
+) -> Result<Vec<MatchupPairing>, ApiError> {
+    // Run both teams through the same validation used for battle creation, sandbox teams included
+    create_pokemon_team(team_a, true)?;
+    create_pokemon_team(team_b, true)?;
+
+    let mut pairings = Vec::new();
+
+    for (a_index, a) in team_a.iter().enumerate() {
+        for (b_index, b) in team_b.iter().enumerate() {
+            let a_multiplier = best_move_multiplier(&a.moves, b.species)?;
+            let b_multiplier = best_move_multiplier(&b.moves, a.species)?;
+
+            let advantage = if a_multiplier > b_multiplier {
+                "team_a"
+            } else if b_multiplier > a_multiplier {
+                "team_b"
+            } else {
+                "even"
+            };
+
+            pairings.push(MatchupPairing {
+                team_a_index: a_index,
+                team_b_index: b_index,
+                team_a_species: a.species,
+                team_b_species: b.species,
+                team_a_best_multiplier: a_multiplier,
+                team_b_best_multiplier: b_multiplier,
+                advantage: advantage.to_string(),
+            });
+        }
+    }
+
+    Ok(pairings)
+}
+
+/// Get the type effectiveness multiplier of an attacking type against a species
+/// Returns the species' types alongside the multiplier
+pub fn species_effectiveness(
+    move_type: PokemonType,
+    defender_species: Species,
+) -> Result<(Vec<PokemonType>, f32), ApiError> {
+    let defender_types = species_types(defender_species)?;
+    let multiplier = type_effectiveness(move_type, &defender_types);
+    Ok((defender_types, multiplier))
+}
+
+/// Get the type effectiveness multiplier of an attacking type against a defender's types
+pub fn type_effectiveness(move_type: PokemonType, defender_types: &[PokemonType]) -> f32 {
+    pokemon_adventure::battle::stats::get_type_effectiveness(move_type, defender_types) as f32
+}
+
 /// Submit a player action and process the battle forward
 /// This implements the "game tick" loop from the API plan
 /// Returns the updated battle state and events that occurred during processing
+/// Works on a copy, so on any error the caller's state still lacks the queued action
+/// With a battle seed, every turn's RNG is derived from it; see `turn_seed`
 pub fn submit_action(
+    battle_state: &BattleState,
+    player_id: &PlayerId,
+    action: PlayerAction,
+    rules: &BattleRules,
+    battle_seed: Option<u64>,
+) -> Result<(BattleState, TickEvents), ApiError> {
+    // Validate and apply the action to a copy of the battle state
+    let battle_state = queue_action(battle_state.clone(), player_id, action, rules)?;
+
+    // Process battle forward as far as possible ("game tick" loop)
+    resolve_queued_actions(battle_state, battle_seed)
+}
+
+/// Concede at once: queue a forfeit and resolve it without waiting on the opponent
+/// An NPC's or already-submitted action lets the engine resolve the turn as usual;
+/// a human opponent who has not acted yet would hold it open, so the API decides the loss itself
+pub fn forfeit(
+    battle_state: &BattleState,
+    player_id: &PlayerId,
+    rules: &BattleRules,
+    battle_seed: Option<u64>,
+) -> Result<(BattleState, TickEvents), ApiError> {
+    let player_index = get_player_index(battle_state, player_id)?;
+    let (mut battle_state, mut tick_events) = submit_action(battle_state, player_id, PlayerAction::Forfeit, rules, battle_seed)?;
+
+    if !is_battle_finished(&battle_state) {
+        tick_events.resolutions.push(TurnResolution {
+            seed: None,
+            actions: battle_state.action_queue.clone(),
+            unseeded: false,
+        });
+        apply_forfeit(&mut battle_state, player_index);
+        tick_events.events.push(format!("{} forfeited the battle.", player_id.0));
+        tick_events.structured_events.push(BattleEventDto::Other);
+    }
+
+    Ok((battle_state, tick_events))
+}
+
+/// Validate and queue a player action without processing the battle forward
+/// Used for asynchronous resolution, where `resolve_queued_actions` runs later
+pub fn queue_action(
     mut battle_state: BattleState,
     player_id: &PlayerId,
     action: PlayerAction,
-) -> Result<(BattleState, Vec<String>), ApiError> {
+    rules: &BattleRules,
+) -> Result<BattleState, ApiError> {
     // Determine which player is acting
     let player_index = get_player_index(&battle_state, player_id)?;
 
     // Validate the action is legal in current game state
     validate_action_context(&battle_state, player_index, &action)?;
 
+    // Validate the action is allowed by the battle's format rules
+    validate_action_rules(&battle_state, &action, rules)?;
+
+    // Validate a move action names a move the active Pokemon actually has
+    validate_move_slot(&battle_state, player_index, &action)?;
+
+    // Validate a switch brings in a different Pokemon than the active one
+    validate_switch_target(&battle_state, player_index, &action)?;
+
     // Validate the specific action details
     validate_player_action(&battle_state, player_index, &action)
         .map_err(|e| ApiError::invalid_action(e))?;
 
     // Apply the action to the battle state
     battle_state.action_queue[player_index] = Some(action);
+    Ok(battle_state)
+}
 
-    // Process battle forward as far as possible ("game tick" loop)
-    let turn_events = process_battle_ticks(&mut battle_state)?;
-
+/// Process a battle with already-queued actions forward as far as possible
+pub fn resolve_queued_actions(
+    mut battle_state: BattleState,
+    battle_seed: Option<u64>,
+) -> Result<(BattleState, TickEvents), ApiError> {
+    let turn_events = process_battle_ticks(&mut battle_state, battle_seed)?;
     Ok((battle_state, turn_events))
 }
 
 /// Get all valid actions for a player
+/// When the active Pokemon has no PP left, the engine substitutes Struggle for any move,
+/// so the move actions collapse into a single `UseMove { move_index: 0 }`
 pub fn get_player_valid_actions(
     battle_state: &BattleState,
     player_id: &PlayerId,
+    rules: &BattleRules,
 ) -> Result<Vec<PlayerAction>, ApiError> {
     let player_index = get_player_index(battle_state, player_id)?;
-    Ok(get_valid_actions(battle_state, player_index))
+    Ok(valid_actions_for_index(battle_state, player_index, rules))
 }
 
-/// Validate that a player is authorized to act on behalf of this battle
-pub fn validate_player_authorization(
+fn valid_actions_for_index(
     battle_state: &BattleState,
-    player_id: &PlayerId,
-) -> Result<usize, ApiError> {
-    get_player_index(battle_state, player_id)
+    player_index: usize,
+    rules: &BattleRules,
+) -> Vec<PlayerAction> {
+    let mut actions = get_valid_actions(battle_state, player_index);
+
+    actions.retain(|action| validate_action_rules(battle_state, action, rules).is_ok());
+
+    if will_struggle(&battle_state.players[player_index]) {
+        let had_move = actions.iter().any(|a| matches!(a, PlayerAction::UseMove { .. }));
+        actions.retain(|a| !matches!(a, PlayerAction::UseMove { .. }));
+        if had_move {
+            actions.insert(0, PlayerAction::UseMove { move_index: 0 });
+        }
+    }
+
+    actions
 }
 
-/// Get current battle state information for a specific player
-pub fn get_battle_state_for_player(
+/// Get all valid actions for a player, each with a menu description
+pub fn get_player_annotated_actions(
     battle_state: &BattleState,
-    requesting_player_id: &PlayerId,
-) -> Result<PlayerBattleView, ApiError> {
-    let player_index = get_player_index(battle_state, requesting_player_id)?;
-    let opponent_index = 1 - player_index;
-
+    player_id: &PlayerId,
+    rules: &BattleRules,
+) -> Result<Vec<AnnotatedAction>, ApiError> {
+    let player_index = get_player_index(battle_state, player_id)?;
     let player = &battle_state.players[player_index];
-    let opponent = &battle_state.players[opponent_index];
 
-    Ok(PlayerBattleView {
-        game_state: battle_state.game_state,
-        turn_number: battle_state.turn_number,
-        player_team: create_player_team_view(player),
-        opponent_public_info: create_opponent_view(opponent),
-        can_act: can_player_act(battle_state, player_index),
-    })
+    Ok(valid_actions_for_index(battle_state, player_index, rules)
+        .into_iter()
+        .map(|action| {
+            let description = describe_action(player, &action);
+            AnnotatedAction { action, description }
+        })
+        .collect())
 }
 
-/// Data structure for battle state from a player's perspective
-#[derive(Debug)]
-pub struct PlayerBattleView {
-    pub game_state: GameState,
-    pub turn_number: u32,
-    pub player_team: TeamView,
-    pub opponent_public_info: OpponentView,
-    pub can_act: bool,
+/// Priority bracket of a move from its move data; 0 unless the move carries a priority effect
+pub fn move_priority(move_: Move) -> i8 {
+    get_move_data(move_)
+        .and_then(|move_data| move_data.effects.iter().find_map(|effect| match effect {
+            MoveEffect::Priority(priority) => Some(*priority),
+            _ => None,
+        }))
+        .unwrap_or(0)
 }
 
-#[derive(Debug)]
-pub struct TeamView {
-    pub active_pokemon: Option<PokemonDetailView>,
-    pub team_pokemon: Vec<Option<PokemonSummaryView>>,
-}
+/// Gen 1 stat stage multipliers for stages -6..=6 as (numerator, denominator); +1 is x1.5, -1 is x0.66
+const STAGE_RATIOS: [(u32, u32); 13] = [
+    (25, 100), (28, 100), (33, 100), (40, 100), (50, 100), (66, 100),
+    (1, 1),
+    (15, 10), (2, 1), (25, 10), (3, 1), (35, 10), (4, 1),
+];
 
-#[derive(Debug)]
-pub struct PokemonDetailView {
-    pub name: String,
-    pub species: Species,
-    pub level: u8,
-    pub current_hp: u16,
-    pub max_hp: u16,
-    pub stats: pokemon_adventure::pokemon::CurrentStats,
-    pub moves: Vec<Option<MoveView>>,
-    pub status: Option<pokemon_adventure::pokemon::StatusCondition>,
+/// Multiplier ratio for a stat stage, clamped to the usual -6..=6 range
+fn stage_ratio(stage: i8) -> (u32, u32) {
+    STAGE_RATIOS[(stage.clamp(-6, 6) + 6) as usize]
 }
 
-#[derive(Debug)]
-pub struct PokemonSummaryView {
-    pub name: String,
-    pub species: Species,
-    pub level: u8,
-    pub current_hp: u16,
-    pub max_hp: u16,
-    pub is_fainted: bool,
-    pub status: Option<pokemon_adventure::pokemon::StatusCondition>,
+/// Chance a move lands, the Gen 1 way: base accuracy scaled to 0..=255, then the attacker's accuracy stage and the
+/// defender's evasion stage (as its inverse) applied one after the other, and a hit when a 0..=255 roll is below it.
+/// The roll caps every move at 255/256. Moves without an accuracy value never miss; other miss sources
+/// (semi-invulnerable targets, status) are ignored
+pub fn hit_chance(move_: Move, attacker: &BattlePlayer, defender: &BattlePlayer) -> f32 {
+    let Some(accuracy) = get_move_data(move_).and_then(|move_data| move_data.accuracy) else {
+        return 1.0;
+    };
+    let mut threshold = accuracy as u32 * 255 / 100;
+    for stage in [attacker.get_stat_stage(StatType::Accuracy), -defender.get_stat_stage(StatType::Evasion)] {
+        let (numerator, denominator) = stage_ratio(stage);
+        threshold = threshold * numerator / denominator;
+    }
+    threshold.clamp(1, 255) as f32 / 256.0
 }
 
-#[derive(Debug)]
-pub struct MoveView {
-    pub move_: Move,
-    pub pp: u8,
-    pub max_pp: u8,
-}
+/// Priority and hit chance of the move behind each valid UseMove action, for turn planning
+pub fn analyze_moves(
+    battle_state: &BattleState,
+    player_id: &PlayerId,
+    rules: &BattleRules,
+) -> Result<Vec<MoveAnalysis>, ApiError> {
+    let player_index = get_player_index(battle_state, player_id)?;
+    let attacker = &battle_state.players[player_index];
+    let defender = &battle_state.players[1 - player_index];
+    let Some(active) = attacker.active_pokemon() else {
+        return Ok(Vec::new());
+    };
 
-#[derive(Debug)]
-pub struct OpponentView {
-    pub player_name: String,
-    pub active_pokemon: Option<PokemonSummaryView>,
-    pub remaining_pokemon_count: usize,
+    Ok(valid_actions_for_index(battle_state, player_index, rules)
+        .into_iter()
+        .filter_map(|action| match action {
+            PlayerAction::UseMove { move_index } => Some(move_index),
+            _ => None,
+        })
+        .filter_map(|move_index| {
+            let move_ = active.moves.get(move_index)?.as_ref()?.move_;
+            Some(MoveAnalysis {
+                move_index,
+                move_,
+                priority: move_priority(move_),
+                hit_chance: hit_chance(move_, attacker, defender),
+            })
+        })
+        .collect())
 }
 
-// Helper functions
+/// Preview the type matchup each valid switch target would face against the opponent's active Pokemon
+/// Only public information is used: the incoming Pokemon's moves and the opponent's types
+pub fn get_switch_previews(
+    battle_state: &BattleState,
+    player_id: &PlayerId,
+    rules: &BattleRules,
+) -> Result<Vec<SwitchPreview>, ApiError> {
+    let player_index = get_player_index(battle_state, player_id)?;
+    let player = &battle_state.players[player_index];
+    let opponent_species = battle_state.players[1 - player_index].active_pokemon()
+        .map(|pokemon| pokemon.species);
 
-fn create_pokemon_team(team_config: &[TeamPokemon]) -> Result<Vec<PokemonInst>, ApiError> {
-    if team_config.is_empty() {
-        return Err(ApiError::validation_error("Team cannot be empty"));
-    }
+    valid_actions_for_index(battle_state, player_index, rules)
+        .into_iter()
+        .filter_map(|action| match action {
+            PlayerAction::SwitchPokemon { team_index } => Some(team_index),
+            _ => None,
+        })
+        .filter_map(|team_index| player.team.get(team_index)?.as_ref().map(|pokemon| (team_index, pokemon)))
+        .map(|(team_index, pokemon)| {
+            let (offensive_multiplier, defensive_multiplier) = match opponent_species {
+                Some(opponent) => {
+                    let moves: Vec<Move> = pokemon.moves.iter()
+                        .filter_map(|m| m.as_ref().map(|move_inst| move_inst.move_))
+                        .collect();
+                    let incoming_types = species_types(pokemon.species)?;
+                    let defensive = species_types(opponent)?.into_iter()
+                        .map(|attacking_type| type_effectiveness(attacking_type, &incoming_types))
+                        .fold(0.0, f32::max);
+                    (Some(best_move_multiplier(&moves, opponent)?), Some(defensive))
+                }
+                None => (None, None),
+            };
 
-    if team_config.len() > 6 {
-        return Err(ApiError::validation_error("Team cannot have more than 6 Pokemon"));
-    }
+            Ok(SwitchPreview {
+                team_index,
+                species: pokemon.species,
+                opponent_species,
+                offensive_multiplier,
+                defensive_multiplier,
+            })
+        })
+        .collect()
+}
 
-    let mut pokemon_team = Vec::new();
+/// Apply an admin patch to a battle state and check the result is still a playable battle
+/// The patch is a JSON merge patch (RFC 7386) over the serialized state, extended so an object
+/// with index keys patches individual array elements, e.g. `{"players": {"0": {"team": {"1": ...}}}}`
+pub fn patch_battle_state(battle_state: &BattleState, patch: &serde_json::Value) -> Result<BattleState, ApiError> {
+    let mut state_json = serde_json::to_value(battle_state)
+        .map_err(|e| ApiError::InternalError { message: format!("Failed to serialize battle state: {}", e) })?;
+    merge_patch(&mut state_json, patch)?;
 
-    for team_pokemon in team_config {
-        // Validate level range
-        if team_pokemon.level == 0 || team_pokemon.level > 100 {
-            return Err(ApiError::validation_error(
-                format!("Invalid level {} for {:?}", team_pokemon.level, team_pokemon.species)
-            ));
-        }
+    let patched: BattleState = serde_json::from_value(state_json)
+        .map_err(|e| ApiError::validation_error(format!("Patch does not produce a valid battle state: {}", e)))?;
+    validate_patched_state(battle_state, &patched)?;
+    Ok(patched)
+}
 
-        // Get species data
-        let species_data = get_species_data(team_pokemon.species)
-            .ok_or_else(|| ApiError::validation_error(
-                format!("Species data not found for {:?}", team_pokemon.species)
-            ))?;
+fn merge_patch(target: &mut serde_json::Value, patch: &serde_json::Value) -> Result<(), ApiError> {
+    use serde_json::Value;
 
-        // Validate moves
-        if team_pokemon.moves.is_empty() || team_pokemon.moves.len() > 4 {
-            return Err(ApiError::validation_error(
-                "Pokemon must have 1-4 moves"
-            ));
+    match (target, patch) {
+        (Value::Object(target_fields), Value::Object(patch_fields)) => {
+            for (key, patch_value) in patch_fields {
+                if patch_value.is_null() {
+                    target_fields.remove(key);
+                } else {
+                    merge_patch(target_fields.entry(key.clone()).or_insert(Value::Null), patch_value)?;
+                }
+            }
+        }
+        (Value::Array(target_items), Value::Object(patch_fields)) => {
+            for (key, patch_value) in patch_fields {
+                let item = key.parse::<usize>().ok()
+                    .and_then(|index| target_items.get_mut(index))
+                    .ok_or_else(|| ApiError::validation_error(format!("Patch index {} is out of range", key)))?;
+                merge_patch(item, patch_value)?;
+            }
         }
+        (target, patch) => *target = patch.clone(),
+    }
+    Ok(())
+}
 
-        // Create Pokemon instance
-        let pokemon = PokemonInst::new(
-            team_pokemon.species,
-            &species_data,
-            team_pokemon.level,
-            None, // Use default IVs
-            Some(team_pokemon.moves.clone()),
-        );
+/// Identity can't be rewritten, and every player needs a healthy-or-fainted team with an active Pokemon
+fn validate_patched_state(original: &BattleState, patched: &BattleState) -> Result<(), ApiError> {
+    if patched.battle_id != original.battle_id {
+        return Err(ApiError::validation_error("Patch cannot change the battle id"));
+    }
 
-        pokemon_team.push(pokemon);
+    for (player, original_player) in patched.players.iter().zip(original.players.iter()) {
+        if player.player_id != original_player.player_id {
+            return Err(ApiError::validation_error("Patch cannot change player ids"));
+        }
+        if player.team.iter().all(|slot| slot.is_none()) {
+            return Err(ApiError::validation_error(format!("{} has no Pokemon", player.player_id)));
+        }
+        if player.active_pokemon().is_none() {
+            return Err(ApiError::validation_error(format!("{} has no active Pokemon", player.player_id)));
+        }
+        for pokemon in player.team.iter().flatten() {
+            if pokemon.current_hp() > pokemon.max_hp() {
+                return Err(ApiError::validation_error(format!(
+                    "{} has {} HP, above its max of {}", pokemon.name, pokemon.current_hp(), pokemon.max_hp()
+                )));
+            }
+            if pokemon.moves.iter().all(|slot| slot.is_none()) {
+                return Err(ApiError::validation_error(format!("{} has no moves", pokemon.name)));
+            }
+        }
     }
+    Ok(())
+}
 
-    Ok(pokemon_team)
+/// Why a turn has or has not resolved, as the tick loop sees it
+pub struct QueueDebugState {
+    pub slots: Vec<ActionQueueSlot>,
+    pub ready_for_turn_resolution: bool, // The engine's own readiness check
+    pub awaiting_second_replacement: bool, // The API holds a lone replacement after a double faint
 }
 
-fn get_player_index(battle_state: &BattleState, player_id: &PlayerId) -> Result<usize, ApiError> {
-    if battle_state.players[0].player_id == player_id.0 {
-        Ok(0)
-    } else if battle_state.players[1].player_id == player_id.0 {
-        Ok(1)
-    } else {
-        Err(ApiError::player_not_authorized(player_id))
+/// Read-only diagnosis of the action queue for operators; queued actions are included,
+/// so this must only back admin endpoints
+pub fn debug_queue_state(battle_state: &BattleState) -> QueueDebugState {
+    let slots = battle_state.action_queue.iter()
+        .enumerate()
+        .map(|(player_index, action)| ActionQueueSlot {
+            player_index,
+            player_id: PlayerId(battle_state.players[player_index].player_id.clone()),
+            action_submitted: action.is_some(),
+            action: action.clone(),
+        })
+        .collect();
+
+    QueueDebugState {
+        slots,
+        ready_for_turn_resolution: ready_for_turn_resolution(battle_state),
+        awaiting_second_replacement: awaiting_second_replacement(battle_state),
     }
 }
 
-fn validate_action_context(
+/// Validate that a player is authorized to act on behalf of this battle
+pub fn validate_player_authorization(
     battle_state: &BattleState,
-    player_index: usize,
-    action: &PlayerAction,
-) -> Result<(), ApiError> {
-    match battle_state.game_state {
-        GameState::WaitingForActions => {
-            // Normal turn - validate player can act
-            if battle_state.action_queue[player_index].is_some() {
+    player_id: &PlayerId,
+) -> Result<usize, ApiError> {
+    get_player_index(battle_state, player_id)
+}
+
+/// Total damage each Pokemon dealt and received, per team in slot order
+/// Uses the unredacted events, so callers should only expose it once the battle is over.
+/// Damage is credited to the opposing Pokemon that last used a move in the same turn;
+/// damage with no mover that turn (e.g. residual poison) counts as received only.
+/// Damage is attributed to the side the event names; older events without one fall back to
+/// the species, and damage whose species is on both teams then cannot be attributed and is skipped.
+pub fn damage_report(battle_state: &BattleState, turn_logs: &[TurnLog]) -> [Vec<PokemonDamage>; 2] {
+    let mut report: [Vec<PokemonDamage>; 2] = [0, 1].map(|player_index| {
+        battle_state.players[player_index].team.iter()
+            .enumerate()
+            .filter_map(|(slot_id, slot)| slot.as_ref().map(|pokemon| PokemonDamage {
+                slot_id,
+                species: pokemon.species,
+                damage_dealt: 0,
+                damage_received: 0,
+            }))
+            .collect()
+    });
+    let slot_of = |report: &[Vec<PokemonDamage>; 2], player_index: usize, species: Species| {
+        report[player_index].iter().position(|entry| entry.species == species)
+    };
+
+    for turn_log in turn_logs {
+        let mut last_mover: [Option<usize>; 2] = [None, None]; // Report index per player, reset each turn
+        for event in unredacted_events(turn_log) {
+            match event {
+                BattleEventDto::MoveUsed { player_index, pokemon, .. } if player_index < 2 => {
+                    last_mover[player_index] = slot_of(&report, player_index, pokemon);
+                }
+                BattleEventDto::Damage { target, target_player, damage: Some(damage), .. } => {
+                    let owners: Vec<usize> = match target_player {
+                        Some(player_index) if player_index < 2 => vec![player_index],
+                        _ => (0..2)
+                            .filter(|&player_index| slot_of(&report, player_index, target).is_some())
+                            .collect(),
+                    };
+                    let [owner] = owners[..] else { continue };
+                    if let Some(target_slot) = slot_of(&report, owner, target) {
+                        report[owner][target_slot].damage_received += damage as u32;
+                    }
+                    if let Some(attacker_slot) = last_mover[1 - owner] {
+                        report[1 - owner][attacker_slot].damage_dealt += damage as u32;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    report
+}
+
+/// Rough number of turns until one side runs out of HP, at each side's average damage taken per turn
+/// None before any turn has dealt damage, or once the battle is over
+pub fn estimate_remaining_turns(battle_state: &BattleState, turn_logs: &[TurnLog]) -> Option<u32> {
+    let turns_played = turns_resolved(battle_state);
+    if is_battle_finished(battle_state) || turns_played == 0 {
+        return None;
+    }
+
+    let damage_taken = damage_report(battle_state, turn_logs)
+        .map(|team| team.iter().map(|entry| entry.damage_received).sum::<u32>());
+
+    (0..2)
+        .filter(|&player_index| damage_taken[player_index] > 0)
+        .map(|player_index| {
+            let remaining_hp: u32 = battle_state.players[player_index].team.iter()
+                .flatten()
+                .map(|pokemon| pokemon.current_hp() as u32)
+                .sum();
+            let damage_per_turn = damage_taken[player_index] as f64 / turns_played as f64;
+            (remaining_hp as f64 / damage_per_turn).ceil() as u32
+        })
+        .min()
+}
+
+/// A turn's structured events with every player's private details restored
+fn unredacted_events(turn_log: &TurnLog) -> Vec<BattleEventDto> {
+    let mut events = turn_log.structured_events.clone();
+    for private in turn_log.private_events.iter().flatten() {
+        if let Some(event) = events.get_mut(private.index) {
+            *event = private.structured_event.clone();
+        }
+    }
+    events
+}
+
+/// Raw engine state as JSON, for diffing against the API's views
+/// With a viewer, the opponent's bench, active Pokemon's moveset and queued action are nulled out
+pub fn debug_battle_state(battle_state: &BattleState, viewer_index: Option<usize>) -> Result<serde_json::Value, ApiError> {
+    let mut raw_state = serde_json::to_value(battle_state).map_err(|e| ApiError::InternalError {
+        message: format!("Failed to serialize battle state: {}", e),
+    })?;
+
+    if let Some(viewer_index) = viewer_index {
+        let opponent_index = 1 - viewer_index;
+        let active_slot_id = active_slot(&battle_state.players[opponent_index]).map(|(slot_id, _)| slot_id);
+
+        if let Some(team) = raw_state.pointer_mut(&format!("/players/{}/team", opponent_index)).and_then(|t| t.as_array_mut()) {
+            for (slot_id, slot) in team.iter_mut().enumerate() {
+                if Some(slot_id) == active_slot_id {
+                    // Unrevealed moves and exact PP are hidden in battle
+                    if let Some(moves) = slot.get_mut("moves") {
+                        *moves = serde_json::Value::Null;
+                    }
+                } else {
+                    *slot = serde_json::Value::Null;
+                }
+            }
+        }
+        if let Some(queued) = raw_state.pointer_mut(&format!("/action_queue/{}", opponent_index)) {
+            *queued = serde_json::Value::Null;
+        }
+    }
+
+    Ok(raw_state)
+}
+
+/// Get PP for the requesting player's active Pokemon's known moves
+/// Returns an empty list when there is no active Pokemon
+pub fn get_active_move_pp(
+    battle_state: &BattleState,
+    player_id: &PlayerId,
+) -> Result<Vec<MoveView>, ApiError> {
+    let player_index = get_player_index(battle_state, player_id)?;
+
+    Ok(battle_state.players[player_index].active_pokemon()
+        .map(|pokemon| move_views(pokemon).into_iter().flatten().collect())
+        .unwrap_or_default())
+}
+
+/// Moves the opponent's active Pokemon has revealed by using them, counted from the turn logs
+/// PP is what a player could count themselves: max PP minus times used, omitted when `include_pp` is false
+pub fn get_revealed_opponent_moves(
+    battle_state: &BattleState,
+    player_id: &PlayerId,
+    turn_logs: &[TurnLog],
+    include_pp: bool,
+) -> Result<Vec<RevealedMoveView>, ApiError> {
+    let opponent_index = 1 - get_player_index(battle_state, player_id)?;
+    let Some(active) = battle_state.players[opponent_index].active_pokemon() else {
+        return Ok(Vec::new());
+    };
+
+    let mut revealed: Vec<(Move, u32)> = Vec::new();
+    for event in turn_logs.iter().flat_map(|log| &log.structured_events) {
+        let BattleEventDto::MoveUsed { player_index, pokemon, move_used } = event else { continue };
+        if *player_index != opponent_index || *pokemon != active.species {
+            continue;
+        }
+        match revealed.iter_mut().find(|(move_, _)| move_ == move_used) {
+            Some((_, times_used)) => *times_used += 1,
+            None => revealed.push((*move_used, 1)),
+        }
+    }
+
+    // Moves outside the known moveset (e.g. Struggle) have no PP to track
+    Ok(revealed.into_iter()
+        .filter_map(|(move_, times_used)| {
+            let move_inst = active.moves.iter().flatten().find(|m| m.move_ == move_)?;
+            Some(RevealedMoveView {
+                move_,
+                times_used,
+                revealed_move_pp: include_pp
+                    .then(|| move_inst.max_pp().saturating_sub(times_used.min(u8::MAX as u32) as u8)),
+            })
+        })
+        .collect())
+}
+
+/// Get current battle state information for a specific player
+pub fn get_battle_state_for_player(
+    battle_state: &BattleState,
+    requesting_player_id: &PlayerId,
+    rules: &BattleRules,
+) -> Result<PlayerBattleView, ApiError> {
+    let player_index = get_player_index(battle_state, requesting_player_id)?;
+    let opponent_index = 1 - player_index;
+
+    let player = &battle_state.players[player_index];
+    let opponent = &battle_state.players[opponent_index];
+    let can_switch = valid_actions_for_index(battle_state, player_index, rules).iter()
+        .any(|action| matches!(action, PlayerAction::SwitchPokemon { .. }));
+
+    Ok(PlayerBattleView {
+        game_state: battle_state.game_state,
+        turn_number: current_turn(battle_state),
+        turns_resolved: turns_resolved(battle_state),
+        player_team: create_player_team_view(player),
+        opponent_public_info: create_opponent_view(opponent),
+        can_act: can_player_act(battle_state, player_index),
+        will_struggle: will_struggle(player),
+        can_switch,
+        trapped_reason: if can_switch { None } else { trapped_reason(player) },
+        field_conditions: field_conditions(battle_state, player_index),
+    })
+}
+
+/// Conditions in effect on the field, from `viewer_index`'s perspective
+/// The engine tracks no weather or entry hazards, only per-side team conditions,
+/// each stored with the turns left on its timer
+fn field_conditions(battle_state: &BattleState, viewer_index: usize) -> Vec<ConditionDto> {
+    let mut conditions: Vec<ConditionDto> = battle_state.players.iter()
+        .enumerate()
+        .flat_map(|(player_index, player)| {
+            let side = if player_index == viewer_index { "player" } else { "opponent" };
+            player.team_conditions.iter().map(move |(condition, turns_remaining)| ConditionDto {
+                kind: snake_case(&format!("{:?}", condition)),
+                side: Some(side.to_string()),
+                turns_remaining: Some(*turns_remaining),
+            })
+        })
+        .collect();
+
+    // Conditions are stored in a map, so order them for stable responses
+    conditions.sort_by(|a, b| (&a.side, &a.kind).cmp(&(&b.side, &b.kind)));
+    conditions
+}
+
+/// "LightScreen" -> "light_screen"
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+/// Events produced while processing battle ticks
+/// `events[i]` and `structured_events[i]` always describe the same event
+/// (pacing markers pair with an empty string)
+#[derive(Debug, Default)]
+pub struct TickEvents {
+    pub events: Vec<String>, // Formatted event strings, empty only for pacing markers
+    pub structured_events: Vec<BattleEventDto>, // Same length as `events`
+    pub private_events: [Vec<PrivateEvent>; 2], // Unredacted events, visible to the indexed player only
+    pub draw_reason: Option<String>, // Set when processing ended the battle in an API-decided draw
+    pub resolutions: Vec<TurnResolution>, // Replay inputs, one per turn resolved while processing
+}
+
+/// Unmasked view of both teams; callers decide who may see it
+pub fn get_spectator_view(battle_state: &BattleState) -> SpectatorBattleView {
+    SpectatorBattleView {
+        game_state: battle_state.game_state,
+        turn_number: current_turn(battle_state),
+        turns_resolved: turns_resolved(battle_state),
+        players: battle_state.players.iter()
+            .map(|player| SpectatorPlayerView {
+                player_id: PlayerId(player.player_id.clone()),
+                player_name: display_name(&player.player_name),
+                team: create_player_team_view(player),
+            })
+            .collect(),
+    }
+}
+
+/// Pokemon Showdown protocol lines for a battle, for the community's replay viewers
+/// Rendered from the public structured log, so HP is a percentage as in Showdown's own
+/// replays. Damage names its side; status, and damage in older logs, whose target species is
+/// on both teams is attributed to the opponent of the last Pokemon to move. Logs from before
+/// structured events existed add nothing.
+pub fn showdown_log(battle_state: &BattleState, turn_logs: &[TurnLog]) -> Vec<String> {
+    let side_id = |player_index: usize| format!("p{}", player_index + 1);
+    let name_of = |player_index: usize, species: Species| {
+        battle_state.players[player_index].team.iter().flatten()
+            .find(|pokemon| pokemon.species == species)
+            .map(|pokemon| display_name(&pokemon.name))
+            .unwrap_or_else(|| readable_name(&species))
+    };
+    let ident = |player_index: usize, species: Species| {
+        format!("{}a: {}", side_id(player_index), name_of(player_index, species))
+    };
+    let details = |player_index: usize, species: Species| {
+        match battle_state.players[player_index].team.iter().flatten().find(|pokemon| pokemon.species == species) {
+            Some(pokemon) => format!("{}, L{}", readable_name(&species), pokemon.level),
+            None => readable_name(&species),
+        }
+    };
+    let owners_of = |species: Species| -> Vec<usize> {
+        (0..battle_state.players.len())
+            .filter(|&index| battle_state.players[index].team.iter().flatten().any(|pokemon| pokemon.species == species))
+            .collect()
+    };
+
+    let mut lines = Vec::new();
+    for (player_index, player) in battle_state.players.iter().enumerate() {
+        lines.push(format!("|player|{}|{}|", side_id(player_index), display_name(&player.player_name)));
+    }
+    for (player_index, player) in battle_state.players.iter().enumerate() {
+        lines.push(format!("|teamsize|{}|{}|", side_id(player_index), player.team.iter().flatten().count()));
+    }
+    lines.push("|gen|1|".to_string());
+    lines.push("|start|".to_string());
+
+    // Every battle opens with each team's first Pokemon at full HP
+    let mut active: Vec<Option<Species>> = battle_state.players.iter()
+        .map(|player| player.team.iter().flatten().next().map(|pokemon| pokemon.species))
+        .collect();
+    let mut hp_percents: Vec<((usize, Species), u8)> = Vec::new(); // Last reported HP per side and species
+    for (player_index, species) in active.iter().enumerate() {
+        if let Some(species) = *species {
+            lines.push(format!("|switch|{}|{}|100/100", ident(player_index, species), details(player_index, species)));
+        }
+    }
+    lines.push("|turn|1|".to_string());
+
+    let mut last_mover: Option<usize> = None;
+    let owner = |species: Species, last_mover: Option<usize>| match owners_of(species).as_slice() {
+        [index] => Some(*index),
+        _ => last_mover.map(|mover| 1 - mover),
+    };
+    for event in turn_logs.iter().flat_map(|log| &log.structured_events) {
+        match event {
+            BattleEventDto::MoveUsed { player_index, pokemon, move_used } => {
+                let target = active.get(1 - player_index).copied().flatten()
+                    .map(|species| ident(1 - player_index, species))
+                    .unwrap_or_default();
+                lines.push(format!("|move|{}|{}|{}", ident(*player_index, *pokemon), readable_name(move_used), target));
+                last_mover = Some(*player_index);
+            }
+            BattleEventDto::Damage { target, target_player, remaining_hp_percent, .. } => {
+                // Without a percentage there is no HP to report, so the line is left out
+                let side = target_player.or_else(|| owner(*target, last_mover));
+                if let (Some(player_index), Some(percent)) = (side, remaining_hp_percent) {
+                    set_hp_percent(&mut hp_percents, (player_index, *target), *percent);
+                    let hp = if *percent == 0 { "0 fnt".to_string() } else { format!("{}/100", percent) };
+                    lines.push(format!("|-damage|{}|{}", ident(player_index, *target), hp));
+                }
+            }
+            BattleEventDto::Faint { player_index, pokemon } => {
+                set_hp_percent(&mut hp_percents, (*player_index, *pokemon), 0);
+                lines.push(format!("|faint|{}", ident(*player_index, *pokemon)));
+            }
+            BattleEventDto::StatusApplied { target, status } => {
+                if let Some(player_index) = owner(*target, last_mover) {
+                    lines.push(format!("|-status|{}|{}", ident(player_index, *target), showdown_status(status)));
+                }
+            }
+            BattleEventDto::SwitchIn { player_index, new_pokemon, .. } => {
+                if let Some(slot) = active.get_mut(*player_index) {
+                    *slot = Some(*new_pokemon);
+                }
+                let percent = hp_percents.iter()
+                    .find(|(key, _)| *key == (*player_index, *new_pokemon))
+                    .map_or(100, |(_, percent)| *percent);
+                lines.push(format!("|switch|{}|{}|{}/100", ident(*player_index, *new_pokemon), details(*player_index, *new_pokemon), percent));
+            }
+            BattleEventDto::TurnBoundary { turn_number } => {
+                lines.push("|upkeep|".to_string());
+                lines.push(format!("|turn|{}|", turn_number + 1));
+            }
+            BattleEventDto::PhaseBoundary { .. } | BattleEventDto::Other => {}
+        }
+    }
+
+    if is_battle_finished(battle_state) {
+        // Trailing turn boundaries announce turns that never started, e.g. one ended by a forfeit
+        while lines.last().is_some_and(|line| line.starts_with("|turn|") || line == "|upkeep|") {
+            lines.pop();
+        }
+        match winner_index(battle_state) {
+            Some(index) => lines.push(format!("|win|{}|", display_name(&battle_state.players[index].player_name))),
+            None => lines.push("|tie|".to_string()),
+        }
+    }
+    lines
+}
+
+/// Record the latest HP percentage reported for a side's Pokemon
+fn set_hp_percent(hp_percents: &mut Vec<((usize, Species), u8)>, key: (usize, Species), percent: u8) {
+    match hp_percents.iter_mut().find(|(existing, _)| *existing == key) {
+        Some((_, stored)) => *stored = percent,
+        None => hp_percents.push((key, percent)),
+    }
+}
+
+/// Engine enum names as players read them (and Showdown spells them), e.g. `QuickAttack` -> "Quick Attack"
+fn readable_name(name: &impl std::fmt::Debug) -> String {
+    let mut spaced = String::new();
+    for (i, c) in format!("{:?}", name).chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            spaced.push(' ');
+        }
+        spaced.push(c);
+    }
+    spaced
+}
+
+/// Showdown's three-letter status code, falling back to the API's status name
+fn showdown_status(status: &StatusCondition) -> String {
+    let kind = status_dto(status).kind;
+    let code = match kind.as_str() {
+        "sleep" => "slp",
+        "burn" => "brn",
+        "freeze" => "frz",
+        "paralysis" => "par",
+        "poison" => "psn",
+        "toxic" | "bad_poison" | "badly_poisoned" => "tox",
+        _ => return kind,
+    };
+    code.to_string()
+}
+
+/// Version of the engine's serialized `BattleState` format stored with each battle
+/// Bump this whenever a `pokemon_adventure` upgrade changes that format
+pub const ENGINE_SCHEMA_VERSION: u32 = 1;
+
+/// Schema version assumed for battles stored before versions were recorded
+pub fn legacy_engine_schema_version() -> u32 {
+    1
+}
+
+/// Ensure a stored battle was serialized by a compatible engine before deserializing its state
+pub fn check_engine_schema_version(battle_id: BattleId, stored_version: u32) -> Result<(), ApiError> {
+    if stored_version != ENGINE_SCHEMA_VERSION {
+        return Err(ApiError::engine_version_mismatch(battle_id, stored_version, ENGINE_SCHEMA_VERSION));
+    }
+    Ok(())
+}
+
+/// Turn numbering shared by every endpoint:
+/// the current turn is the one awaiting actions (`BattleState::turn_number`, starting at 1),
+/// and each resolution advances it, so turns resolved is always one less.
+/// Turn logs are labelled with the turn they resolved, so the newest log is `turns_resolved`
+pub fn current_turn(battle_state: &BattleState) -> u32 {
+    battle_state.turn_number
+}
+
+/// Number of turns resolved so far; see `current_turn`
+pub fn turns_resolved(battle_state: &BattleState) -> u32 {
+    battle_state.turn_number.saturating_sub(1)
+}
+
+/// Draw reason recorded when both players forfeit in the same turn
+pub const MUTUAL_FORFEIT_REASON: &str = "mutual forfeit";
+
+impl TickEvents {
+    pub fn is_empty(&self) -> bool {
+        // A resolution must be logged even without narrative, or replays would skip it
+        self.resolutions.is_empty() && self.structured_events.iter().all(|e| e.is_marker())
+    }
+
+    /// Pacing markers carry an empty narrative entry to preserve index alignment
+    fn push_marker(&mut self, marker: BattleEventDto) {
+        self.events.push(String::new());
+        self.structured_events.push(marker);
+    }
+}
+
+/// Data structure for battle state from a player's perspective
+#[derive(Debug)]
+pub struct PlayerBattleView {
+    pub game_state: GameState,
+    pub turn_number: u32,
+    pub turns_resolved: u32,
+    pub player_team: TeamView,
+    pub opponent_public_info: OpponentView,
+    pub can_act: bool,
+    pub will_struggle: bool,
+    pub can_switch: bool, // A voluntary or forced switch is among the valid actions
+    pub trapped_reason: Option<String>, // Why switching is blocked, when a trapping move holds the active Pokemon
+    pub field_conditions: Vec<ConditionDto>,
+}
+
+/// Both sides in full, for neutral spectators such as a replay UI
+#[derive(Debug)]
+pub struct SpectatorBattleView {
+    pub game_state: GameState,
+    pub turn_number: u32,
+    pub turns_resolved: u32,
+    pub players: Vec<SpectatorPlayerView>, // Player 1 then player 2
+}
+
+#[derive(Debug)]
+pub struct SpectatorPlayerView {
+    pub player_id: PlayerId,
+    pub player_name: String,
+    pub team: TeamView,
+}
+
+#[derive(Debug)]
+pub struct TeamView {
+    pub active_pokemon: Option<PokemonDetailView>,
+    pub team_pokemon: Vec<Option<PokemonSummaryView>>,
+}
+
+#[derive(Debug)]
+pub struct PokemonDetailView {
+    pub slot_id: usize, // Team slot index; stable for the whole battle, unlike names
+    pub name: String,
+    pub species: Species,
+    pub level: u8,
+    pub current_hp: u16,
+    pub max_hp: u16,
+    pub stats: pokemon_adventure::pokemon::CurrentStats,
+    pub moves: Vec<Option<MoveView>>,
+    pub status: Option<pokemon_adventure::pokemon::StatusCondition>,
+    pub charging_move: Option<Move>, // Two-turn move being charged, e.g. SolarBeam
+}
+
+#[derive(Debug)]
+pub struct PokemonSummaryView {
+    pub slot_id: usize, // Team slot index; stable for the whole battle, unlike names
+    pub name: String,
+    pub species: Species,
+    pub level: u8,
+    pub current_hp: u16,
+    pub max_hp: u16,
+    pub is_fainted: bool,
+    pub status: Option<pokemon_adventure::pokemon::StatusCondition>,
+    pub types: Vec<PokemonType>,
+    pub error: Option<String>, // Set when species data could not be resolved; other fields are best-effort
+}
+
+#[derive(Debug)]
+pub struct MoveView {
+    pub move_: Move,
+    pub pp: u8,
+    pub max_pp: u8,
+    pub priority: i8,
+}
+
+#[derive(Debug)]
+pub struct OpponentView {
+    pub player_name: String,
+    pub active_pokemon: Option<PokemonSummaryView>,
+    pub remaining_pokemon_count: usize,
+    pub revealed_moves: Vec<RevealedMoveView>,
+}
+
+#[derive(Debug)]
+pub struct RevealedMoveView {
+    pub move_: Move,
+    pub times_used: u32,
+    pub revealed_move_pp: Option<u8>,
+}
+
+// Helper functions
+
+/// Longest nickname a custom team member may take, in characters
+const MAX_NICKNAME_LEN: usize = 12;
+
+/// Longest player name (or two-player battle player id) accepted, in characters
+const MAX_PLAYER_NAME_LEN: usize = 40;
+
+/// Longest name shown in views and event text; longer stored names are cut with an ellipsis
+const MAX_DISPLAY_NAME_LEN: usize = 40;
+
+/// Longest formatted event string; only reachable by names stored before validation existed
+const MAX_EVENT_TEXT_LEN: usize = 300;
+
+/// Reject player names that are empty, too long, or contain control characters
+pub fn validate_player_name(name: &str) -> Result<(), ApiError> {
+    if name.trim().is_empty() || name.chars().count() > MAX_PLAYER_NAME_LEN {
+        return Err(ApiError::validation_error(format!("Player name must be 1-{} characters", MAX_PLAYER_NAME_LEN)));
+    }
+    if name.chars().any(char::is_control) {
+        return Err(ApiError::validation_error("Player name must not contain control characters"));
+    }
+    Ok(())
+}
+
+/// Text cut to at most `max_len` characters, ending in an ellipsis when cut
+fn truncate_display(text: &str, max_len: usize) -> String {
+    if text.chars().count() <= max_len {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(max_len - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+/// A player or Pokemon name as views show it
+pub fn display_name(name: &str) -> String {
+    truncate_display(name, MAX_DISPLAY_NAME_LEN)
+}
+
+/// Every problem with a proposed team, in team order; empty when the team is legal
+/// Team-wide problems, like its size, carry no `pokemon_index`
+pub fn validate_team(team_config: &[TeamPokemon], allow_illegal_moves: bool) -> Vec<TeamError> {
+    let mut errors = Vec::new();
+
+    if team_config.is_empty() {
+        errors.push(TeamError { pokemon_index: None, reason: "Team cannot be empty".to_string() });
+    }
+
+    if team_config.len() > 6 {
+        errors.push(TeamError { pokemon_index: None, reason: "Team cannot have more than 6 Pokemon".to_string() });
+    }
+
+    for (index, team_pokemon) in team_config.iter().enumerate() {
+        let mut reject = |reason: String| errors.push(TeamError { pokemon_index: Some(index), reason });
+
+        // Validate level range
+        if team_pokemon.level == 0 || team_pokemon.level > 100 {
+            reject(format!("Invalid level {} for {:?}", team_pokemon.level, team_pokemon.species));
+        }
+
+        // Validate species data exists
+        let species_data = get_species_data(team_pokemon.species);
+        if species_data.is_none() {
+            reject(format!("Species data not found for {:?}", team_pokemon.species));
+        }
+
+        // Validate moves
+        if team_pokemon.moves.is_empty() || team_pokemon.moves.len() > 4 {
+            reject("Pokemon must have 1-4 moves".to_string());
+        }
+
+        // Validate no move appears in more than one slot
+        for (slot, move_) in team_pokemon.moves.iter().enumerate() {
+            if team_pokemon.moves[..slot].contains(move_) {
+                reject(format!("Duplicate move {:?} in slot {} for {:?}", move_, slot, team_pokemon.species));
+            }
+        }
+
+        // Validate every move is in the species' learnset, unless this is a sandbox battle
+        if let Some(species_data) = species_data.as_ref().filter(|_| !allow_illegal_moves) {
+            let learnset = &species_data.learnset;
+            for move_ in &team_pokemon.moves {
+                let learnable = learnset.level_up.values().any(|moves| moves.contains(move_))
+                    || learnset.signature == Some(*move_)
+                    || learnset.can_learn.contains(move_);
+                if !learnable {
+                    reject(format!("{:?} cannot learn {:?}", team_pokemon.species, move_));
+                }
+            }
+        }
+
+        // Validate the nickname, if any
+        if let Some(nickname) = &team_pokemon.nickname {
+            if nickname.trim().is_empty() || nickname.chars().count() > MAX_NICKNAME_LEN {
+                reject(format!("Nickname for {:?} must be 1-{} characters", team_pokemon.species, MAX_NICKNAME_LEN));
+            } else if nickname.chars().any(char::is_control) {
+                reject(format!("Nickname for {:?} must not contain control characters", team_pokemon.species));
+            }
+        }
+
+        // Rejected rather than dropped, so a team never silently battles without its items
+        if let Some(item) = &team_pokemon.item {
+            reject(format!("Held items are not supported yet ({} on {:?})", item, team_pokemon.species));
+        }
+    }
+
+    errors
+}
+
+/// Instantiate a team for battle, failing with the first problem `validate_team` finds
+fn create_pokemon_team(team_config: &[TeamPokemon], allow_illegal_moves: bool) -> Result<Vec<PokemonInst>, ApiError> {
+    if let Some(error) = validate_team(team_config, allow_illegal_moves).into_iter().next() {
+        return Err(ApiError::validation_error(error.reason));
+    }
+
+    let mut pokemon_team = Vec::new();
+
+    for team_pokemon in team_config {
+        // Get species data
+        let species_data = get_species_data(team_pokemon.species)
+            .ok_or_else(|| ApiError::validation_error(
+                format!("Species data not found for {:?}", team_pokemon.species)
+            ))?;
+
+        // Create Pokemon instance
+        let mut pokemon = PokemonInst::new(
+            team_pokemon.species,
+            &species_data,
+            team_pokemon.level,
+            None, // Use default IVs
+            Some(team_pokemon.moves.clone()),
+        );
+        // Without a nickname the species name the engine assigned stays
+        if let Some(nickname) = &team_pokemon.nickname {
+            pokemon.name = nickname.clone();
+        }
+
+        pokemon_team.push(pokemon);
+    }
+
+    Ok(pokemon_team)
+}
+
+/// Rebuild every Pokemon above `level_cap` at the cap so its stats are recomputed
+fn apply_level_cap(player: &mut BattlePlayer, level_cap: u8) -> Result<(), ApiError> {
+    if level_cap == 0 || level_cap > 100 {
+        return Err(ApiError::validation_error(
+            format!("Invalid level cap {}", level_cap)
+        ));
+    }
+
+    for pokemon_slot in player.team.iter_mut() {
+        let Some(pokemon) = pokemon_slot.as_ref() else { continue };
+        if pokemon.level <= level_cap {
+            continue;
+        }
+
+        let species_data = get_species_data(pokemon.species)
+            .ok_or_else(|| ApiError::validation_error(
+                format!("Species data not found for {:?}", pokemon.species)
+            ))?;
+        let moves: Vec<Move> = pokemon.moves.iter()
+            .filter_map(|m| m.as_ref().map(|move_inst| move_inst.move_))
+            .collect();
+
+        *pokemon_slot = Some(PokemonInst::new(
+            pokemon.species,
+            &species_data,
+            level_cap,
+            None, // Use default IVs
+            Some(moves),
+        ));
+    }
+
+    Ok(())
+}
+
+/// List every move a species can learn, level-up moves first in level order
+/// A move obtainable several ways is listed once, under its earliest method
+pub fn get_learnable_moves(species: Species) -> Result<Vec<LearnableMove>, ApiError> {
+    let species_data = get_species_data(species)
+        .ok_or_else(|| ApiError::SpeciesNotFound { species: format!("{:?}", species) })?;
+    let learnset = &species_data.learnset;
+
+    let mut levels: Vec<u8> = learnset.level_up.keys().copied().collect();
+    levels.sort_unstable();
+
+    let mut learnable: Vec<LearnableMove> = Vec::new();
+    let mut push_unique = |move_: Move, method: &str, level: Option<u8>| {
+        if !learnable.iter().any(|existing| existing.move_ == move_) {
+            learnable.push(LearnableMove { move_, method: method.to_string(), level });
+        }
+    };
+
+    for level in levels {
+        for move_ in &learnset.level_up[&level] {
+            push_unique(*move_, "level_up", Some(level));
+        }
+    }
+    if let Some(signature) = learnset.signature {
+        push_unique(signature, "signature", None);
+    }
+    for move_ in &learnset.can_learn {
+        push_unique(*move_, "teachable", None);
+    }
+
+    Ok(learnable)
+}
+
+/// Pokedex entry for a species from its species data
+pub fn get_species_info(species: Species) -> Result<SpeciesInfo, ApiError> {
+    let species_data = get_species_data(species)
+        .ok_or_else(|| ApiError::SpeciesNotFound { species: format!("{:?}", species) })?;
+    let base_stats = &species_data.base_stats;
+
+    Ok(SpeciesInfo {
+        species,
+        types: species_data.types.clone(),
+        base_stats: BaseStatsInfo {
+            hp: base_stats.hp,
+            attack: base_stats.attack,
+            defense: base_stats.defense,
+            sp_attack: base_stats.sp_attack,
+            sp_defense: base_stats.sp_defense,
+            speed: base_stats.speed,
+        },
+    })
+}
+
+/// Static data for a move from the move registry
+pub fn get_move_info(move_: Move) -> Result<MoveInfo, ApiError> {
+    let move_data = get_move_data(move_)
+        .ok_or_else(|| ApiError::MoveNotFound { move_name: format!("{:?}", move_) })?;
+
+    Ok(MoveInfo {
+        move_,
+        move_type: move_data.move_type,
+        power: move_data.power,
+        accuracy: move_data.accuracy,
+        max_pp: move_data.max_pp,
+        priority: move_priority(move_),
+    })
+}
+
+fn species_types(species: Species) -> Result<Vec<PokemonType>, ApiError> {
+    get_species_data(species)
+        .map(|data| data.types.clone())
+        .ok_or_else(|| ApiError::validation_error(
+            format!("Species data not found for {:?}", species)
+        ))
+}
+
+/// Highest multiplier among a moveset's damaging moves against a defending species
+fn best_move_multiplier(moves: &[Move], defender: Species) -> Result<f32, ApiError> {
+    let defender_types = species_types(defender)?;
+
+    Ok(moves.iter()
+        .filter_map(|move_| get_move_data(*move_))
+        .filter(|move_data| move_data.power.is_some())
+        .map(|move_data| type_effectiveness(move_data.move_type, &defender_types))
+        .fold(0.0, f32::max))
+}
+
+/// Largest series a match may be configured for
+const MAX_BEST_OF: u32 = 9;
+
+/// A match needs an odd number of games so a majority always decides it
+pub fn validate_best_of(best_of: u32) -> Result<(), ApiError> {
+    if best_of == 0 || best_of % 2 == 0 || best_of > MAX_BEST_OF {
+        return Err(ApiError::validation_error(
+            format!("best_of must be an odd number between 1 and {}, got {}", MAX_BEST_OF, best_of)
+        ));
+    }
+    Ok(())
+}
+
+fn validate_level_budget(team_config: &[TeamPokemon], budget: u32) -> Result<(), ApiError> {
+    let total: u32 = team_config.iter().map(|p| p.level as u32).sum();
+    if total > budget {
+        return Err(ApiError::validation_error(
+            format!("Team level total {} exceeds level budget {}", total, budget)
+        ));
+    }
+    Ok(())
+}
+
+fn get_player_index(battle_state: &BattleState, player_id: &PlayerId) -> Result<usize, ApiError> {
+    if battle_state.players[0].player_id == player_id.0 {
+        Ok(0)
+    } else if battle_state.players[1].player_id == player_id.0 {
+        Ok(1)
+    } else {
+        Err(ApiError::player_not_in_battle(
+            player_id,
+            &[&battle_state.players[0].player_id, &battle_state.players[1].player_id],
+        ))
+    }
+}
+
+fn validate_action_context(
+    battle_state: &BattleState,
+    player_index: usize,
+    action: &PlayerAction,
+) -> Result<(), ApiError> {
+    match battle_state.game_state {
+        GameState::WaitingForActions => {
+            // Normal turn - validate player can act
+            if battle_state.action_queue[player_index].is_some() {
                 return Err(ApiError::invalid_action("Player has already submitted an action for this turn"));
             }
         }
@@ -321,6 +1632,10 @@ fn validate_action_context(
             }
         }
         GameState::WaitingForBothReplacements => {
+            // The first replacement waits for the second; it can't be swapped for another
+            if battle_state.action_queue[player_index].is_some() {
+                return Err(ApiError::invalid_action("Player has already chosen a replacement"));
+            }
             if !matches!(action, PlayerAction::SwitchPokemon { .. }) {
                 return Err(ApiError::invalid_action("Must switch Pokemon during replacement phase"));
             }
@@ -335,7 +1650,76 @@ fn validate_action_context(
     Ok(())
 }
 
-fn process_battle_ticks(battle_state: &mut BattleState) -> Result<Vec<String>, ApiError> {
+/// Enforce format rules on top of the engine's own action validation
+fn validate_action_rules(
+    battle_state: &BattleState,
+    action: &PlayerAction,
+    rules: &BattleRules,
+) -> Result<(), ApiError> {
+    // Replacement phases are forced switches and stay free regardless of rules
+    if rules.switch_uses_turn
+        && battle_state.game_state == GameState::WaitingForActions
+        && matches!(action, PlayerAction::SwitchPokemon { .. })
+    {
+        return Err(ApiError::invalid_action(
+            "Voluntary switches are disabled in this battle format"
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+thread_local! {
+    /// Makes the next tick processing on this thread fail after mutating the state, to test rollback
+    pub static FAIL_NEXT_TICK: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+/// Reject move actions naming an empty or nonexistent slot, before the engine sees them
+fn validate_move_slot(
+    battle_state: &BattleState,
+    player_index: usize,
+    action: &PlayerAction,
+) -> Result<(), ApiError> {
+    let PlayerAction::UseMove { move_index } = action else {
+        return Ok(());
+    };
+    let Some(pokemon) = battle_state.players[player_index].active_pokemon() else {
+        return Ok(());
+    };
+
+    match pokemon.moves.get(*move_index) {
+        Some(Some(_)) => Ok(()),
+        Some(None) => Err(ApiError::invalid_action(format!("Move slot {} is empty", move_index))),
+        None => Err(ApiError::invalid_action(format!(
+            "Move slot {} does not exist (slots are 0-{})", move_index, pokemon.moves.len() - 1
+        ))),
+    }
+}
+
+/// Reject switching to the active Pokemon, a common off-by-one in clients' team indexes
+fn validate_switch_target(
+    battle_state: &BattleState,
+    player_index: usize,
+    action: &PlayerAction,
+) -> Result<(), ApiError> {
+    match action {
+        PlayerAction::SwitchPokemon { team_index }
+            if *team_index == battle_state.players[player_index].active_pokemon_index =>
+        {
+            Err(ApiError::invalid_action("That Pokémon is already active"))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// After a double faint, both replacements go in together, so one queued switch resolves nothing
+fn awaiting_second_replacement(battle_state: &BattleState) -> bool {
+    battle_state.game_state == GameState::WaitingForBothReplacements
+        && battle_state.action_queue.iter().any(|action| action.is_none())
+}
+
+fn process_battle_ticks(battle_state: &mut BattleState, battle_seed: Option<u64>) -> Result<TickEvents, ApiError> {
     // Collect AI actions as needed
     let npc_actions = collect_npc_actions(battle_state);
 
@@ -344,24 +1728,108 @@ fn process_battle_ticks(battle_state: &mut BattleState) -> Result<Vec<String>, A
     for (player_index, action) in npc_actions {
         battle_state.action_queue[player_index] = Some(action);
     }
-    let mut all_formatted_events = Vec::new();
+
+    #[cfg(test)]
+    if FAIL_NEXT_TICK.with(|fail| fail.replace(false)) {
+        return Err(ApiError::InternalError { message: "Injected tick failure".to_string() });
+    }
+    let mut tick_events = TickEvents::default();
+
+    // Simultaneous forfeits end the battle in a draw instead of letting
+    // whichever forfeit the engine resolves first decide a winner
+    if battle_state.action_queue.iter().all(|a| matches!(a, Some(PlayerAction::Forfeit))) {
+        tick_events.resolutions.push(TurnResolution {
+            seed: None,
+            actions: battle_state.action_queue.clone(),
+            unseeded: false,
+        });
+        apply_mutual_forfeit(battle_state);
+        tick_events.events.push("Both players forfeited. The battle ends in a draw.".to_string());
+        tick_events.structured_events.push(BattleEventDto::Other);
+        tick_events.draw_reason = Some(MUTUAL_FORFEIT_REASON.to_string());
+        return Ok(tick_events);
+    }
+
     let mut iterations = 0;
     const MAX_ITERATIONS: u32 = 100; // Prevent infinite loops
 
-    while ready_for_turn_resolution(battle_state) && iterations < MAX_ITERATIONS {
-        let rng = TurnRng::new_random();
+    while ready_for_turn_resolution(battle_state) && !awaiting_second_replacement(battle_state) && iterations < MAX_ITERATIONS {
+        let resolving_turn = current_turn(battle_state);
+        // Only seeded battles need a reproducible stream; the rest use the engine's own RNG
+        let seed = battle_seed.map(|battle_seed| turn_seed(battle_seed, resolving_turn));
+        tick_events.resolutions.push(TurnResolution {
+            seed,
+            actions: battle_state.action_queue.clone(),
+            unseeded: seed.is_none(),
+        });
+        let rng = match seed {
+            Some(seed) => turn_rng_from_seed(seed),
+            None => TurnRng::new_random(),
+        };
+        // Each side's active species and HP before the turn, kept current through the turn
+        let before = battle_state.clone();
+        let mut active: Vec<Option<(Species, u16)>> = before.players.iter()
+            .map(|player| player.active_pokemon().map(|pokemon| (pokemon.species, pokemon.current_hp())))
+            .collect();
         let event_bus = resolve_turn(battle_state, rng);
 
         // Use the new context-aware format method for each event
+        let mut current_phase: Option<&'static str> = None;
         for event in event_bus.events() {
-            let formatted_string = event.format(battle_state);
-            // Only add non-empty event strings to the log
+            let formatted_string = truncate_display(&event.format(battle_state), MAX_EVENT_TEXT_LEN);
+            // Only add events with non-empty strings, keeping both representations index-aligned
             if !formatted_string.is_empty() {
-                all_formatted_events.push(formatted_string);
+                let event_dto = to_event_dto(event);
+
+                // Mark where a turn moves from one sub-phase to the next
+                if let Some(phase) = event_phase(&event_dto) {
+                    if current_phase.is_some_and(|current| current != phase) {
+                        tick_events.push_marker(BattleEventDto::PhaseBoundary { phase: phase.to_string() });
+                    }
+                    current_phase = Some(phase);
+                }
+
+                // Later damage this turn lands on whoever just came in, at the HP it left the field with
+                if let BattleEventDto::SwitchIn { player_index, new_pokemon, .. } = &event_dto {
+                    if let Some(slot) = active.get_mut(*player_index) {
+                        let hp = before.players[*player_index].team.iter().flatten()
+                            .find(|pokemon| pokemon.species == *new_pokemon)
+                            .map_or(0, |pokemon| pokemon.current_hp());
+                        *slot = Some((*new_pokemon, hp));
+                    }
+                }
+
+                // Exact figures about a Pokemon are only revealed to its owner
+                match redact_private_details(battle_state, &event_dto, &active) {
+                    Some(redacted) => {
+                        if let (
+                            BattleEventDto::Damage { target_player: Some(side), .. },
+                            BattleEventDto::Damage { target, remaining_hp: Some(hp), .. },
+                        ) = (&redacted.public_structured_event, &event_dto) {
+                            if let Some(slot) = active.get_mut(*side) {
+                                *slot = Some((*target, *hp));
+                            }
+                        }
+                        for (owner, full_event) in redacted.owners {
+                            tick_events.private_events[owner].push(PrivateEvent {
+                                index: tick_events.events.len(),
+                                event: formatted_string.clone(),
+                                structured_event: full_event,
+                            });
+                        }
+                        tick_events.events.push(redacted.public_event);
+                        tick_events.structured_events.push(redacted.public_structured_event);
+                    }
+                    None => {
+                        tick_events.events.push(formatted_string);
+                        tick_events.structured_events.push(event_dto);
+                    }
+                }
             }
         }
+        tick_events.push_marker(BattleEventDto::TurnBoundary { turn_number: resolving_turn });
 
-        if matches!(battle_state.game_state, GameState::Player1Win | GameState::Player2Win | GameState::Draw) {
+        if is_battle_finished(battle_state) {
             break;
         }
 
@@ -374,7 +1842,313 @@ fn process_battle_ticks(battle_state: &mut BattleState) -> Result<Vec<String>, A
         });
     }
 
-    Ok(all_formatted_events)
+    Ok(tick_events)
+}
+
+/// End a battle in a draw when both players forfeit the same turn
+fn apply_mutual_forfeit(battle_state: &mut BattleState) {
+    battle_state.action_queue = [None, None];
+    battle_state.game_state = GameState::Draw;
+    // Advance like an engine resolution so the forfeited turn counts as resolved
+    battle_state.turn_number += 1;
+}
+
+/// End a battle with a win for the opponent of a player whose forfeit the API resolved alone
+fn apply_forfeit(battle_state: &mut BattleState, forfeiting_index: usize) {
+    battle_state.action_queue = [None, None];
+    battle_state.game_state = if forfeiting_index == 0 { GameState::Player2Win } else { GameState::Player1Win };
+    // Advance like an engine resolution so the forfeited turn counts as resolved
+    battle_state.turn_number += 1;
+}
+
+/// Outcomes pre-drawn for one seeded resolution
+/// The engine exposes no seeded `TurnRng` constructor, so seeded battles feed its
+/// `new_for_test` constructor a stream drawn from the seed instead. A resolution rolls a
+/// handful of values per action (accuracy, critical hit, damage spread, secondary effects,
+/// NPC choices), so this leaves a wide margin; unseeded battles never use it and keep
+/// `TurnRng::new_random`.
+const TURN_RNG_OUTCOMES: usize = 1024;
+
+/// Seed for one turn of a seeded battle, so the same actions replay to the same outcomes
+/// The turn number is spread across all bits before mixing, keeping consecutive turns' streams unrelated
+pub fn turn_seed(battle_seed: u64, turn_number: u32) -> u64 {
+    battle_seed ^ (turn_number as u64).wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+}
+
+/// Turn RNG derived entirely from a seed, so a recorded seed reproduces the resolution
+/// Only for seeded battles; see `TURN_RNG_OUTCOMES`
+pub fn turn_rng_from_seed(seed: u64) -> TurnRng {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let outcomes = (0..TURN_RNG_OUTCOMES).map(|_| rng.random_range(1..=100)).collect();
+    TurnRng::new_for_test(outcomes)
+}
+
+/// Rebuild a battle's state by re-resolving every recorded turn from its initial state
+/// NPC choices come from the recorded actions, so the AI is never consulted again;
+/// logs written before resolutions were recorded contribute nothing
+pub fn replay_from_actions(initial_state: &BattleState, turn_logs: &[TurnLog]) -> BattleState {
+    let mut battle_state = initial_state.clone();
+    for resolution in turn_logs.iter().flat_map(|log| &log.resolutions) {
+        replay_resolution(&mut battle_state, resolution);
+    }
+    battle_state
+}
+
+/// Whether every recorded resolution can be replayed, i.e. none used the unseeded engine RNG
+/// Logs from before unseeded turns were flagged recorded a random seed, so they still count
+pub fn is_replayable(turn_logs: &[TurnLog]) -> bool {
+    turn_logs.iter()
+        .flat_map(|log| &log.resolutions)
+        .all(|resolution| !resolution.unseeded)
+}
+
+/// Replay a battle one turn log at a time, giving the state after each log with its turn number
+pub fn replay_turns(initial_state: &BattleState, turn_logs: &[TurnLog]) -> Vec<(u32, BattleState)> {
+    let mut battle_state = initial_state.clone();
+    turn_logs.iter()
+        .map(|log| {
+            for resolution in &log.resolutions {
+                replay_resolution(&mut battle_state, resolution);
+            }
+            (log.turn_number, battle_state.clone())
+        })
+        .collect()
+}
+
+/// Re-apply one recorded resolution exactly as it was first resolved
+fn replay_resolution(battle_state: &mut BattleState, resolution: &TurnResolution) {
+    battle_state.action_queue = resolution.actions.clone();
+    match resolution.seed {
+        Some(seed) => {
+            resolve_turn(battle_state, turn_rng_from_seed(seed));
+        }
+        // API-resolved turns: a lone forfeit is a loss, two are a draw
+        None => match resolution.actions {
+            [Some(PlayerAction::Forfeit), None] => apply_forfeit(battle_state, 0),
+            [None, Some(PlayerAction::Forfeit)] => apply_forfeit(battle_state, 1),
+            _ => apply_mutual_forfeit(battle_state),
+        },
+    }
+}
+
+/// A Pokemon's HP, status, PP and active status, for change tracking
+#[derive(Debug)]
+pub struct PokemonChange {
+    pub slot_id: usize, // Team slot index
+    pub name: String,
+    pub current_hp: u16,
+    pub max_hp: u16,
+    pub status: Option<StatusDto>,
+    pub is_active: bool,
+    pub move_pp: Option<Vec<Option<u8>>>, // Per move slot; None for the opponent's Pokemon
+}
+
+/// Pokemon that changed since a turn, split by who owns them
+#[derive(Debug)]
+pub struct PokemonChanges {
+    pub team: Vec<PokemonChange>,
+    pub opponent: Vec<PokemonChange>,
+    pub full_refresh: bool, // The state as of `since_turn` could not be rebuilt, so every visible Pokemon is listed
+}
+
+/// Pokemon whose HP, status, PP or active status changed after turn `since_turn`, seen by `player_id`
+/// The state as of that turn is rebuilt by replaying the logs up to it; if the replay cannot
+/// reach the current state (no initial state, pre-resolution logs, an admin patch) every Pokemon is listed.
+/// Opponent Pokemon are only listed while active at either end, so benched ones stay hidden
+pub fn diff_since(
+    battle_state: &BattleState,
+    initial_state: Option<&BattleState>,
+    turn_logs: &[TurnLog],
+    since_turn: u32,
+    player_id: &PlayerId,
+) -> Result<PokemonChanges, ApiError> {
+    let player_index = get_player_index(battle_state, player_id)?;
+
+    let split = turn_logs.partition_point(|log| log.turn_number <= since_turn);
+    let prior_state = initial_state
+        .filter(|_| is_replayable(turn_logs))
+        .map(|initial_state| replay_from_actions(initial_state, &turn_logs[..split]))
+        .filter(|prior_state| {
+            let replayed = replay_from_actions(prior_state, &turn_logs[split..]);
+            serde_json::to_value(&replayed).ok() == serde_json::to_value(battle_state).ok()
+        });
+
+    let changes_for = |index: usize| -> Vec<PokemonChange> {
+        let own = index == player_index;
+        let player = &battle_state.players[index];
+        let snapshot = |player: &BattlePlayer, slot_id: usize| {
+            let pokemon = player.team.get(slot_id)?.as_ref()?;
+            Some(PokemonChange {
+                slot_id,
+                name: display_name(&pokemon.name),
+                current_hp: pokemon.current_hp(),
+                max_hp: pokemon.max_hp(),
+                status: pokemon.status.as_ref().map(status_dto),
+                is_active: active_slot(player).is_some_and(|(active, _)| active == slot_id),
+                move_pp: own.then(|| pokemon.moves.iter()
+                    .map(|move_slot| move_slot.as_ref().map(|move_inst| move_inst.pp))
+                    .collect()),
+            })
+        };
+
+        (0..player.team.len())
+            .filter_map(|slot_id| {
+                let current = snapshot(player, slot_id)?;
+                let prior = prior_state.as_ref().and_then(|prior_state| snapshot(&prior_state.players[index], slot_id));
+                let visible = own || current.is_active || prior.as_ref().is_some_and(|prior| prior.is_active);
+                let changed = match &prior {
+                    Some(prior) => prior.current_hp != current.current_hp
+                        || prior.status != current.status
+                        || prior.is_active != current.is_active
+                        || prior.move_pp != current.move_pp,
+                    None => true,
+                };
+                (visible && changed).then_some(current)
+            })
+            .collect()
+    };
+
+    Ok(PokemonChanges {
+        team: changes_for(player_index),
+        opponent: changes_for(1 - player_index),
+        full_refresh: prior_state.is_none(),
+    })
+}
+
+/// Public and owner-only versions of an event that reveals exact figures
+struct RedactedEvent {
+    owners: Vec<(usize, BattleEventDto)>, // Full event per player allowed it; both players when ownership is ambiguous
+    public_event: String,
+    public_structured_event: BattleEventDto,
+}
+
+/// Split an event carrying private details into owner and public versions
+/// `active` is each side's active species and HP at the event, as tracked through the turn.
+/// Damage only lands on an active Pokemon, so its side is the one whose active Pokemon has the
+/// target's species; in a mirror match it is the one whose HP drops to the event's remaining HP.
+/// When even that can't tell the sides apart, both players get the exact figures rather than
+/// losing them. Returns None for events that are fully public.
+fn redact_private_details(battle_state: &BattleState, event: &BattleEventDto, active: &[Option<(Species, u16)>]) -> Option<RedactedEvent> {
+    let BattleEventDto::Damage { target, damage, remaining_hp, .. } = event else {
+        return None;
+    };
+
+    let mut candidates: Vec<usize> = (0..battle_state.players.len())
+        .filter(|&index| active.get(index).copied().flatten().is_some_and(|(species, _)| species == *target))
+        .collect();
+    if candidates.len() > 1 {
+        if let (Some(damage), Some(remaining_hp)) = (damage, remaining_hp) {
+            let consistent: Vec<usize> = candidates.iter().copied()
+                .filter(|&index| active[index].is_some_and(|(_, hp)| hp.saturating_sub(*damage) == *remaining_hp))
+                .collect();
+            if consistent.len() == 1 {
+                candidates = consistent;
+            }
+        }
+    }
+    if candidates.is_empty() {
+        // Not an active species (e.g. a switch the events did not report): fall back to team membership
+        candidates = (0..battle_state.players.len())
+            .filter(|&index| battle_state.players[index].team.iter().flatten().any(|pokemon| pokemon.species == *target))
+            .collect();
+    }
+    let target_player = match candidates[..] {
+        [index] => Some(index),
+        _ => None,
+    };
+
+    // The side's Pokemon of that species, preferring the one now active
+    let pokemon_of = |index: usize| -> Option<&PokemonInst> {
+        let player = &battle_state.players[index];
+        player.active_pokemon()
+            .filter(|pokemon| pokemon.species == *target)
+            .or_else(|| player.team.iter().flatten().find(|pokemon| pokemon.species == *target))
+    };
+    let percent_for = |index: usize| {
+        pokemon_of(index).zip(*remaining_hp).map(|(pokemon, hp)| hp_percent(hp, pokemon.max_hp()))
+    };
+    let remaining_hp_percent = target_player.and_then(percent_for);
+
+    let name = target_player
+        .and_then(pokemon_of)
+        .map(|pokemon| display_name(&pokemon.name))
+        .unwrap_or_else(|| readable_name(target));
+    let public_event = match remaining_hp_percent {
+        Some(percent) => format!("{} took damage ({}% HP remaining)", name, percent),
+        None => format!("{} took damage", name),
+    };
+
+    Some(RedactedEvent {
+        owners: candidates.iter()
+            .map(|&index| (index, BattleEventDto::Damage {
+                target: *target,
+                target_player,
+                damage: *damage,
+                remaining_hp: *remaining_hp,
+                remaining_hp_percent: percent_for(index),
+            }))
+            .collect(),
+        public_event,
+        public_structured_event: BattleEventDto::Damage {
+            target: *target,
+            target_player,
+            damage: None,
+            remaining_hp: None,
+            remaining_hp_percent,
+        },
+    })
+}
+
+/// HP as a whole percentage, rounded up so a Pokemon with HP left never shows 0%
+fn hp_percent(current_hp: u16, max_hp: u16) -> u8 {
+    if max_hp == 0 {
+        return 0;
+    }
+    ((current_hp as u32 * 100).div_ceil(max_hp as u32)).min(100) as u8
+}
+
+/// Sub-phase of a turn an event belongs to, for placing phase boundary markers
+fn event_phase(event: &BattleEventDto) -> Option<&'static str> {
+    match event {
+        BattleEventDto::MoveUsed { .. }
+        | BattleEventDto::Damage { .. }
+        | BattleEventDto::StatusApplied { .. } => Some("action"),
+        BattleEventDto::Faint { .. } => Some("faint"),
+        BattleEventDto::SwitchIn { .. } => Some("switch"),
+        _ => None,
+    }
+}
+
+/// Map an engine event onto its structured API representation
+fn to_event_dto(event: &BattleEvent) -> BattleEventDto {
+    match event {
+        BattleEvent::MoveUsed { player_index, pokemon, move_used, .. } => BattleEventDto::MoveUsed {
+            player_index: *player_index,
+            pokemon: *pokemon,
+            move_used: *move_used,
+        },
+        BattleEvent::DamageDealt { target, damage, remaining_hp, .. } => BattleEventDto::Damage {
+            target: *target,
+            target_player: None, // Filled in from the active Pokemon when redacting
+            damage: Some(*damage),
+            remaining_hp: Some(*remaining_hp),
+            remaining_hp_percent: None, // Filled in when the owner is known
+        },
+        BattleEvent::PokemonFainted { player_index, pokemon, .. } => BattleEventDto::Faint {
+            player_index: *player_index,
+            pokemon: *pokemon,
+        },
+        BattleEvent::StatusApplied { target, status, .. } => BattleEventDto::StatusApplied {
+            target: *target,
+            status: *status,
+        },
+        BattleEvent::PokemonSwitched { player_index, old_pokemon, new_pokemon, .. } => BattleEventDto::SwitchIn {
+            player_index: *player_index,
+            old_pokemon: *old_pokemon,
+            new_pokemon: *new_pokemon,
+        },
+        _ => BattleEventDto::Other,
+    }
 }
 
 fn can_player_act(battle_state: &BattleState, player_index: usize) -> bool {
@@ -382,39 +2156,89 @@ fn can_player_act(battle_state: &BattleState, player_index: usize) -> bool {
         GameState::WaitingForActions => battle_state.action_queue[player_index].is_none(),
         GameState::WaitingForPlayer1Replacement => player_index == 0,
         GameState::WaitingForPlayer2Replacement => player_index == 1,
-        GameState::WaitingForBothReplacements => true,
+        GameState::WaitingForBothReplacements => battle_state.action_queue[player_index].is_none(),
         _ => false,
     }
 }
 
+fn move_views(pokemon: &PokemonInst) -> Vec<Option<MoveView>> {
+    pokemon.moves.iter().map(|move_slot| {
+        move_slot.as_ref().map(|move_inst| MoveView {
+            move_: move_inst.move_,
+            pp: move_inst.pp,
+            max_pp: move_inst.max_pp(),
+            priority: move_priority(move_inst.move_),
+        })
+    }).collect()
+}
+
+/// Structured form of a status condition, named after the engine variant in snake case
+/// Only sleep's counter counts down, so it is the only one exposed as `turns_remaining`
+pub fn status_dto(status: &StatusCondition) -> StatusDto {
+    // Unit variants serialize as "Burn", counters as {"Sleep": 2}
+    let (kind, counter) = match serde_json::to_value(status) {
+        Ok(serde_json::Value::Object(map)) => match map.into_iter().next() {
+            Some((name, value)) => (name, value.as_u64()),
+            None => (format!("{:?}", status), None),
+        },
+        Ok(serde_json::Value::String(name)) => (name, None),
+        _ => (format!("{:?}", status), None),
+    };
+    let kind = snake_case(&kind);
+    let turns_remaining = if kind == "sleep" {
+        counter.and_then(|turns| u8::try_from(turns).ok())
+    } else {
+        None
+    };
+    StatusDto { kind, turns_remaining }
+}
+
+/// Summarize a Pokemon for team and opponent views
+/// A species data lookup failure yields a flagged placeholder instead of failing the whole view
+fn summarize_pokemon(slot_id: usize, pokemon: &PokemonInst) -> PokemonSummaryView {
+    let (types, error) = match species_types(pokemon.species) {
+        Ok(types) => (types, None),
+        Err(_) => (Vec::new(), Some("data unavailable".to_string())),
+    };
+
+    PokemonSummaryView {
+        slot_id,
+        name: display_name(&pokemon.name),
+        species: pokemon.species,
+        level: pokemon.level,
+        current_hp: pokemon.current_hp(),
+        max_hp: pokemon.max_hp(),
+        is_fainted: pokemon.is_fainted(),
+        status: pokemon.status,
+        types,
+        error,
+    }
+}
+
+/// The active Pokemon together with its team slot index
+fn active_slot(player: &BattlePlayer) -> Option<(usize, &PokemonInst)> {
+    let active = player.active_pokemon()?;
+    player.team.iter()
+        .position(|slot| slot.as_ref().is_some_and(|pokemon| std::ptr::eq(pokemon, active)))
+        .map(|slot_id| (slot_id, active))
+}
+
 fn create_player_team_view(player: &BattlePlayer) -> TeamView {
-    let active_pokemon = player.active_pokemon().map(|pokemon| PokemonDetailView {
-        name: pokemon.name.clone(),
+    let active_pokemon = active_slot(player).map(|(slot_id, pokemon)| PokemonDetailView {
+        slot_id,
+        name: display_name(&pokemon.name),
         species: pokemon.species,
         level: pokemon.level,
         current_hp: pokemon.current_hp(),
         max_hp: pokemon.max_hp(),
         stats: pokemon.stats.clone(),
-        moves: pokemon.moves.iter().map(|move_slot| {
-            move_slot.as_ref().map(|move_inst| MoveView {
-                move_: move_inst.move_,
-                pp: move_inst.pp,
-                max_pp: move_inst.max_pp(),
-            })
-        }).collect(),
+        moves: move_views(pokemon),
         status: pokemon.status,
+        charging_move: charging_move(player),
     });
 
-    let team_pokemon = player.team.iter().map(|pokemon_slot| {
-        pokemon_slot.as_ref().map(|pokemon| PokemonSummaryView {
-            name: pokemon.name.clone(),
-            species: pokemon.species,
-            level: pokemon.level,
-            current_hp: pokemon.current_hp(),
-            max_hp: pokemon.max_hp(),
-            is_fainted: pokemon.is_fainted(),
-            status: pokemon.status,
-        })
+    let team_pokemon = player.team.iter().enumerate().map(|(slot_id, pokemon_slot)| {
+        pokemon_slot.as_ref().map(|pokemon| summarize_pokemon(slot_id, pokemon))
     }).collect();
 
     TeamView {
@@ -423,16 +2247,66 @@ fn create_player_team_view(player: &BattlePlayer) -> TeamView {
     }
 }
 
+fn describe_action(player: &BattlePlayer, action: &PlayerAction) -> String {
+    match action {
+        PlayerAction::UseMove { .. } if will_struggle(player) => {
+            "Struggle (no PP remaining)".to_string()
+        }
+        PlayerAction::UseMove { move_index } => {
+            let move_slot = player.active_pokemon()
+                .and_then(|pokemon| pokemon.moves.get(*move_index))
+                .and_then(|m| m.as_ref());
+            match move_slot {
+                Some(move_inst) => format!(
+                    "Use {:?} ({}/{} PP)", move_inst.move_, move_inst.pp, move_inst.max_pp()
+                ),
+                None => format!("Use move {}", move_index),
+            }
+        }
+        PlayerAction::SwitchPokemon { team_index } => {
+            match player.team.get(*team_index).and_then(|p| p.as_ref()) {
+                Some(pokemon) => format!("Switch to {}", pokemon.name),
+                None => format!("Switch to team slot {}", team_index),
+            }
+        }
+        PlayerAction::Forfeit => "Forfeit the battle".to_string(),
+    }
+}
+
+/// Whether the active Pokemon has moves but none of them have PP left
+fn will_struggle(player: &BattlePlayer) -> bool {
+    player.active_pokemon().is_some_and(|pokemon| {
+        let mut known_moves = pokemon.moves.iter().filter_map(|m| m.as_ref()).peekable();
+        known_moves.peek().is_some() && known_moves.all(|move_inst| move_inst.pp == 0)
+    })
+}
+
+/// Explanation for a switch being blocked by a trapping move (Wrap, Bind, Fire Spin, Clamp)
+fn trapped_reason(player: &BattlePlayer) -> Option<String> {
+    let is_trapped = player.active_pokemon_conditions.values()
+        .any(|condition| matches!(condition, PokemonCondition::Trapped { .. }));
+
+    if is_trapped {
+        player.active_pokemon().map(|pokemon| format!("{} is trapped and cannot switch out", pokemon.name))
+    } else {
+        None
+    }
+}
+
+/// The move locked in by a Charging condition, which the engine records as the player's last move
+fn charging_move(player: &BattlePlayer) -> Option<Move> {
+    let is_charging = player.active_pokemon_conditions.values()
+        .any(|condition| matches!(condition, PokemonCondition::Charging));
+
+    if is_charging {
+        player.last_move
+    } else {
+        None
+    }
+}
+
 fn create_opponent_view(opponent: &BattlePlayer) -> OpponentView {
-    let active_pokemon = opponent.active_pokemon().map(|pokemon| PokemonSummaryView {
-        name: pokemon.name.clone(),
-        species: pokemon.species,
-        level: pokemon.level,
-        current_hp: pokemon.current_hp(),
-        max_hp: pokemon.max_hp(),
-        is_fainted: pokemon.is_fainted(),
-        status: pokemon.status,
-    });
+    let active_pokemon = active_slot(opponent).map(|(slot_id, pokemon)| summarize_pokemon(slot_id, pokemon));
 
     let remaining_pokemon_count = opponent.team.iter()
         .filter_map(|p| p.as_ref())
@@ -440,8 +2314,9 @@ fn create_opponent_view(opponent: &BattlePlayer) -> OpponentView {
         .count();
 
     OpponentView {
-        player_name: opponent.player_name.clone(),
+        player_name: display_name(&opponent.player_name),
         active_pokemon,
         remaining_pokemon_count,
+        revealed_moves: Vec::new(), // Derived from the turn logs, see get_revealed_opponent_moves
     }
 }
\ No newline at end of file
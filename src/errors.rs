@@ -1,6 +1,6 @@
 use thiserror::Error;
 use serde::{Deserialize, Serialize};
-use crate::types::{BattleId, PlayerId};
+use crate::types::{BattleId, MatchId, PlayerId};
 
 /// Main API error type with HTTP status code mapping
 #[derive(Error, Debug)]
@@ -8,15 +8,51 @@ pub enum ApiError {
     #[error("Battle {battle_id} not found")]
     BattleNotFound { battle_id: BattleId },
 
+    #[error("Battle #{battle_number} not found")]
+    BattleNumberNotFound { battle_number: u64 },
+
+    #[error("Match {match_id} not found")]
+    MatchNotFound { match_id: MatchId },
+
+    #[error("Turn {turn_number} not found in battle {battle_id}")]
+    TurnNotFound { battle_id: BattleId, turn_number: u32 },
+
+    #[error("Species {species} not found")]
+    SpeciesNotFound { species: String },
+
+    #[error("Move {move_name} not found")]
+    MoveNotFound { move_name: String },
+
+    #[error("Battle {battle_id} cannot be replayed: it predates recorded initial states or was not seeded")]
+    ReplayUnavailable { battle_id: BattleId },
+
+    #[error("Battle {battle_id} has no recorded setup")]
+    SetupNotRecorded { battle_id: BattleId },
+
     #[error("Player {player_id} is not authorized for this battle")]
     PlayerNotAuthorized { player_id: String },
 
+    #[error("Player '{player_id}' is not in this battle (participants: {})", .participants.join(", "))]
+    PlayerNotInBattle { player_id: String, participants: Vec<String> },
+
+    #[error("Observer token is not valid for this battle")]
+    InvalidObserverToken,
+
     #[error("Invalid action: {message}")]
     InvalidAction { message: String },
 
     #[error("Battle is in state {state:?}, cannot accept actions")]
     InvalidBattleState { state: String },
 
+    #[error("Battle {battle_id} was modified concurrently; reload and retry")]
+    Conflict { battle_id: BattleId },
+
+    #[error("Battle {battle_id} is still in progress")]
+    BattleInProgress { battle_id: BattleId },
+
+    #[error("Battle {battle_id} cannot be loaded: {message}")]
+    CorruptedBattle { battle_id: BattleId, message: String },
+
     #[error("Database error: {message}")]
     DatabaseError { message: String },
 
@@ -38,9 +74,21 @@ impl ApiError {
     pub fn status_code(&self) -> u16 {
         match self {
             ApiError::BattleNotFound { .. } => 404,
+            ApiError::BattleNumberNotFound { .. } => 404,
+            ApiError::MatchNotFound { .. } => 404,
+            ApiError::TurnNotFound { .. } => 404,
+            ApiError::SpeciesNotFound { .. } => 404,
+            ApiError::MoveNotFound { .. } => 404,
+            ApiError::ReplayUnavailable { .. } => 404,
+            ApiError::SetupNotRecorded { .. } => 404,
             ApiError::PlayerNotAuthorized { .. } => 403,
+            ApiError::PlayerNotInBattle { .. } => 403,
+            ApiError::InvalidObserverToken => 403,
             ApiError::InvalidAction { .. } => 400,
             ApiError::InvalidBattleState { .. } => 409, // Conflict
+            ApiError::Conflict { .. } => 409,
+            ApiError::BattleInProgress { .. } => 409,
+            ApiError::CorruptedBattle { .. } => 500,
             ApiError::DatabaseError { .. } => 500,
             ApiError::ValidationError { .. } => 400,
             ApiError::InternalError { .. } => 500,
@@ -53,9 +101,21 @@ impl ApiError {
     pub fn error_code(&self) -> &'static str {
         match self {
             ApiError::BattleNotFound { .. } => "BATTLE_NOT_FOUND",
+            ApiError::BattleNumberNotFound { .. } => "BATTLE_NOT_FOUND",
+            ApiError::MatchNotFound { .. } => "MATCH_NOT_FOUND",
+            ApiError::TurnNotFound { .. } => "TURN_NOT_FOUND",
+            ApiError::SpeciesNotFound { .. } => "SPECIES_NOT_FOUND",
+            ApiError::MoveNotFound { .. } => "MOVE_NOT_FOUND",
+            ApiError::ReplayUnavailable { .. } => "REPLAY_UNAVAILABLE",
+            ApiError::SetupNotRecorded { .. } => "SETUP_NOT_RECORDED",
             ApiError::PlayerNotAuthorized { .. } => "PLAYER_NOT_AUTHORIZED",
+            ApiError::PlayerNotInBattle { .. } => "PLAYER_NOT_IN_BATTLE",
+            ApiError::InvalidObserverToken => "INVALID_OBSERVER_TOKEN",
             ApiError::InvalidAction { .. } => "INVALID_ACTION",
             ApiError::InvalidBattleState { .. } => "INVALID_BATTLE_STATE",
+            ApiError::Conflict { .. } => "CONFLICT",
+            ApiError::BattleInProgress { .. } => "BATTLE_IN_PROGRESS",
+            ApiError::CorruptedBattle { .. } => "CORRUPTED_BATTLE",
             ApiError::DatabaseError { .. } => "DATABASE_ERROR",
             ApiError::ValidationError { .. } => "VALIDATION_ERROR",
             ApiError::InternalError { .. } => "INTERNAL_ERROR",
@@ -64,6 +124,23 @@ impl ApiError {
         }
     }
 
+    /// Hide participant ids from callers who are not in the battle themselves
+    /// Player ids are the only credential, so listing them in full would tell a stranger whom to impersonate;
+    /// admins and callers whose `x-player-id` names a participant still see them in full
+    pub fn redact_participants(self, is_admin: bool, authenticated_player_id: Option<&PlayerId>) -> Self {
+        match self {
+            ApiError::PlayerNotInBattle { player_id, participants }
+                if !is_admin && !authenticated_player_id.is_some_and(|id| participants.contains(&id.0)) =>
+            {
+                ApiError::PlayerNotInBattle {
+                    player_id,
+                    participants: participants.iter().map(|p| redact_player_id(p)).collect(),
+                }
+            }
+            other => other,
+        }
+    }
+
     /// Convert to API response format
     pub fn to_response(&self) -> ApiErrorResponse {
         ApiErrorResponse {
@@ -74,6 +151,11 @@ impl ApiError {
     }
 }
 
+/// The first two characters of a player id, e.g. `al***`
+fn redact_player_id(player_id: &str) -> String {
+    format!("{}***", player_id.chars().take(2).collect::<String>())
+}
+
 /// API error response format
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ApiErrorResponse {
@@ -113,12 +195,49 @@ impl ApiError {
         ApiError::BattleNotFound { battle_id }
     }
 
+    pub fn battle_number_not_found(battle_number: u64) -> Self {
+        ApiError::BattleNumberNotFound { battle_number }
+    }
+
+    pub fn match_not_found(match_id: MatchId) -> Self {
+        ApiError::MatchNotFound { match_id }
+    }
+
+    pub fn turn_not_found(battle_id: BattleId, turn_number: u32) -> Self {
+        ApiError::TurnNotFound { battle_id, turn_number }
+    }
+
     pub fn player_not_authorized(player_id: &PlayerId) -> Self {
         ApiError::PlayerNotAuthorized {
             player_id: player_id.0.clone(),
         }
     }
 
+    pub fn player_not_in_battle(player_id: &PlayerId, participants: &[&str]) -> Self {
+        ApiError::PlayerNotInBattle {
+            player_id: player_id.0.clone(),
+            participants: participants.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    pub fn engine_version_mismatch(battle_id: BattleId, stored_version: u32, current_version: u32) -> Self {
+        ApiError::CorruptedBattle {
+            battle_id,
+            message: format!(
+                "battle created with engine v{}, current is v{}",
+                stored_version, current_version
+            ),
+        }
+    }
+
+    /// Map a storage-layer error, keeping API errors raised inside the database layer intact
+    pub fn from_database(err: anyhow::Error) -> Self {
+        match err.downcast::<ApiError>() {
+            Ok(api_error) => api_error,
+            Err(err) => ApiError::DatabaseError { message: err.to_string() },
+        }
+    }
+
     pub fn invalid_action(message: impl Into<String>) -> Self {
         ApiError::InvalidAction {
             message: message.into(),
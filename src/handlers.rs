@@ -7,9 +7,30 @@ use crate::types::*;
 use crate::database::Db;
 use pokemon_adventure::{
     battle::state::BattleState,
+    moves::Move,
     player::PlayerAction,
+    pokemon::PokemonType,
+    species::Species,
 };
+use std::collections::HashMap;
 use std::sync::Arc;
+use tracing::error;
+
+/// Most battles a single batch events request may cover
+const MAX_BATCH_BATTLES: usize = 25;
+
+/// Load-apply-save passes `submit_action` makes before surfacing a version conflict
+const SUBMIT_ACTION_MAX_ATTEMPTS: usize = 3;
+
+/// Player actions a playout submits before giving up on the battle ending
+const MAX_PLAYOUT_ACTIONS: usize = 1000;
+
+/// Most battles one `abandon_all_battles` call looks through
+const MAX_ABANDON_BATTLES: usize = 200;
+
+/// The player id every MVP battle is filed under, whoever is playing it
+const MVP_PLAYER_ID: &str = "player_1";
+
 /// Clean architecture: Request → Router → Database (load) → Engine (logic) → Database (save) → Response
 pub struct BattleHandler {
     db: Arc<dyn Db>,
@@ -26,124 +47,488 @@ impl BattleHandler {
     pub async fn new_with_real_db(table_name: String) -> Result<Self, ApiError> {
         let db = Database::new(table_name)
             .await
-            .map_err(|e| ApiError::DatabaseError { message: e.to_string() })?;
+            .map_err(ApiError::from_database)?;
         Ok(BattleHandler { db: Arc::new(db) })
     }
 
+    // In-memory constructor for black-box tests, including downstream crates
+    // built with the `test-utils` feature.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn new_in_memory() -> Self {
+        BattleHandler::new(Arc::new(crate::database::InMemoryDb::new()))
+    }
+
     /// Create a new battle - Clean architecture implementation
     pub async fn create_battle(&self, request: CreateBattleRequest) -> Result<CreateBattleResponse, ApiError> {
-        let battle_id = BattleId::new();
-        
-        // Engine Logic: Pure function creates battle state
-        let battle_state = engine::create_battle(
-            battle_id.to_string(),
-            request.player1_id.clone(),
-            &request.player1_team,
-            request.player2_id.clone(),
-            &request.player2_team,
-        )?;
-
-        // Database Save: Store the new battle
-        let stored_battle = StoredBattle {
-            battle_id,
-            player1_id: request.player1_id,
-            player2_id: request.player2_id,
-            battle_state,
-            turn_logs: Vec::new(), // Start with empty turn logs
-            created_at: current_timestamp(),
-            last_updated: current_timestamp(),
-        };
-
-        self.db.create_battle(&stored_battle).await
-            .map_err(|e| ApiError::DatabaseError { message: e.to_string() })?;
+        // Engine Logic + Database Save: Shared with match games
+        let (battle_id, battle_number, observer_token) = create_pvp_battle(self.db.as_ref(), &request, None).await?;
 
         // Response: Clean API response
         Ok(CreateBattleResponse {
             battle_id,
+            battle_number,
             status: "Battle created successfully".to_string(),
+            observer_token,
         })
     }
 
     /// Submit a player action - Clean architecture implementation
+    /// A save that loses a version race is retried from a fresh load, so only persistent
+    /// contention surfaces as a 409; NPC battles have a single writer and rarely conflict at all
     pub async fn submit_action(&self, request: SubmitActionRequest) -> Result<SubmitActionResponse, ApiError> {
+        // Authorization: The acting player must match the authenticated identity, when there is one
+        if let Some(authenticated) = &request.authenticated_player_id {
+            if *authenticated != request.player_id {
+                return Err(ApiError::player_not_authorized(&request.player_id));
+            }
+        }
+
+        let mut attempt = 1;
+        let mut turn = None; // Turn the first pass loaded; retries may only apply the action to that turn
+        loop {
+            match self.try_submit_action(&request, &mut turn).await {
+                Err(ApiError::Conflict { .. }) if attempt < SUBMIT_ACTION_MAX_ATTEMPTS => attempt += 1,
+                result => return result,
+            }
+        }
+    }
+
+    /// One load-apply-save pass of `submit_action`; each pass revalidates against the battle it loaded
+    async fn try_submit_action(&self, request: &SubmitActionRequest, turn: &mut Option<u32>) -> Result<SubmitActionResponse, ApiError> {
         // Database Load: Get current battle state
         let mut stored_battle = self.db.get_battle(request.battle_id).await
-            .map_err(|e| ApiError::DatabaseError { message: e.to_string() })?
+            .map_err(ApiError::from_database)?
             .ok_or_else(|| ApiError::battle_not_found(request.battle_id))?;
 
-        // Engine Logic: Pure function processes the action
+        // A pass that lost the race to a save resolving the turn must not spill into the next turn,
+        // so a duplicate submission applies at most once per player per turn
+        let loaded_turn = engine::current_turn(&stored_battle.battle_state);
+        match *turn {
+            Some(expected) if expected != loaded_turn => {
+                return Err(ApiError::invalid_action(format!(
+                    "Turn {} resolved while this action was being submitted; it was not applied", expected
+                )));
+            }
+            _ => *turn = Some(loaded_turn),
+        }
+
+        if stored_battle.resolution_pending {
+            return Err(ApiError::InvalidBattleState { state: "ResolutionPending".to_string() });
+        }
+
+        // A paused battle still accepts a forfeit, so conceding never waits on a resume
+        if stored_battle.paused && !matches!(request.action, PlayerAction::Forfeit) {
+            return Err(ApiError::InvalidBattleState { state: "Paused".to_string() });
+        }
+
+        if request.async_resolution {
+            return self.submit_action_async(stored_battle, request).await;
+        }
+
+        // Engine Logic: Pure function processes the action; on error nothing below runs,
+        // so the stored battle is neither mutated nor saved
         let (new_battle_state, turn_events) = engine::submit_action(
-            stored_battle.battle_state,
+            &stored_battle.battle_state,
             &request.player_id,
-            request.action,
+            request.action.clone(),
+            &stored_battle.rules,
+            stored_battle.battle_seed,
         )?;
 
         // Database Save: Update battle state and turn logs
-        stored_battle.battle_state = new_battle_state.clone();
-        stored_battle.last_updated = current_timestamp();
-        
-        // Add turn log if there were events
-        if !turn_events.is_empty() {
-            let turn_log = TurnLog {
-                turn_number: new_battle_state.turn_number,
-                events: turn_events,
-                timestamp: current_timestamp(),
-            };
-            stored_battle.turn_logs.push(turn_log);
-        }
+        let move_counts = move_usage_counts(&turn_events.structured_events);
+        let logs_before = stored_battle.turn_logs.len();
+        record_turn(&mut stored_battle, new_battle_state.clone(), turn_events);
         
         self.db.update_battle(&stored_battle).await
-            .map_err(|e| ApiError::DatabaseError { message: e.to_string() })?;
+            .map_err(ApiError::from_database)?;
+        record_move_usage(self.db.as_ref(), &move_counts).await;
+        advance_match(self.db.as_ref(), &stored_battle).await;
+
+        // Engine Logic: Post-action view for the acting player
+        let battle_view = engine::get_battle_state_for_player(
+            &new_battle_state,
+            &request.player_id,
+            &stored_battle.rules,
+        )?;
+
+        // Engine Logic: The resolved turns and next choices, from the data already computed
+        let outcome = turn_outcome(&stored_battle, &request.player_id, logs_before)?;
 
-        // Response: Success response
+        // Response: Success response with the new state summary
         Ok(SubmitActionResponse {
             success: true,
             message: "Action processed successfully".to_string(),
             battle_updated: true,
+            game_state: battle_view.game_state,
+            turn_number: battle_view.turn_number,
+            turns_resolved: battle_view.turns_resolved,
+            can_act: battle_view.can_act,
+            outcome: Some(outcome),
         })
     }
 
-    /// Get current battle state - Clean architecture implementation
-    pub async fn get_battle_state(&self, request: GetBattleStateRequest) -> Result<GetBattleStateResponse, ApiError> {
+    /// One page of the player's finished battles, newest first, from either side, continuing after `cursor`
+    pub async fn get_player_history(&self, request: PlayerHistoryRequest) -> Result<PlayerHistoryResponse, ApiError> {
+        // Authorization: Only the player themselves may read their history
+        match &request.authenticated_player_id {
+            None => return Err(ApiError::AuthRequired),
+            Some(authenticated) if *authenticated != request.player_id => {
+                return Err(ApiError::player_not_authorized(&request.player_id));
+            }
+            Some(_) => {}
+        }
+        reject_shared_mvp_player(&request.player_id)?;
+
+        // Database Load: One extra battle tells whether another page follows
+        let mut battles = self.db.list_player_history(&request.player_id, request.limit + 1, request.cursor.as_ref()).await
+            .map_err(ApiError::from_database)?;
+        let next_cursor = if battles.len() > request.limit {
+            battles.truncate(request.limit);
+            battles.last().map(|battle| HistoryCursor::of(battle).to_string())
+        } else {
+            None
+        };
+
+        // Response: Each battle from the player's side
+        let battles = battles.iter()
+            .map(|battle| {
+                let player_index = if battle.player1_id == request.player_id { 0 } else { 1 };
+                PlayerHistoryEntry {
+                    battle_id: battle.battle_id,
+                    result: match engine::winner_index(&battle.battle_state) {
+                        Some(winner) if winner == player_index => HistoryResult::Win,
+                        Some(_) => HistoryResult::Loss,
+                        None => HistoryResult::Draw,
+                    },
+                    opponent_name: engine::display_name(&battle.battle_state.players[1 - player_index].player_name),
+                    finished_at: battle.last_updated,
+                    turn_count: engine::turns_resolved(&battle.battle_state),
+                }
+            })
+            .collect();
+
+        Ok(PlayerHistoryResponse {
+            player_id: request.player_id,
+            battles,
+            next_cursor,
+        })
+    }
+
+    pub async fn list_player_battles(&self, request: ListPlayerBattlesRequest) -> Result<PlayerBattlesResponse, ApiError> {
+        // Authorization: Only the player themselves may list their battles
+        match &request.authenticated_player_id {
+            None => return Err(ApiError::AuthRequired),
+            Some(authenticated) if *authenticated != request.player_id => {
+                return Err(ApiError::player_not_authorized(&request.player_id));
+            }
+            Some(_) => {}
+        }
+        reject_shared_mvp_player(&request.player_id)?;
+
+        // Database Load: Battle ids from the player indexes, then the battles themselves
+        let battle_ids = self.db.list_player_battles(&request.player_id, request.limit).await
+            .map_err(ApiError::from_database)?;
+        let battles = self.db.get_battles(&battle_ids).await
+            .map_err(ApiError::from_database)?;
+
+        // Response: Newest activity first
+        let mut battles: Vec<PlayerBattleEntry> = battles.into_iter()
+            .map(|battle| PlayerBattleEntry {
+                battle_id: battle.battle_id,
+                battle_number: battle.battle_number,
+                opponent_id: if battle.player1_id == request.player_id {
+                    battle.player2_id
+                } else {
+                    battle.player1_id
+                },
+                game_state: battle.battle_state.game_state,
+                created_at: battle.created_at,
+                last_updated: battle.last_updated,
+            })
+            .collect();
+        battles.sort_by(|a, b| b.last_updated.cmp(&a.last_updated));
+
+        Ok(PlayerBattlesResponse {
+            player_id: request.player_id,
+            battles,
+        })
+    }
+
+    /// Forfeit every unfinished battle the player is in, for logout and account cleanup
+    pub async fn abandon_all_battles(&self, request: AbandonAllRequest) -> Result<AbandonAllResponse, ApiError> {
+        // Authorization: Only the player themselves may abandon their battles
+        match &request.authenticated_player_id {
+            None => return Err(ApiError::AuthRequired),
+            Some(authenticated) if *authenticated != request.player_id => {
+                return Err(ApiError::player_not_authorized(&request.player_id));
+            }
+            Some(_) => {}
+        }
+        reject_shared_mvp_player(&request.player_id)?;
+
+        // Database Load: The battles the player is in, up to the cap
+        let battle_ids = self.db.list_player_battles(&request.player_id, MAX_ABANDON_BATTLES).await
+            .map_err(ApiError::from_database)?;
+
+        let mut abandoned_count = 0;
+        let mut failed_battle_ids = Vec::new();
+        for battle_id in battle_ids {
+            let stored_battle = match self.db.get_battle(battle_id).await {
+                Ok(Some(stored_battle)) => stored_battle,
+                Ok(None) => continue,
+                Err(e) => {
+                    error!("Failed to load battle {} to abandon for {}: {}", battle_id, request.player_id.0, e);
+                    failed_battle_ids.push(battle_id);
+                    continue;
+                }
+            };
+            if stored_battle.is_finished() {
+                continue;
+            }
+
+            // Engine Logic + Database Save: Same path as a forfeit the player submits
+            let forfeit = self.submit_action(SubmitActionRequest {
+                battle_id,
+                player_id: request.player_id.clone(),
+                action: PlayerAction::Forfeit,
+                async_resolution: false,
+                authenticated_player_id: request.authenticated_player_id.clone(),
+            }).await;
+
+            match forfeit {
+                Ok(_) => abandoned_count += 1,
+                Err(e) => {
+                    error!("Failed to abandon battle {} for {}: {}", battle_id, request.player_id.0, e);
+                    failed_battle_ids.push(battle_id);
+                }
+            }
+        }
+
+        // Response: How many battles were forfeited
+        Ok(AbandonAllResponse {
+            player_id: request.player_id,
+            abandoned_count,
+            failed_battle_ids,
+        })
+    }
+
+    /// Queue the action and respond immediately; the next `/tick` poll resolves the turn
+    /// Nothing runs after the response: Lambda freezes the environment once the handler returns,
+    /// so a background task could be cut off mid-save and leave the battle pending for good
+    async fn submit_action_async(
+        &self,
+        mut stored_battle: StoredBattle,
+        request: &SubmitActionRequest,
+    ) -> Result<SubmitActionResponse, ApiError> {
+        // Engine Logic: Validate and queue without resolving
+        stored_battle.battle_state = engine::queue_action(
+            stored_battle.battle_state,
+            &request.player_id,
+            request.action.clone(),
+            &stored_battle.rules,
+        )?;
+        stored_battle.resolution_pending = true;
+        stored_battle.last_updated = current_timestamp();
+
+        // Database Save: Persist the queued action before responding
+        self.db.update_battle(&stored_battle).await
+            .map_err(ApiError::from_database)?;
+
+        let battle_view = engine::get_battle_state_for_player(
+            &stored_battle.battle_state,
+            &request.player_id,
+            &stored_battle.rules,
+        )?;
+
+        // Response: Queued, not yet resolved
+        Ok(SubmitActionResponse {
+            success: true,
+            message: "Action queued; poll /tick for the resolved turn".to_string(),
+            battle_updated: false,
+            game_state: battle_view.game_state,
+            turn_number: battle_view.turn_number,
+            turns_resolved: battle_view.turns_resolved,
+            can_act: false,
+            outcome: None,
+        })
+    }
+
+    /// Resolve a turn queued by `submit_action_async` and save it conditionally on the loaded version
+    /// A save that loses the race (another poll resolved it first) reports whatever is stored now;
+    /// any other failure leaves the battle pending, so the next poll simply tries again
+    async fn resolve_pending_turn(&self, mut stored_battle: StoredBattle) -> Result<StoredBattle, ApiError> {
+        let battle_id = stored_battle.battle_id;
+
+        // Engine Logic: Resolve the queued actions; if that fails, drop them so the battle is not stuck
+        let move_counts = match engine::resolve_queued_actions(stored_battle.battle_state.clone(), stored_battle.battle_seed) {
+            Ok((new_battle_state, turn_events)) => {
+                let move_counts = move_usage_counts(&turn_events.structured_events);
+                record_turn(&mut stored_battle, new_battle_state, turn_events);
+                move_counts
+            }
+            Err(e) => {
+                error!("Async resolution failed for battle {}: {}", battle_id, e);
+                stored_battle.battle_state.action_queue = Default::default();
+                stored_battle.last_updated = current_timestamp();
+                HashMap::new()
+            }
+        };
+        stored_battle.resolution_pending = false;
+
+        // Database Save: Conditional on the version loaded above
+        match self.db.update_battle(&stored_battle).await.map_err(ApiError::from_database) {
+            Ok(()) => {
+                record_move_usage(self.db.as_ref(), &move_counts).await;
+                advance_match(self.db.as_ref(), &stored_battle).await;
+                stored_battle.version += 1;
+                Ok(stored_battle)
+            }
+            Err(ApiError::Conflict { .. }) => self.db.get_battle(battle_id).await
+                .map_err(ApiError::from_database)?
+                .ok_or_else(|| ApiError::battle_not_found(battle_id)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Poll whether an asynchronously submitted turn has been resolved, resolving it if it is still pending
+    pub async fn get_tick_status(&self, request: GetTickStatusRequest) -> Result<GetTickStatusResponse, ApiError> {
         // Database Load: Get current battle state
-        let stored_battle = self.db.get_battle(request.battle_id).await
-            .map_err(|e| ApiError::DatabaseError { message: e.to_string() })?
+        let mut stored_battle = self.db.get_battle(request.battle_id).await
+            .map_err(ApiError::from_database)?
             .ok_or_else(|| ApiError::battle_not_found(request.battle_id))?;
 
-        // Engine Logic: Pure function creates player-specific view
+        // Engine Logic + Database Save: Only participants may poll, and so drive the resolution
+        engine::validate_player_authorization(&stored_battle.battle_state, &request.player_id)?;
+        if stored_battle.resolution_pending {
+            stored_battle = self.resolve_pending_turn(stored_battle).await?;
+        }
+
+        // Engine Logic: Player-specific view of the latest state
         let battle_view = engine::get_battle_state_for_player(
             &stored_battle.battle_state,
             &request.player_id,
+            &stored_battle.rules,
         )?;
 
-        // Response: Convert engine view to API response
-        Ok(GetBattleStateResponse {
+        // Response: Resolution status
+        Ok(GetTickStatusResponse {
             battle_id: request.battle_id,
+            processing: stored_battle.resolution_pending,
             game_state: battle_view.game_state,
             turn_number: battle_view.turn_number,
-            can_act: battle_view.can_act,
-            player_team: convert_team_view(battle_view.player_team),
-            opponent_info: convert_opponent_view(battle_view.opponent_public_info),
+            turns_resolved: battle_view.turns_resolved,
+            can_act: battle_view.can_act && !stored_battle.resolution_pending,
         })
     }
 
+    /// Get current battle state - Clean architecture implementation
+    pub async fn get_battle_state(&self, request: GetBattleStateRequest) -> Result<GetBattleStateResponse, ApiError> {
+        // Database Load: Get current battle state
+        let stored_battle = self.db.get_battle(request.battle_id).await
+            .map_err(ApiError::from_database)?
+            .ok_or_else(|| ApiError::battle_not_found(request.battle_id))?;
+
+        // Authorization: An observer token stands in for the participant it was granted for
+        let player_id = match &request.observer_token {
+            Some(token) => stored_battle.observer_tokens.iter()
+                .find(|observer| observer.token == *token)
+                .map(|observer| observer.player_id.clone())
+                .ok_or(ApiError::InvalidObserverToken)?,
+            None => request.player_id.clone(),
+        };
+
+        // Engine Logic: Pure function creates player-specific view
+        let mut battle_view = engine::get_battle_state_for_player(
+            &stored_battle.battle_state,
+            &player_id,
+            &stored_battle.rules,
+        )?;
+
+        // Engine Logic: Opponent moves revealed so far, from the turn logs
+        battle_view.opponent_public_info.revealed_moves = engine::get_revealed_opponent_moves(
+            &stored_battle.battle_state,
+            &player_id,
+            &stored_battle.turn_logs,
+            !request.strict_info,
+        )?;
+
+        // Response: Convert engine view to API response
+        let mut response = convert_battle_view(request.battle_id, battle_view);
+        response.paused = stored_battle.paused;
+        response.can_act &= !stored_battle.paused;
+        if request.strict_info {
+            // Exact sleep turns are hidden information; strict mode only shows that the opponent sleeps
+            if let Some(status) = response.opponent_info.active_pokemon.as_mut().and_then(|p| p.status.as_mut()) {
+                status.turns_remaining = None;
+            }
+        }
+        if request.debug {
+            // Participants get the opponent's hidden details redacted; admins see ground truth
+            let viewer = if request.is_admin {
+                None
+            } else {
+                Some(engine::validate_player_authorization(&stored_battle.battle_state, &player_id)?)
+            };
+            response.raw_state = Some(engine::debug_battle_state(&stored_battle.battle_state, viewer)?);
+        }
+        Ok(response)
+    }
+
     /// Get valid actions for a player - Clean architecture implementation  
     pub async fn get_valid_actions(&self, request: GetValidActionsRequest) -> Result<GetValidActionsResponse, ApiError> {
         // Database Load: Get current battle state
         let stored_battle = self.db.get_battle(request.battle_id).await
-            .map_err(|e| ApiError::DatabaseError { message: e.to_string() })?
+            .map_err(ApiError::from_database)?
             .ok_or_else(|| ApiError::battle_not_found(request.battle_id))?;
 
         // Engine Logic: Pure function gets valid actions
         let valid_actions = engine::get_player_valid_actions(
             &stored_battle.battle_state,
             &request.player_id,
+            &stored_battle.rules,
+        )?;
+
+        // Engine Logic: Type matchup preview for each switch option
+        let switch_previews = engine::get_switch_previews(
+            &stored_battle.battle_state,
+            &request.player_id,
+            &stored_battle.rules,
+        )?;
+
+        // Engine Logic: Priority bracket and hit chance for each move option
+        let move_analysis = engine::analyze_moves(
+            &stored_battle.battle_state,
+            &request.player_id,
+            &stored_battle.rules,
         )?;
 
         // Response: Convert to API format
         Ok(GetValidActionsResponse {
             battle_id: request.battle_id,
             valid_actions,
+            switch_previews,
+            move_analysis,
+        })
+    }
+
+    /// Get PP remaining on the active Pokemon's moves
+    pub async fn get_move_pp(&self, request: GetMovePpRequest) -> Result<GetMovePpResponse, ApiError> {
+        // Database Load: Get current battle state
+        let stored_battle = self.db.get_battle(request.battle_id).await
+            .map_err(ApiError::from_database)?
+            .ok_or_else(|| ApiError::battle_not_found(request.battle_id))?;
+
+        // Engine Logic: Pure function reads the active Pokemon's move slots
+        let moves = engine::get_active_move_pp(
+            &stored_battle.battle_state,
+            &request.player_id,
+        )?;
+
+        // Response: Convert to API format
+        Ok(GetMovePpResponse {
+            battle_id: request.battle_id,
+            moves: moves.into_iter().map(convert_move_view).collect(),
         })
     }
 
@@ -151,13 +536,14 @@ impl BattleHandler {
     pub async fn get_team_info(&self, request: GetTeamInfoRequest) -> Result<GetTeamInfoResponse, ApiError> {
         // Database Load: Get current battle state
         let stored_battle = self.db.get_battle(request.battle_id).await
-            .map_err(|e| ApiError::DatabaseError { message: e.to_string() })?
+            .map_err(ApiError::from_database)?
             .ok_or_else(|| ApiError::battle_not_found(request.battle_id))?;
 
         // Engine Logic: Validate player and get team view
         let battle_view = engine::get_battle_state_for_player(
             &stored_battle.battle_state,
             &request.player_id,
+            &stored_battle.rules,
         )?;
 
         // Response: Return detailed team information
@@ -176,6 +562,15 @@ impl BattleHandler {
         Ok(AvailableTeamsResponse { teams })
     }
 
+    /// MVP Endpoints - Get available teams with full rosters
+    pub async fn get_available_teams_full(&self) -> Result<AvailableTeamsFullResponse, ApiError> {
+        // Engine Logic: Cached prefab rosters
+        let teams = engine::get_available_teams_full().to_vec();
+
+        // Response: Return every team's roster
+        Ok(AvailableTeamsFullResponse { teams })
+    }
+
     /// MVP Endpoints - Get NPC opponents
     pub async fn get_npc_opponents(&self) -> Result<NpcOpponentsResponse, ApiError> {
         // Engine Logic: Pure function gets NPC opponents
@@ -185,146 +580,1343 @@ impl BattleHandler {
         Ok(NpcOpponentsResponse { opponents })
     }
 
-    /// MVP Endpoints - Create battle with prefab team vs NPC
-    pub async fn create_mvp_battle(&self, request: CreateMvpBattleRequest) -> Result<CreateMvpBattleResponse, ApiError> {
-        let battle_id = BattleId::new();
-        
-        // Engine Logic: Create battle between player and NPC
-        let battle_state = engine::create_mvp_battle(
-            battle_id.to_string(),
-            request.player_name.clone(),
-            &request.team_id,
-            &request.opponent_id,
-        )?;
+    /// Get every game state with its description
+    pub async fn get_game_states(&self) -> Result<GameStatesResponse, ApiError> {
+        // Engine Logic: Pure function describes the state machine
+        let states = engine::get_game_states();
 
-        // Database Save: Store the new battle
-        let stored_battle = StoredBattle {
-            battle_id,
-            player1_id: PlayerId("player_1".to_string()),
-            player2_id: PlayerId("npc".to_string()),
-            battle_state: battle_state.clone(),
-            turn_logs: Vec::new(), // Start with empty turn logs
+        // Response: Return game states
+        Ok(GameStatesResponse { states })
+    }
+
+    /// Create a best-of-N match between two configured teams and start its first game
+    pub async fn create_match(&self, request: CreateMatchRequest) -> Result<CreateMatchResponse, ApiError> {
+        engine::validate_best_of(request.best_of)?;
+
+        let match_id = MatchId::new();
+        let mut stored_match = StoredMatch {
+            match_id,
+            best_of: request.best_of,
+            config: request.battle,
+            battle_ids: Vec::new(),
+            score: [0, 0],
+            winner_index: None,
+            finished: false,
             created_at: current_timestamp(),
             last_updated: current_timestamp(),
         };
 
-        self.db.create_battle(&stored_battle).await
-            .map_err(|e| ApiError::DatabaseError { message: e.to_string() })?;
+        // Engine Logic + Database Save: First game is an ordinary battle tagged with the match
+        let (battle_id, battle_number, _) = create_pvp_battle(
+            self.db.as_ref(),
+            &stored_match.config,
+            Some(match_id),
+        ).await?;
+        stored_match.battle_ids.push(battle_id);
 
-        // Response: Return battle info with initial state
-        let battle_view = engine::get_battle_state_for_player(
-            &battle_state,
-            &PlayerId("player_1".to_string()),
-        )?;
+        // Database Save: Store the match
+        self.db.create_match(&stored_match).await
+            .map_err(ApiError::from_database)?;
 
-        let initial_state = GetBattleStateResponse {
+        // Response: Match and first game ids
+        Ok(CreateMatchResponse {
+            match_id,
+            best_of: stored_match.best_of,
             battle_id,
-            game_state: battle_view.game_state,
-            turn_number: battle_view.turn_number,
-            can_act: battle_view.can_act,
-            player_team: convert_team_view(battle_view.player_team),
-            opponent_info: convert_opponent_view(battle_view.opponent_public_info),
-        };
+            battle_number,
+        })
+    }
 
-        Ok(CreateMvpBattleResponse {
-            battle_id,
-            status: "Battle created successfully".to_string(),
-            battle_state: initial_state,
+    /// Get a match's series score and per-game results
+    pub async fn get_match(&self, match_id: MatchId) -> Result<GetMatchResponse, ApiError> {
+        // Database Load: Match and each of its games
+        let stored_match = self.db.get_match(match_id).await
+            .map_err(ApiError::from_database)?
+            .ok_or_else(|| ApiError::match_not_found(match_id))?;
+
+        let player_ids = [stored_match.config.player1_id.clone(), stored_match.config.player2_id.clone()];
+        let mut games = Vec::new();
+        for (index, &battle_id) in stored_match.battle_ids.iter().enumerate() {
+            let battle = self.db.get_battle(battle_id).await
+                .map_err(ApiError::from_database)?;
+            let result = battle.as_ref().map(|b| b.battle_state.game_state);
+            let winner_player_id = battle.as_ref()
+                .and_then(|b| engine::winner_index(&b.battle_state))
+                .map(|winner| player_ids[winner].clone());
+            games.push(MatchGame {
+                game_number: index as u32 + 1,
+                battle_id,
+                result,
+                winner_player_id,
+            });
+        }
+
+        // Response: Series summary
+        Ok(GetMatchResponse {
+            match_id,
+            best_of: stored_match.best_of,
+            score: stored_match.score,
+            finished: stored_match.finished,
+            winner_player_id: stored_match.winner_index.map(|winner| player_ids[winner].clone()),
+            current_battle_id: if stored_match.finished { None } else { stored_match.battle_ids.last().copied() },
+            player1_id: stored_match.config.player1_id,
+            player2_id: stored_match.config.player2_id,
+            games,
         })
     }
 
-    /// Get battle events/log - Clean architecture implementation
-    pub async fn get_battle_events(&self, request: GetBattleEventsRequest) -> Result<GetBattleEventsResponse, ApiError> {
-        // Database Load: Get battle
-        let stored_battle = self.db.get_battle(request.battle_id).await
-            .map_err(|e| ApiError::DatabaseError { message: e.to_string() })?
-            .ok_or_else(|| ApiError::battle_not_found(request.battle_id))?;
+    /// Compare two teams' type matchups before battling
+    pub async fn get_team_matchup(&self, request: TeamMatchupRequest) -> Result<TeamMatchupResponse, ApiError> {
+        // Engine Logic: Pure function computes the effectiveness grid
+        let pairings = engine::team_matchup(&request.team_a, &request.team_b)?;
 
-        // Validate player authorization
-        let _player_index = engine::validate_player_authorization(
-            &stored_battle.battle_state,
-            &request.player_id,
-        )?;
+        let team_a_advantages = pairings.iter().filter(|p| p.advantage == "team_a").count();
+        let team_b_advantages = pairings.iter().filter(|p| p.advantage == "team_b").count();
 
-        // Filter turn logs based on request
-        let turn_logs = if let Some(last_turns) = request.last_turns {
-            // Get only the last X turns
-            let total_turns = stored_battle.turn_logs.len();
-            let start_index = if total_turns > last_turns as usize {
-                total_turns - last_turns as usize
-            } else {
-                0
-            };
-            stored_battle.turn_logs[start_index..].to_vec()
-        } else {
-            // Get all turn logs
-            stored_battle.turn_logs.clone()
-        };
+        // Response: Return the matchup grid
+        Ok(TeamMatchupResponse {
+            pairings,
+            team_a_advantages,
+            team_b_advantages,
+        })
+    }
 
-        // Response: Return filtered turn logs
-        Ok(GetBattleEventsResponse {
-            battle_id: request.battle_id,
-            turn_logs,
-            total_turns: stored_battle.battle_state.turn_number,
+    /// Preview a custom team's computed stats without creating a battle
+    pub async fn preview_team(&self, request: TeamPreviewRequest) -> Result<TeamPreviewResponse, ApiError> {
+        // Engine Logic: Same instantiation path as battle creation
+        let pokemon = engine::preview_team(&request.team)?;
+
+        // Response: Convert each Pokemon to the API detail view
+        Ok(TeamPreviewResponse {
+            pokemon: pokemon.into_iter().map(convert_pokemon_detail).collect(),
         })
     }
-}
 
-// Helper functions for converting engine types to API types
+    /// Check a proposed team against the battle creation rules, reporting every problem
+    pub async fn validate_team(&self, request: TeamValidationRequest) -> Result<TeamValidationResponse, ApiError> {
+        // Engine Logic: The same checks battle creation applies, collected instead of fail-fast
+        let errors = engine::validate_team(&request.team, request.allow_illegal_moves);
 
-fn convert_team_view(team_view: engine::TeamView) -> ApiTeamView {
-    ApiTeamView {
-        active_pokemon: team_view.active_pokemon.map(convert_pokemon_detail),
-        team_pokemon: team_view.team_pokemon.into_iter()
-            .map(|p| p.map(convert_pokemon_summary))
-            .collect(),
+        // Response: Valid only with no errors at all
+        Ok(TeamValidationResponse {
+            valid: errors.is_empty(),
+            errors,
+        })
     }
-}
 
-fn convert_pokemon_detail(pokemon: engine::PokemonDetailView) -> ApiPokemonDetail {
-    ApiPokemonDetail {
-        name: pokemon.name,
-        species: pokemon.species,
-        level: pokemon.level,
-        current_hp: pokemon.current_hp,
-        max_hp: pokemon.max_hp,
-        attack: pokemon.stats.attack,
-        defense: pokemon.stats.defense,
-        sp_attack: pokemon.stats.sp_attack,
-        sp_defense: pokemon.stats.sp_defense,
-        speed: pokemon.stats.speed,
-        moves: pokemon.moves.into_iter()
-            .map(|m| m.map(convert_move_view))
-            .collect(),
-        status: pokemon.status.map(|s| format!("{:?}", s)),
+    /// Look up how effective an attacking type is against a species
+    pub async fn get_effectiveness(&self, move_type: PokemonType, defender_species: Species) -> Result<EffectivenessResponse, ApiError> {
+        // Engine Logic: Pure type chart lookup
+        let (defender_types, multiplier) = engine::species_effectiveness(move_type, defender_species)?;
+
+        // Response: Return the multiplier
+        Ok(EffectivenessResponse {
+            move_type,
+            defender_species,
+            defender_types,
+            multiplier,
+        })
     }
-}
 
-fn convert_pokemon_summary(pokemon: engine::PokemonSummaryView) -> ApiPokemonSummary {
-    ApiPokemonSummary {
-        name: pokemon.name,
-        species: pokemon.species,
-        level: pokemon.level,
-        current_hp: pokemon.current_hp,
-        max_hp: pokemon.max_hp,
-        is_fainted: pokemon.is_fainted,
-        status: pokemon.status.map(|s| format!("{:?}", s)),
+    /// List the moves a species can learn, for the team builder
+    pub async fn get_learnable_moves(&self, species: Species) -> Result<LearnsetResponse, ApiError> {
+        // Engine Logic: Pure lookup of species learnset
+        let moves = engine::get_learnable_moves(species)?;
+
+        // Response: Return the learnset
+        Ok(LearnsetResponse { species, moves })
     }
-}
 
-fn convert_move_view(move_view: engine::MoveView) -> ApiMoveView {
-    ApiMoveView {
-        move_: move_view.move_,
-        pp: move_view.pp,
-        max_pp: move_view.max_pp,
+    /// Pokedex entry for a species
+    pub async fn get_species_info(&self, species: Species) -> Result<SpeciesInfo, ApiError> {
+        // Engine Logic: Pure lookup of species data
+        engine::get_species_info(species)
     }
-}
 
-fn convert_opponent_view(opponent: engine::OpponentView) -> ApiOpponentView {
-    ApiOpponentView {
-        player_name: opponent.player_name,
-        active_pokemon: opponent.active_pokemon.map(convert_pokemon_summary),
-        remaining_pokemon_count: opponent.remaining_pokemon_count,
+    /// Static data for a move
+    pub async fn get_move_info(&self, move_: Move) -> Result<MoveInfo, ApiError> {
+        // Engine Logic: Pure lookup of move data
+        engine::get_move_info(move_)
+    }
+
+    /// MVP Endpoints - Create battle with prefab team vs NPC
+    pub async fn create_mvp_battle(&self, request: CreateMvpBattleRequest) -> Result<CreateMvpBattleResponse, ApiError> {
+        // Engine Logic: Create battle between player and NPC
+        let stored_battle = new_mvp_battle(self.db.as_ref(), request).await?;
+
+        // Database Save: Store the new battle
+        self.db.create_battle(&stored_battle).await
+            .map_err(ApiError::from_database)?;
+
+        // Response: Return battle info with initial state
+        mvp_battle_response(&stored_battle, "Battle created successfully")
+    }
+
+    /// Create an MVP battle and apply the player's first action, saving the battle once
+    /// The battle and its number are only allocated after the action resolves, so a rejected action creates nothing
+    pub async fn quickplay(&self, request: QuickplayRequest) -> Result<CreateMvpBattleResponse, ApiError> {
+        // Engine Logic: Create battle between player and NPC, numbered once the first action is accepted
+        let mut stored_battle = mvp_battle(request.battle, None)?;
+
+        // Engine Logic: Pure function processes the first action against the unsaved battle
+        let (new_battle_state, turn_events) = engine::submit_action(
+            &stored_battle.battle_state,
+            &stored_battle.player1_id,
+            request.action,
+            &stored_battle.rules,
+            stored_battle.battle_seed,
+        )?;
+
+        // Database Save: Store the battle with its first turn in a single write, under the next short number
+        let move_counts = move_usage_counts(&turn_events.structured_events);
+        record_turn(&mut stored_battle, new_battle_state, turn_events);
+        stored_battle.battle_number = Some(self.db.next_battle_number().await
+            .map_err(ApiError::from_database)?);
+
+        self.db.create_battle(&stored_battle).await
+            .map_err(ApiError::from_database)?;
+        record_move_usage(self.db.as_ref(), &move_counts).await;
+
+        // Response: Battle info with the state after the first action
+        mvp_battle_response(&stored_battle, "Battle created and first action processed")
+    }
+
+    /// Play an MVP battle to the end without storing it, for engine regression tests
+    /// The player always takes their first valid non-forfeit action and every turn's RNG
+    /// comes from the seed, so the same request always plays out the same way
+    pub async fn playout(&self, request: PlayoutRequest) -> Result<PlayoutResponse, ApiError> {
+        // Engine Logic: Create the battle exactly as `POST /battles` would, minus the battle number
+        let mut stored_battle = mvp_battle(CreateMvpBattleRequest {
+            player_name: "Playout".to_string(),
+            team_id: request.team_id,
+            opponent_id: request.opponent_id,
+            opponent_team_id: Some(request.opponent_team_id),
+            opponent_level_cap: request.opponent_level_cap,
+            switch_uses_turn: request.switch_uses_turn,
+            grant_observer_token: false,
+            battle_seed: Some(request.battle_seed),
+        }, None)?;
+
+        // Engine Logic: The NPC acts on its own, so only the player's choices need driving
+        let mut actions_submitted = 0;
+        while !engine::is_battle_finished(&stored_battle.battle_state) {
+            if actions_submitted == MAX_PLAYOUT_ACTIONS {
+                return Err(ApiError::InvalidBattleState {
+                    state: format!("Playout did not finish within {} actions", MAX_PLAYOUT_ACTIONS),
+                });
+            }
+
+            let action = engine::get_player_valid_actions(
+                &stored_battle.battle_state,
+                &stored_battle.player1_id,
+                &stored_battle.rules,
+            )?
+                .into_iter()
+                .find(|action| !matches!(action, PlayerAction::Forfeit))
+                .ok_or_else(|| ApiError::InvalidBattleState {
+                    state: "Player has no action to take".to_string(),
+                })?;
+
+            let (new_battle_state, turn_events) = engine::submit_action(
+                &stored_battle.battle_state,
+                &stored_battle.player1_id,
+                action,
+                &stored_battle.rules,
+                stored_battle.battle_seed,
+            )?;
+            record_turn(&mut stored_battle, new_battle_state, turn_events);
+            actions_submitted += 1;
+        }
+
+        // Response: The result with the complete log
+        let outcome = engine::get_battle_outcome(&stored_battle.battle_state)
+            .ok_or_else(|| ApiError::InvalidBattleState { state: "Playout ended unfinished".to_string() })?;
+
+        Ok(PlayoutResponse {
+            result: outcome.result,
+            winner_player_id: outcome.winner_player_id,
+            draw_reason: stored_battle.draw_reason,
+            turn_count: engine::turns_resolved(&stored_battle.battle_state),
+            turn_logs: stored_battle.turn_logs,
+        })
+    }
+
+    /// Get battle events/log - Clean architecture implementation
+    pub async fn get_battle_events(&self, request: GetBattleEventsRequest) -> Result<GetBattleEventsResponse, ApiError> {
+        // Database Load: Get battle
+        let stored_battle = self.db.get_battle(request.battle_id).await
+            .map_err(ApiError::from_database)?
+            .ok_or_else(|| ApiError::battle_not_found(request.battle_id))?;
+
+        // Engine Logic + Response: Authorize and filter the battle's turn logs
+        battle_events(&stored_battle, &request)
+    }
+
+    /// Get events for several battles at once, e.g. every game of a match recap
+    pub async fn get_batch_events(&self, request: BatchEventsRequest) -> Result<BatchEventsResponse, ApiError> {
+        if request.battle_ids.len() > MAX_BATCH_BATTLES {
+            return Err(ApiError::bad_request(format!(
+                "At most {} battles can be fetched at once", MAX_BATCH_BATTLES
+            )));
+        }
+
+        // Database Load: All requested battles in one batch
+        let stored_battles = self.db.get_battles(&request.battle_ids).await
+            .map_err(ApiError::from_database)?;
+
+        // Engine Logic: Same authorization and filtering as a single /events call, in request order
+        let mut battles = Vec::new();
+        for &battle_id in &request.battle_ids {
+            let stored_battle = stored_battles.iter()
+                .find(|battle| battle.battle_id == battle_id)
+                .ok_or_else(|| ApiError::battle_not_found(battle_id))?;
+            battles.push(battle_events(stored_battle, &GetBattleEventsRequest {
+                battle_id,
+                player_id: request.player_id.clone(),
+                last_turns: request.last_turns,
+                since_turn: None,
+                event_types: None,
+                include_markers: false,
+                offset: None,
+                limit: None,
+            })?);
+        }
+
+        // Response: One events response per battle
+        Ok(BatchEventsResponse { battles })
+    }
+
+    /// Resolve a short battle number to its battle id, for its participants and admins
+    pub async fn get_battle_by_number(&self, request: GetBattleByNumberRequest) -> Result<BattleByNumberResponse, ApiError> {
+        let battle_number = request.battle_number;
+
+        // Database Load: Look up by number
+        let stored_battle = self.db.get_battle_by_number(battle_number).await
+            .map_err(ApiError::from_database)?
+            .ok_or_else(|| ApiError::battle_number_not_found(battle_number))?;
+
+        // Authorization: Numbers are guessable, so the id only goes to the battle's own players
+        if !request.is_admin {
+            let authenticated = request.authenticated_player_id.as_ref().ok_or(ApiError::AuthRequired)?;
+            engine::validate_player_authorization(&stored_battle.battle_state, authenticated)?;
+        }
+
+        // Response: Return the id for use with the other battle endpoints
+        Ok(BattleByNumberResponse {
+            battle_id: stored_battle.battle_id,
+            battle_number,
+        })
+    }
+
+    /// Delete a battle, for one of its participants
+    pub async fn delete_battle(&self, request: DeleteBattleRequest) -> Result<(), ApiError> {
+        // Authorization: The deleting player must match the authenticated identity, when there is one
+        if let Some(authenticated) = &request.authenticated_player_id {
+            if *authenticated != request.player_id {
+                return Err(ApiError::player_not_authorized(&request.player_id));
+            }
+        }
+
+        // Database Load: The battle must exist and the requester must be in it
+        let stored_battle = self.db.get_battle(request.battle_id).await
+            .map_err(ApiError::from_database)?
+            .ok_or_else(|| ApiError::battle_not_found(request.battle_id))?;
+
+        engine::validate_player_authorization(&stored_battle.battle_state, &request.player_id)?;
+
+        // Database Delete: Remove the battle
+        self.db.delete_battle(request.battle_id).await
+            .map_err(ApiError::from_database)
+    }
+
+    /// Get a battle's creation parameters
+    /// Finished battles are public; in-progress battles are visible to participants only
+    pub async fn get_battle_setup(&self, request: GetBattleSetupRequest) -> Result<GetBattleSetupResponse, ApiError> {
+        // Database Load: Get battle
+        let stored_battle = self.db.get_battle(request.battle_id).await
+            .map_err(ApiError::from_database)?
+            .ok_or_else(|| ApiError::battle_not_found(request.battle_id))?;
+
+        // Validate player authorization while the battle is in progress
+        if !engine::is_battle_finished(&stored_battle.battle_state) {
+            engine::validate_player_authorization(
+                &stored_battle.battle_state,
+                &request.player_id,
+            )?;
+        }
+
+        let setup = stored_battle.setup
+            .ok_or(ApiError::SetupNotRecorded { battle_id: request.battle_id })?;
+
+        // Response: Return the recorded setup
+        Ok(GetBattleSetupResponse {
+            battle_id: request.battle_id,
+            setup,
+        })
+    }
+
+    /// Per-Pokemon damage totals for a finished battle, for participants
+    pub async fn get_damage_report(&self, request: GetDamageReportRequest) -> Result<DamageReportResponse, ApiError> {
+        // Database Load: Get battle
+        let stored_battle = self.db.get_battle(request.battle_id).await
+            .map_err(ApiError::from_database)?
+            .ok_or_else(|| ApiError::battle_not_found(request.battle_id))?;
+
+        let player_index = engine::validate_player_authorization(
+            &stored_battle.battle_state,
+            &request.player_id,
+        )?;
+
+        // The report reveals exact damage to both teams, so only once the battle is over
+        if !engine::is_battle_finished(&stored_battle.battle_state) {
+            return Err(ApiError::BattleInProgress { battle_id: request.battle_id });
+        }
+
+        // Engine Logic: Totals from the structured events
+        let [player1_team, player2_team] = engine::damage_report(&stored_battle.battle_state, &stored_battle.turn_logs);
+        let (player_team, opponent_team) = if player_index == 0 {
+            (player1_team, player2_team)
+        } else {
+            (player2_team, player1_team)
+        };
+
+        // Response: Both teams from the requester's perspective
+        Ok(DamageReportResponse {
+            battle_id: request.battle_id,
+            player_team,
+            opponent_team,
+        })
+    }
+
+    /// Estimate how many turns a battle has left, from the damage logged so far
+    pub async fn get_battle_estimate(&self, request: GetBattleEstimateRequest) -> Result<BattleEstimateResponse, ApiError> {
+        // Database Load: Get battle
+        let stored_battle = self.db.get_battle(request.battle_id).await
+            .map_err(ApiError::from_database)?
+            .ok_or_else(|| ApiError::battle_not_found(request.battle_id))?;
+
+        // The estimate uses both teams' remaining HP, so only participants get it
+        engine::validate_player_authorization(&stored_battle.battle_state, &request.player_id)?;
+
+        // Engine Logic: Remaining HP over average damage per turn
+        let estimated_turns_remaining = engine::estimate_remaining_turns(
+            &stored_battle.battle_state,
+            &stored_battle.turn_logs,
+        );
+
+        // Response: The estimate with the turns it is based on
+        Ok(BattleEstimateResponse {
+            battle_id: request.battle_id,
+            turns_resolved: engine::turns_resolved(&stored_battle.battle_state),
+            estimated_turns_remaining,
+        })
+    }
+
+    /// Get both teams unmasked, for replay UIs and other neutral spectators
+    /// Participants only once the battle is over, so it can never reveal an opponent mid-battle
+    pub async fn get_spectator_view(&self, request: GetSpectatorViewRequest) -> Result<SpectatorViewResponse, ApiError> {
+        // Database Load: Get battle
+        let stored_battle = self.db.get_battle(request.battle_id).await
+            .map_err(ApiError::from_database)?
+            .ok_or_else(|| ApiError::battle_not_found(request.battle_id))?;
+
+        // Authorization: Admins any time, participants once the battle has finished
+        if !request.is_admin {
+            engine::validate_player_authorization(&stored_battle.battle_state, &request.player_id)?;
+            if !stored_battle.is_finished() {
+                return Err(ApiError::BattleInProgress { battle_id: request.battle_id });
+            }
+        }
+
+        // Engine Logic: Full team views for both sides
+        let view = engine::get_spectator_view(&stored_battle.battle_state);
+
+        // Response: Convert to API format
+        Ok(SpectatorViewResponse {
+            battle_id: request.battle_id,
+            game_state: view.game_state,
+            turn_number: view.turn_number,
+            turns_resolved: view.turns_resolved,
+            players: view.players.into_iter()
+                .map(|player| ApiSpectatorPlayer {
+                    player_id: player.player_id,
+                    player_name: player.player_name,
+                    team: convert_team_view(player.team),
+                })
+                .collect(),
+        })
+    }
+
+    /// Get only the Pokemon that changed after a turn
+    pub async fn get_battle_changes(&self, request: GetBattleChangesRequest) -> Result<BattleChangesResponse, ApiError> {
+        // Database Load: Get battle
+        let stored_battle = self.db.get_battle(request.battle_id).await
+            .map_err(ApiError::from_database)?
+            .ok_or_else(|| ApiError::battle_not_found(request.battle_id))?;
+
+        // Engine Logic: Diff the current state against the one rebuilt as of `since_turn`
+        let changes = engine::diff_since(
+            &stored_battle.battle_state,
+            stored_battle.initial_state.as_ref(),
+            &stored_battle.turn_logs,
+            request.since_turn,
+            &request.player_id,
+        )?;
+
+        // Response: Convert to API format
+        Ok(BattleChangesResponse {
+            battle_id: request.battle_id,
+            since_turn: request.since_turn,
+            latest_turn: engine::turns_resolved(&stored_battle.battle_state),
+            game_state: stored_battle.battle_state.game_state,
+            team: changes.team.into_iter().map(convert_pokemon_change).collect(),
+            opponent: changes.opponent.into_iter().map(convert_pokemon_change).collect(),
+            full_refresh: changes.full_refresh,
+        })
+    }
+
+    /// Re-simulate a battle from its recorded turns and check it arrives at the stored state
+    pub async fn replay_battle(&self, request: GetBattleReplayRequest) -> Result<BattleReplayResponse, ApiError> {
+        // Database Load: Get battle
+        let stored_battle = self.db.get_battle(request.battle_id).await
+            .map_err(ApiError::from_database)?
+            .ok_or_else(|| ApiError::battle_not_found(request.battle_id))?;
+
+        engine::validate_player_authorization(&stored_battle.battle_state, &request.player_id)?;
+
+        // Battles without a seed resolved turns with the engine's own RNG, which cannot be re-run
+        let initial_state = stored_battle.initial_state.as_ref()
+            .filter(|_| engine::is_replayable(&stored_battle.turn_logs))
+            .ok_or(ApiError::ReplayUnavailable { battle_id: request.battle_id })?;
+
+        // Engine Logic: Re-resolve each logged turn from the recorded actions and seeds
+        let replayed = engine::replay_turns(initial_state, &stored_battle.turn_logs);
+        let final_state = replayed.last().map_or(initial_state, |(_, battle_state)| battle_state);
+        let matches_stored_state = serde_json::to_value(final_state).ok()
+            == serde_json::to_value(&stored_battle.battle_state).ok();
+
+        // Response: The requester's view before the first turn and after each one
+        let view = |battle_state: &BattleState| engine::get_battle_state_for_player(
+            battle_state,
+            &request.player_id,
+            &stored_battle.rules,
+        ).map(|battle_view| convert_battle_view(request.battle_id, battle_view));
+
+        let turns = replayed.iter()
+            .map(|(turn_number, battle_state)| Ok(ReplayTurn {
+                turn_number: *turn_number,
+                battle_state: view(battle_state)?,
+            }))
+            .collect::<Result<Vec<_>, ApiError>>()?;
+
+        Ok(BattleReplayResponse {
+            battle_id: request.battle_id,
+            initial: view(initial_state)?,
+            turns,
+            matches_stored_state,
+        })
+    }
+
+    /// Forfeit a battle immediately, returning its final state
+    pub async fn forfeit(&self, request: ForfeitRequest) -> Result<ForfeitResponse, ApiError> {
+        // Authorization: The forfeiting player must match the authenticated identity, when there is one
+        if let Some(authenticated) = &request.authenticated_player_id {
+            if *authenticated != request.player_id {
+                return Err(ApiError::player_not_authorized(&request.player_id));
+            }
+        }
+
+        // Database Load: Get battle
+        let mut stored_battle = self.db.get_battle(request.battle_id).await
+            .map_err(ApiError::from_database)?
+            .ok_or_else(|| ApiError::battle_not_found(request.battle_id))?;
+
+        engine::validate_player_authorization(&stored_battle.battle_state, &request.player_id)?;
+
+        if engine::is_battle_finished(&stored_battle.battle_state) {
+            return Err(ApiError::InvalidBattleState {
+                state: format!("{:?}", stored_battle.battle_state.game_state),
+            });
+        }
+
+        if stored_battle.resolution_pending {
+            return Err(ApiError::InvalidBattleState { state: "ResolutionPending".to_string() });
+        }
+
+        // Engine Logic: Resolve the forfeit without waiting on the opponent
+        let (new_battle_state, turn_events) = engine::forfeit(
+            &stored_battle.battle_state,
+            &request.player_id,
+            &stored_battle.rules,
+            stored_battle.battle_seed,
+        )?;
+
+        // Database Save: Same bookkeeping as a submitted action
+        let move_counts = move_usage_counts(&turn_events.structured_events);
+        record_turn(&mut stored_battle, new_battle_state, turn_events);
+
+        self.db.update_battle(&stored_battle).await
+            .map_err(ApiError::from_database)?;
+        record_move_usage(self.db.as_ref(), &move_counts).await;
+        advance_match(self.db.as_ref(), &stored_battle).await;
+
+        // Response: The final view, so the client needs no second call
+        let battle_view = engine::get_battle_state_for_player(
+            &stored_battle.battle_state,
+            &request.player_id,
+            &stored_battle.rules,
+        )?;
+        Ok(ForfeitResponse {
+            battle_id: request.battle_id,
+            game_state: battle_view.game_state,
+            battle_state: convert_battle_view(request.battle_id, battle_view),
+        })
+    }
+
+    /// Start a fresh MVP battle with a finished battle's setup; the NPC team is re-rolled
+    /// unless the original named a prefab for it; a seeded battle's rematch gets a fresh seed, so it can be replayed too
+    pub async fn rematch(&self, request: RematchRequest) -> Result<CreateMvpBattleResponse, ApiError> {
+        // Authorization: The player asking must match the authenticated identity, when there is one
+        if let Some(authenticated) = &request.authenticated_player_id {
+            if *authenticated != request.player_id {
+                return Err(ApiError::player_not_authorized(&request.player_id));
+            }
+        }
+
+        // Database Load: Get the finished battle
+        let stored_battle = self.db.get_battle(request.battle_id).await
+            .map_err(ApiError::from_database)?
+            .ok_or_else(|| ApiError::battle_not_found(request.battle_id))?;
+
+        engine::validate_player_authorization(&stored_battle.battle_state, &request.player_id)?;
+
+        if !stored_battle.is_finished() {
+            return Err(ApiError::BattleInProgress { battle_id: request.battle_id });
+        }
+
+        // Only MVP battles record the prefab team and opponent a rematch needs
+        let Some(BattleSetup { team_id: Some(team_id), opponent_id: Some(opponent_id), opponent_team_id, opponent_level_cap, .. }) =
+            stored_battle.setup.clone()
+        else {
+            return Err(ApiError::validation_error("Only battles against an NPC can be rematched"));
+        };
+
+        // Engine Logic: Create the new battle from the same configuration
+        let rematch = new_mvp_battle(self.db.as_ref(), CreateMvpBattleRequest {
+            player_name: stored_battle.battle_state.players[0].player_name.clone(),
+            team_id,
+            opponent_id,
+            opponent_team_id,
+            opponent_level_cap,
+            switch_uses_turn: stored_battle.rules.switch_uses_turn,
+            grant_observer_token: false,
+            battle_seed: stored_battle.battle_seed.map(|_| rand::random()),
+        }).await?;
+
+        // Database Save: Store the new battle
+        self.db.create_battle(&rematch).await
+            .map_err(ApiError::from_database)?;
+
+        // Response: Return the new battle's info with its initial state
+        mvp_battle_response(&rematch, "Rematch created successfully")
+    }
+
+    /// Pause or resume a battle, for participants agreeing to a break
+    pub async fn set_paused(&self, request: SetPausedRequest) -> Result<SetPausedResponse, ApiError> {
+        // Database Load: Get battle
+        let mut stored_battle = self.db.get_battle(request.battle_id).await
+            .map_err(ApiError::from_database)?
+            .ok_or_else(|| ApiError::battle_not_found(request.battle_id))?;
+
+        engine::validate_player_authorization(&stored_battle.battle_state, &request.player_id)?;
+
+        if engine::is_battle_finished(&stored_battle.battle_state) {
+            return Err(ApiError::InvalidBattleState {
+                state: format!("{:?}", stored_battle.battle_state.game_state),
+            });
+        }
+
+        // Database Save: Only the flag changes; pausing twice is harmless
+        if stored_battle.paused != request.paused {
+            stored_battle.paused = request.paused;
+            stored_battle.last_updated = current_timestamp();
+            self.db.update_battle(&stored_battle).await
+                .map_err(ApiError::from_database)?;
+        }
+
+        // Response: The battle's pause state
+        Ok(SetPausedResponse {
+            battle_id: request.battle_id,
+            paused: stored_battle.paused,
+        })
+    }
+
+    /// Get a finished battle's outcome - public, no participant authorization
+    pub async fn get_battle_result(&self, battle_id: BattleId) -> Result<BattleResultResponse, ApiError> {
+        // Database Load: Get battle
+        let stored_battle = self.db.get_battle(battle_id).await
+            .map_err(ApiError::from_database)?
+            .ok_or_else(|| ApiError::battle_not_found(battle_id))?;
+
+        // Engine Logic: Outcome is only available once the battle is over
+        let outcome = engine::get_battle_outcome(&stored_battle.battle_state)
+            .ok_or(ApiError::BattleInProgress { battle_id })?;
+
+        // Response: Public result
+        Ok(BattleResultResponse {
+            battle_id,
+            result: outcome.result,
+            winner_player_id: outcome.winner_player_id,
+            draw_reason: stored_battle.draw_reason,
+            turn_count: engine::turns_resolved(&stored_battle.battle_state),
+            finished_at: stored_battle.last_updated,
+        })
+    }
+
+    /// Render a finished battle as a Pokemon Showdown log for existing replay viewers
+    /// Public like the result, and for the same reason only once the battle is over
+    pub async fn get_showdown_log(&self, battle_id: BattleId) -> Result<ShowdownLogResponse, ApiError> {
+        // Database Load: Get battle
+        let stored_battle = self.db.get_battle(battle_id).await
+            .map_err(ApiError::from_database)?
+            .ok_or_else(|| ApiError::battle_not_found(battle_id))?;
+
+        if !stored_battle.is_finished() {
+            return Err(ApiError::BattleInProgress { battle_id });
+        }
+
+        // Engine Logic: Map the public structured log onto protocol lines
+        let lines = engine::showdown_log(&stored_battle.battle_state, &stored_battle.turn_logs);
+
+        // Response: One line per protocol message
+        Ok(ShowdownLogResponse {
+            battle_id,
+            log: lines.join("\n"),
+        })
+    }
+
+    /// Get the most recently finished battles as public summaries
+    pub async fn get_recent_battles(&self, limit: usize) -> Result<RecentBattlesResponse, ApiError> {
+        // Database Load: Recently finished battles from the feed index
+        let stored_battles = self.db.list_recent_finished(limit).await
+            .map_err(ApiError::from_database)?;
+
+        // Response: Public summaries only
+        let battles = stored_battles.iter()
+            .map(public_battle_summary)
+            .collect();
+
+        Ok(RecentBattlesResponse { battles })
+    }
+
+    /// Get a single turn's log by turn number - Clean architecture implementation
+    pub async fn get_turn_log(&self, request: GetTurnLogRequest) -> Result<GetTurnLogResponse, ApiError> {
+        // Database Load: Get battle
+        let stored_battle = self.db.get_battle(request.battle_id).await
+            .map_err(ApiError::from_database)?
+            .ok_or_else(|| ApiError::battle_not_found(request.battle_id))?;
+
+        // Validate player authorization
+        let player_index = engine::validate_player_authorization(
+            &stored_battle.battle_state,
+            &request.player_id,
+        )?;
+
+        // Find the requested turn
+        let mut turn_log = stored_battle.turn_logs.into_iter()
+            .find(|log| log.turn_number == request.turn_number)
+            .ok_or_else(|| ApiError::turn_not_found(request.battle_id, request.turn_number))?;
+        reveal_private_events(&mut turn_log, player_index);
+        filter_turn_log(&mut turn_log, None, false);
+
+        // Response: Return the single turn log
+        Ok(GetTurnLogResponse {
+            battle_id: request.battle_id,
+            turn_log,
+        })
+    }
+}
+
+// Admin endpoints - callers must be authenticated as admin by the router
+
+impl BattleHandler {
+    /// Get aggregate move usage across all battles, most used first
+    pub async fn get_move_usage(&self) -> Result<MoveUsageResponse, ApiError> {
+        // Database Load: Read the aggregated stats item
+        let counts = self.db.get_move_usage().await
+            .map_err(ApiError::from_database)?;
+
+        // Response: Sort by count, then name for a stable order
+        let mut moves: Vec<MoveUsageCount> = counts.into_iter()
+            .map(|(move_name, count)| MoveUsageCount { move_name, count })
+            .collect();
+        moves.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.move_name.cmp(&b.move_name)));
+        let total_uses = moves.iter().map(|m| m.count).sum();
+
+        Ok(MoveUsageResponse { moves, total_uses })
+    }
+
+    /// Admin: overwrite parts of a battle's state to set up an exact test scenario
+    pub async fn set_battle_state(&self, request: SetBattleStateRequest) -> Result<SetBattleStateResponse, ApiError> {
+        // Database Load: Get current battle state
+        let mut stored_battle = self.db.get_battle(request.battle_id).await
+            .map_err(ApiError::from_database)?
+            .ok_or_else(|| ApiError::battle_not_found(request.battle_id))?;
+
+        if stored_battle.resolution_pending {
+            return Err(ApiError::InvalidBattleState { state: "ResolutionPending".to_string() });
+        }
+
+        // Engine Logic: Apply the patch and check the result is coherent
+        stored_battle.battle_state = engine::patch_battle_state(&stored_battle.battle_state, &request.patch)?;
+        stored_battle.last_updated = current_timestamp();
+
+        // Database Save: Persist the patched state
+        self.db.update_battle(&stored_battle).await
+            .map_err(ApiError::from_database)?;
+
+        // Response: Summary of the patched battle
+        Ok(SetBattleStateResponse {
+            battle_id: request.battle_id,
+            game_state: stored_battle.battle_state.game_state,
+            turn_number: stored_battle.battle_state.turn_number,
+        })
+    }
+
+    /// Get which action queue slots are filled for a battle
+    pub async fn get_action_queue(&self, request: GetActionQueueRequest) -> Result<GetActionQueueResponse, ApiError> {
+        // Database Load: Get current battle state
+        let stored_battle = self.db.get_battle(request.battle_id).await
+            .map_err(ApiError::from_database)?
+            .ok_or_else(|| ApiError::battle_not_found(request.battle_id))?;
+
+        // Engine Logic: Queue slots and the turn's readiness to resolve
+        let queue_state = engine::debug_queue_state(&stored_battle.battle_state);
+
+        // Response: Queue diagnosis with the API-side flags that also hold a turn back
+        Ok(GetActionQueueResponse {
+            battle_id: request.battle_id,
+            game_state: stored_battle.battle_state.game_state,
+            turn_number: stored_battle.battle_state.turn_number,
+            slots: queue_state.slots,
+            ready_for_turn_resolution: queue_state.ready_for_turn_resolution,
+            awaiting_second_replacement: queue_state.awaiting_second_replacement,
+            resolution_pending: stored_battle.resolution_pending,
+            paused: stored_battle.paused,
+        })
+    }
+}
+
+// Helper functions for converting engine types to API types
+
+fn convert_battle_view(battle_id: BattleId, battle_view: engine::PlayerBattleView) -> GetBattleStateResponse {
+    GetBattleStateResponse {
+        battle_id,
+        game_state: battle_view.game_state,
+        turn_number: battle_view.turn_number,
+        turns_resolved: battle_view.turns_resolved,
+        can_act: battle_view.can_act,
+        will_struggle: battle_view.will_struggle,
+        can_switch: battle_view.can_switch,
+        trapped_reason: battle_view.trapped_reason,
+        player_team: convert_team_view(battle_view.player_team),
+        opponent_info: convert_opponent_view(battle_view.opponent_public_info),
+        field_conditions: battle_view.field_conditions,
+        paused: false,
+        raw_state: None,
+    }
+}
+
+fn convert_team_view(team_view: engine::TeamView) -> ApiTeamView {
+    ApiTeamView {
+        active_pokemon: team_view.active_pokemon.map(convert_pokemon_detail),
+        team_pokemon: team_view.team_pokemon.into_iter()
+            .map(|p| p.map(convert_pokemon_summary))
+            .collect(),
+    }
+}
+
+fn convert_pokemon_detail(pokemon: engine::PokemonDetailView) -> ApiPokemonDetail {
+    ApiPokemonDetail {
+        slot_id: pokemon.slot_id,
+        name: pokemon.name,
+        species: pokemon.species,
+        level: pokemon.level,
+        current_hp: pokemon.current_hp,
+        max_hp: pokemon.max_hp,
+        attack: pokemon.stats.attack,
+        defense: pokemon.stats.defense,
+        sp_attack: pokemon.stats.sp_attack,
+        sp_defense: pokemon.stats.sp_defense,
+        speed: pokemon.stats.speed,
+        moves: pokemon.moves.into_iter()
+            .map(|m| m.map(convert_move_view))
+            .collect(),
+        status: pokemon.status.as_ref().map(engine::status_dto),
+        charging_move: pokemon.charging_move,
+    }
+}
+
+fn convert_pokemon_summary(pokemon: engine::PokemonSummaryView) -> ApiPokemonSummary {
+    ApiPokemonSummary {
+        slot_id: pokemon.slot_id,
+        name: pokemon.name,
+        species: pokemon.species,
+        level: pokemon.level,
+        current_hp: pokemon.current_hp,
+        max_hp: pokemon.max_hp,
+        is_fainted: pokemon.is_fainted,
+        status: pokemon.status.as_ref().map(engine::status_dto),
+        types: pokemon.types,
+        error: pokemon.error,
+    }
+}
+
+fn convert_pokemon_change(change: engine::PokemonChange) -> ApiPokemonChange {
+    ApiPokemonChange {
+        slot_id: change.slot_id,
+        name: change.name,
+        current_hp: change.current_hp,
+        max_hp: change.max_hp,
+        status: change.status,
+        is_active: change.is_active,
+        move_pp: change.move_pp,
+    }
+}
+
+fn convert_move_view(move_view: engine::MoveView) -> ApiMoveView {
+    ApiMoveView {
+        move_: move_view.move_,
+        pp: move_view.pp,
+        max_pp: move_view.max_pp,
+        priority: move_view.priority,
+    }
+}
+
+fn convert_opponent_view(opponent: engine::OpponentView) -> ApiOpponentView {
+    ApiOpponentView {
+        player_name: opponent.player_name,
+        active_pokemon: opponent.active_pokemon.map(convert_pokemon_summary),
+        remaining_pokemon_count: opponent.remaining_pokemon_count,
+        revealed_moves: opponent.revealed_moves.into_iter()
+            .map(|revealed| ApiRevealedMove {
+                move_: revealed.move_,
+                times_used: revealed.times_used,
+                revealed_move_pp: revealed.revealed_move_pp,
+            })
+            .collect(),
+    }
+}
+
+/// Apply a resolved turn to the stored battle, appending a turn log if anything happened
+fn record_turn(stored_battle: &mut StoredBattle, new_battle_state: BattleState, turn_events: engine::TickEvents) {
+    let turn_number = engine::turns_resolved(&new_battle_state);
+    stored_battle.battle_state = new_battle_state;
+    stored_battle.last_updated = current_timestamp();
+    if turn_events.draw_reason.is_some() {
+        stored_battle.draw_reason = turn_events.draw_reason.clone();
+    }
+
+    // Add turn log if there were events
+    if !turn_events.is_empty() {
+        let turn_log = TurnLog {
+            turn_number,
+            events: turn_events.events,
+            structured_events: turn_events.structured_events,
+            private_events: turn_events.private_events,
+            resolutions: turn_events.resolutions,
+            timestamp: current_timestamp(),
+        };
+        stored_battle.turn_logs.push(turn_log);
+        stored_battle.last_resolved_turn = Some(turn_number);
+    }
+}
+
+/// Count move uses in a resolved turn, keyed by move name
+fn move_usage_counts(structured_events: &[BattleEventDto]) -> HashMap<String, u64> {
+    let mut counts = HashMap::new();
+    for event in structured_events {
+        if let BattleEventDto::MoveUsed { move_used, .. } = event {
+            *counts.entry(format!("{:?}", move_used)).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+/// Add a turn's move uses to the aggregate stats
+/// Stats are best-effort: a failure is logged rather than failing the already-saved action
+async fn record_move_usage(db: &dyn Db, move_counts: &HashMap<String, u64>) {
+    if move_counts.is_empty() {
+        return;
+    }
+    if let Err(e) = db.increment_move_usage(move_counts).await {
+        error!("Failed to record move usage: {}", e);
+    }
+}
+
+/// Per-player endpoints refuse the shared MVP id, which would reach every user's MVP battles
+fn reject_shared_mvp_player(player_id: &PlayerId) -> Result<(), ApiError> {
+    if player_id.0 == MVP_PLAYER_ID {
+        return Err(ApiError::validation_error(format!(
+            "'{}' is shared by every MVP battle and cannot be used here", MVP_PLAYER_ID
+        )));
+    }
+    Ok(())
+}
+
+/// Build a new battle between the player and an NPC, without saving it
+async fn new_mvp_battle(db: &dyn Db, request: CreateMvpBattleRequest) -> Result<StoredBattle, ApiError> {
+    // The battle is filed under the next short number
+    let battle_number = db.next_battle_number().await
+        .map_err(ApiError::from_database)?;
+    mvp_battle(request, Some(battle_number))
+}
+
+/// An unsaved MVP battle between the shared MVP player and an NPC
+fn mvp_battle(request: CreateMvpBattleRequest, battle_number: Option<u64>) -> Result<StoredBattle, ApiError> {
+    let battle_id = BattleId::new();
+
+    // Engine Logic: Create battle between player and NPC
+    let battle_state = engine::create_mvp_battle(
+        battle_id.to_string(),
+        request.player_name.clone(),
+        &request.team_id,
+        &request.opponent_id,
+        request.opponent_team_id.as_deref(),
+        request.opponent_level_cap,
+    )?;
+
+    Ok(StoredBattle {
+        battle_id,
+        player1_id: PlayerId(MVP_PLAYER_ID.to_string()),
+        player2_id: PlayerId("npc".to_string()),
+        battle_state: battle_state.clone(),
+        turn_logs: Vec::new(), // Start with empty turn logs
+        initial_state: Some(battle_state),
+        setup: Some(BattleSetup {
+            title: engine::mvp_battle_title(&request.team_id, &request.opponent_id),
+            team_id: Some(request.team_id),
+            opponent_id: Some(request.opponent_id),
+            opponent_team_id: request.opponent_team_id,
+            opponent_level_cap: request.opponent_level_cap,
+        }),
+        rules: BattleRules { switch_uses_turn: request.switch_uses_turn },
+        draw_reason: None,
+        resolution_pending: false,
+        paused: false,
+        battle_number,
+        match_id: None,
+        last_resolved_turn: None,
+        engine_schema_version: engine::ENGINE_SCHEMA_VERSION,
+        observer_tokens: if request.grant_observer_token {
+            vec![ObserverToken::new(PlayerId(MVP_PLAYER_ID.to_string()))]
+        } else {
+            Vec::new()
+        },
+        battle_seed: request.battle_seed,
+        version: 0,
+        created_at: current_timestamp(),
+        last_updated: current_timestamp(),
+    })
+}
+
+/// The player's view of a stored MVP battle and their next actions
+fn mvp_battle_response(stored_battle: &StoredBattle, status: &str) -> Result<CreateMvpBattleResponse, ApiError> {
+    let battle_view = engine::get_battle_state_for_player(
+        &stored_battle.battle_state,
+        &stored_battle.player1_id,
+        &stored_battle.rules,
+    )?;
+
+    let valid_actions = engine::get_player_annotated_actions(
+        &stored_battle.battle_state,
+        &stored_battle.player1_id,
+        &stored_battle.rules,
+    )?;
+
+    Ok(CreateMvpBattleResponse {
+        battle_id: stored_battle.battle_id,
+        battle_number: stored_battle.battle_number.unwrap_or_default(),
+        status: status.to_string(),
+        battle_state: convert_battle_view(stored_battle.battle_id, battle_view),
+        valid_actions,
+        observer_token: stored_battle.observer_tokens.first().map(|observer| observer.token.clone()),
+    })
+}
+
+/// Create and store a two-player battle, optionally as a game of a match
+async fn create_pvp_battle(
+    db: &dyn Db,
+    request: &CreateBattleRequest,
+    match_id: Option<MatchId>,
+) -> Result<(BattleId, u64, Option<String>), ApiError> {
+    let battle_id = BattleId::new();
+
+    if let Some(observed) = &request.observer_for {
+        if *observed != request.player1_id && *observed != request.player2_id {
+            return Err(ApiError::ValidationError {
+                message: format!("observer_for '{}' is not a participant", observed.0),
+            });
+        }
+    }
+
+    // Engine Logic: Pure function creates battle state
+    let battle_state = engine::create_battle(
+        battle_id.to_string(),
+        request.player1_id.clone(),
+        &request.player1_team,
+        request.player2_id.clone(),
+        &request.player2_team,
+        request.level_budget,
+        request.allow_illegal_moves,
+    )?;
+
+    // Database Save: Store the new battle under the next short number
+    let battle_number = db.next_battle_number().await
+        .map_err(ApiError::from_database)?;
+    let title = format!("{} vs {}", request.player1_id.0, request.player2_id.0);
+    let stored_battle = StoredBattle {
+        battle_id,
+        player1_id: request.player1_id.clone(),
+        player2_id: request.player2_id.clone(),
+        battle_state: battle_state.clone(),
+        turn_logs: Vec::new(), // Start with empty turn logs
+        initial_state: Some(battle_state),
+        setup: Some(BattleSetup {
+            title,
+            team_id: None,
+            opponent_id: None,
+            opponent_team_id: None,
+            opponent_level_cap: None,
+        }),
+        rules: BattleRules { switch_uses_turn: request.switch_uses_turn },
+        draw_reason: None,
+        resolution_pending: false,
+        paused: false,
+        battle_number: Some(battle_number),
+        match_id,
+        last_resolved_turn: None,
+        engine_schema_version: engine::ENGINE_SCHEMA_VERSION,
+        observer_tokens: request.observer_for.iter().cloned().map(ObserverToken::new).collect(),
+        battle_seed: request.battle_seed,
+        version: 0,
+        created_at: current_timestamp(),
+        last_updated: current_timestamp(),
+    };
+
+    db.create_battle(&stored_battle).await
+        .map_err(ApiError::from_database)?;
+
+    let observer_token = stored_battle.observer_tokens.first().map(|observer| observer.token.clone());
+    Ok((battle_id, battle_number, observer_token))
+}
+
+/// Score a finished match game and start the next one unless the series is decided
+/// Like move stats, a failure is logged rather than failing the already-saved action
+async fn advance_match(db: &dyn Db, stored_battle: &StoredBattle) {
+    let Some(match_id) = stored_battle.match_id else { return };
+    if !stored_battle.is_finished() {
+        return;
+    }
+    if let Err(e) = try_advance_match(db, match_id, stored_battle).await {
+        error!("Failed to advance match {} after battle {}: {}", match_id, stored_battle.battle_id, e);
+    }
+}
+
+async fn try_advance_match(db: &dyn Db, match_id: MatchId, stored_battle: &StoredBattle) -> Result<(), ApiError> {
+    let mut stored_match = db.get_match(match_id).await
+        .map_err(ApiError::from_database)?
+        .ok_or_else(|| ApiError::match_not_found(match_id))?;
+
+    // Only the current game moves the series on, so a game is never scored twice
+    if stored_match.finished || stored_match.battle_ids.last() != Some(&stored_battle.battle_id) {
+        return Ok(());
+    }
+
+    if let Some(winner) = engine::winner_index(&stored_battle.battle_state) {
+        stored_match.score[winner] += 1;
+    }
+
+    // A majority decides the series; after the last game the leader takes it, or it ends drawn
+    let wins_needed = stored_match.best_of / 2 + 1;
+    let games_played = stored_match.battle_ids.len() as u32;
+    let [player1_wins, player2_wins] = stored_match.score;
+    stored_match.winner_index = if player1_wins >= wins_needed {
+        Some(0)
+    } else if player2_wins >= wins_needed {
+        Some(1)
+    } else if games_played >= stored_match.best_of && player1_wins != player2_wins {
+        Some(if player1_wins > player2_wins { 0 } else { 1 })
+    } else {
+        None
+    };
+    stored_match.finished = stored_match.winner_index.is_some() || games_played >= stored_match.best_of;
+
+    if !stored_match.finished {
+        let (next_battle_id, _, _) = create_pvp_battle(db, &stored_match.config, Some(match_id)).await?;
+        stored_match.battle_ids.push(next_battle_id);
+    }
+
+    stored_match.last_updated = current_timestamp();
+    db.update_match(&stored_match).await
+        .map_err(ApiError::from_database)
+}
+
+/// Authorize the requester and apply the events request's turn and kind filters
+fn battle_events(stored_battle: &StoredBattle, request: &GetBattleEventsRequest) -> Result<GetBattleEventsResponse, ApiError> {
+    // Validate player authorization
+    let player_index = engine::validate_player_authorization(
+        &stored_battle.battle_state,
+        &request.player_id,
+    )?;
+
+    // Validate requested event kinds before filtering
+    if let Some(event_types) = &request.event_types {
+        if let Some(unknown) = event_types.iter().find(|t| !BattleEventDto::KINDS.contains(&t.as_str())) {
+            return Err(ApiError::bad_request(format!(
+                "Unknown event type '{}' (expected one of: {})",
+                unknown,
+                BattleEventDto::KINDS.join(", ")
+            )));
+        }
+    }
+
+    let paginated = request.offset.is_some() || request.limit.is_some();
+    if paginated && request.last_turns.is_some() {
+        return Err(ApiError::bad_request("last_turns cannot be combined with offset/limit"));
+    }
+
+    // Filter turn logs based on request
+    let missed_turns: Vec<TurnLog> = match request.since_turn {
+        // Only turns the client has not seen yet
+        Some(since_turn) => stored_battle.turn_logs.iter()
+            .filter(|log| log.turn_number > since_turn)
+            .cloned()
+            .collect(),
+        None => stored_battle.turn_logs.clone(),
+    };
+
+    let mut next_offset = None;
+    let mut turn_logs = if paginated {
+        // A window starting at `offset`; past the end is an empty last page
+        let start = request.offset.unwrap_or(0).min(missed_turns.len());
+        let end = match request.limit {
+            Some(limit) => start.saturating_add(limit).min(missed_turns.len()),
+            None => missed_turns.len(),
+        };
+        next_offset = (end < missed_turns.len()).then_some(end);
+        missed_turns[start..end].to_vec()
+    } else if let Some(last_turns) = request.last_turns {
+        // Get only the last X turns, clamped to the turns there are
+        let kept_turns = (last_turns as usize).min(missed_turns.len());
+        missed_turns[missed_turns.len() - kept_turns..].to_vec()
+    } else {
+        // Get all turn logs
+        missed_turns
+    };
+
+    // Show the requester their own private details, then keep only the requested
+    // event kinds, filtering both representations together so they stay index-aligned
+    for turn_log in turn_logs.iter_mut() {
+        reveal_private_events(turn_log, player_index);
+        filter_turn_log(turn_log, request.event_types.as_deref(), request.include_markers);
+    }
+
+    // Response: Return filtered turn logs
+    Ok(GetBattleEventsResponse {
+        battle_id: request.battle_id,
+        turn_logs,
+        total_turns: engine::current_turn(&stored_battle.battle_state),
+        current_turn: engine::current_turn(&stored_battle.battle_state),
+        turns_resolved: engine::turns_resolved(&stored_battle.battle_state),
+        last_resolved_turn: stored_battle.last_resolved_turn,
+        has_more: next_offset.is_some(),
+        next_offset,
+    })
+}
+
+/// The acting player's view of the turns their action resolved, logged from `first_new_log` on
+fn turn_outcome(stored_battle: &StoredBattle, player_id: &PlayerId, first_new_log: usize) -> Result<TurnOutcome, ApiError> {
+    let battle_state = &stored_battle.battle_state;
+    let player_index = engine::validate_player_authorization(battle_state, player_id)?;
+
+    let mut events = Vec::new();
+    let mut structured_events = Vec::new();
+    for turn_log in &stored_battle.turn_logs[first_new_log..] {
+        let mut turn_log = turn_log.clone();
+        reveal_private_events(&mut turn_log, player_index);
+        filter_turn_log(&mut turn_log, None, false);
+        events.extend(turn_log.events);
+        structured_events.extend(turn_log.structured_events);
+    }
+
+    let mut battle_view = engine::get_battle_state_for_player(battle_state, player_id, &stored_battle.rules)?;
+    battle_view.opponent_public_info.revealed_moves = engine::get_revealed_opponent_moves(
+        battle_state,
+        player_id,
+        &stored_battle.turn_logs,
+        true,
+    )?;
+    let mut new_state_summary = convert_battle_view(stored_battle.battle_id, battle_view);
+    new_state_summary.paused = stored_battle.paused;
+
+    let result = engine::is_battle_finished(battle_state).then(|| {
+        match engine::winner_index(battle_state) {
+            Some(winner) if winner == player_index => "win",
+            Some(_) => "loss",
+            None => "draw",
+        }.to_string()
+    });
+
+    Ok(TurnOutcome {
+        events,
+        structured_events,
+        new_state_summary,
+        your_valid_actions: engine::get_player_annotated_actions(battle_state, player_id, &stored_battle.rules)?,
+        game_state: battle_state.game_state,
+        result,
+    })
+}
+
+/// Overlay a player's private events onto the public log and drop everyone's private copies
+fn reveal_private_events(turn_log: &mut TurnLog, player_index: usize) {
+    let private_events = std::mem::take(&mut turn_log.private_events);
+
+    for private in private_events[player_index].iter().cloned() {
+        if private.index < turn_log.events.len() && private.index < turn_log.structured_events.len() {
+            turn_log.events[private.index] = private.event;
+            turn_log.structured_events[private.index] = private.structured_event;
+        }
+    }
+}
+
+/// Filter a turn log's events by kind, dropping pacing markers unless requested
+/// Both representations are filtered together so they stay index-aligned
+fn filter_turn_log(turn_log: &mut TurnLog, event_types: Option<&[String]>, include_markers: bool) {
+    if turn_log.structured_events.len() != turn_log.events.len() {
+        return; // Legacy log without structured events; nothing to filter on
+    }
+
+    let (events, structured_events) = turn_log.events.drain(..)
+        .zip(turn_log.structured_events.drain(..))
+        .filter(|(_, e)| include_markers || !e.is_marker())
+        .filter(|(_, e)| event_types.is_none_or(|types| types.iter().any(|t| t == e.kind())))
+        .unzip();
+    turn_log.events = events;
+    turn_log.structured_events = structured_events;
+}
+
+fn public_battle_summary(stored_battle: &StoredBattle) -> PublicBattleSummary {
+    let battle_state = &stored_battle.battle_state;
+
+    PublicBattleSummary {
+        battle_id: stored_battle.battle_id,
+        title: stored_battle.setup.as_ref().map(|setup| setup.title.clone()),
+        result: battle_state.game_state,
+        winner_name: engine::winner_index(battle_state)
+            .map(|index| engine::display_name(&battle_state.players[index].player_name)),
+        turn_count: engine::turns_resolved(battle_state),
+        finished_at: stored_battle.last_updated,
     }
 }
 
@@ -10,5 +10,8 @@ pub use handlers::BattleHandler;
 pub use types::*;
 pub use errors::ApiError;
 
+#[cfg(feature = "test-utils")]
+pub use database::InMemoryDb;
+
 #[cfg(test)]
 mod tests;
\ No newline at end of file
@@ -1,7 +1,11 @@
 use lambda_runtime::{run, service_fn, Error, LambdaEvent};
 use lambda_web::{is_running_on_lambda, LambdaError};
 use serde_json::Value;
-use tracing::info;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::OnceLock;
+use tokio::sync::Notify;
+use tracing::{error, info, warn};
+use tracing_subscriber::EnvFilter;
 
 mod api;
 mod database;
@@ -12,7 +16,87 @@ mod types;
 
 use api::router::create_router;
 
+/// Tracks in-flight invocations so the local run path can drain them before exiting
+struct InFlight {
+    count: AtomicUsize,
+    drained: Notify,
+    shutting_down: AtomicBool,
+}
+
+fn in_flight() -> &'static InFlight {
+    static IN_FLIGHT: OnceLock<InFlight> = OnceLock::new();
+    IN_FLIGHT.get_or_init(|| InFlight {
+        count: AtomicUsize::new(0),
+        drained: Notify::new(),
+        shutting_down: AtomicBool::new(false),
+    })
+}
+
+/// Held for the duration of one invocation; the last guard dropped wakes any waiter
+struct InFlightGuard;
+
+impl InFlightGuard {
+    /// None once shutdown has begun, so no new work starts while the rest drains
+    fn new() -> Option<Self> {
+        in_flight().count.fetch_add(1, Ordering::SeqCst);
+        let guard = InFlightGuard;
+        if in_flight().shutting_down.load(Ordering::SeqCst) {
+            return None; // Dropping the guard undoes the count
+        }
+        Some(guard)
+    }
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        if in_flight().count.fetch_sub(1, Ordering::SeqCst) == 1 {
+            in_flight().drained.notify_waiters();
+        }
+    }
+}
+
+async fn wait_for_in_flight() {
+    loop {
+        // Create the waiter before checking the count so a concurrent drop can't be missed
+        let drained = in_flight().drained.notified();
+        let remaining = in_flight().count.load(Ordering::SeqCst);
+        if remaining == 0 {
+            return;
+        }
+        info!("Waiting for {} in-flight request(s) to finish", remaining);
+        drained.await;
+    }
+}
+
+/// Startup self-check enabled by `VALIDATE_PREFABS`
+/// Logs every prefab team that fails validation and refuses to start if any is unusable
+fn validate_prefabs() -> Result<(), Error> {
+    let failures = engine::validate_prefab_teams();
+    if failures.is_empty() {
+        info!("All prefab teams validated");
+        return Ok(());
+    }
+
+    for failure in &failures {
+        if failure.critical {
+            error!("Prefab team {} cannot start a battle: {}", failure.team_id, failure.message);
+        } else {
+            warn!("Prefab team {} failed validation: {}", failure.team_id, failure.message);
+        }
+    }
+
+    let critical = failures.iter().filter(|f| f.critical).count();
+    if critical > 0 {
+        return Err(format!("{} prefab team(s) cannot start a battle", critical).into());
+    }
+    Ok(())
+}
+
 async fn function_handler(event: LambdaEvent<Value>) -> Result<Value, Error> {
+    let Some(_in_flight) = InFlightGuard::new() else {
+        return Err("Shutting down; not accepting new requests".into());
+    };
+
     info!("Received event: {}", serde_json::to_string_pretty(&event.payload)?);
     
     let router = create_router().await?;
@@ -23,14 +107,33 @@ async fn function_handler(event: LambdaEvent<Value>) -> Result<Value, Error> {
     Ok(response)
 }
 
+/// Initialize tracing from the environment
+/// `RUST_LOG` takes full filter directives and wins over `LOG_LEVEL` (a plain level); the default is info.
+/// `LOG_FORMAT=json` writes one JSON object per line for CloudWatch queries; otherwise logs stay plain text.
+fn init_tracing() {
+    let filter = EnvFilter::try_from_default_env()
+        .ok()
+        .or_else(|| std::env::var("LOG_LEVEL").ok().and_then(|level| EnvFilter::try_new(level).ok()))
+        .unwrap_or_else(|| EnvFilter::new("info"));
+
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_target(false)
+        .without_time();
+
+    match std::env::var("LOG_FORMAT").as_deref() {
+        Ok("json") => builder.json().flatten_event(true).init(),
+        _ => builder.init(),
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    // Initialize tracing
-    tracing_subscriber::fmt()
-        .with_max_level(tracing::Level::INFO)
-        .with_target(false)
-        .without_time()
-        .init();
+    init_tracing();
+
+    if std::env::var("VALIDATE_PREFABS").is_ok() {
+        validate_prefabs()?;
+    }
 
     if is_running_on_lambda() {
         // Running on AWS Lambda
@@ -38,7 +141,24 @@ async fn main() -> Result<(), Error> {
         run(service_fn(function_handler)).await
     } else {
         // Running locally for development/testing
+        // Finish in-flight battle updates on Ctrl+C so restarts don't leave half-applied state
         info!("Starting Pokemon Adventure API locally");
-        run(service_fn(function_handler)).await
+        let server = run(service_fn(function_handler));
+        tokio::pin!(server);
+        tokio::select! {
+            result = &mut server => result,
+            _ = tokio::signal::ctrl_c() => {
+                info!("Shutdown requested");
+                in_flight().shutting_down.store(true, Ordering::SeqCst);
+                // The in-flight handlers live inside the server future, so keep polling it while they drain
+                tokio::select! {
+                    result = &mut server => result,
+                    _ = wait_for_in_flight() => {
+                        info!("All in-flight requests finished, exiting");
+                        Ok(())
+                    }
+                }
+            }
+        }
     }
 }
\ No newline at end of file
@@ -1,62 +1,49 @@
 // This file contains shared helper code for all integration tests.
 // It will not be included in the final production binary.
 
-use anyhow::anyhow;
-use async_trait::async_trait;
-use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use pokemon_adventure::{moves::Move, species::Species};
 
-use crate::{
-    database::Db,
-    handlers::BattleHandler,
-    types::{BattleId, StoredBattle},
-    ApiError,
-};
-// --- MOCK DATABASE ---
-#[derive(Clone)]
-pub struct MockDb {
-    battles: Arc<Mutex<HashMap<BattleId, StoredBattle>>>,
-}
+use crate::{handlers::BattleHandler, ApiError, BattleId, CreateBattleRequest, PlayerId, TeamPokemon};
 
 pub fn create_test_handler() -> Result<BattleHandler, ApiError> {
-    let mock_db = MockDb::new();
-    // Call the simple `new` constructor, not the async one.
-    Ok(BattleHandler::new(Arc::new(mock_db)))
+    // The in-memory handler is shared with downstream crates via the `test-utils` feature.
+    Ok(BattleHandler::new_in_memory())
 }
 
-impl MockDb {
-    pub fn new() -> Self {
-        Self {
-            battles: Arc::new(Mutex::new(HashMap::new())),
-        }
+pub fn team_pokemon(species: Species, level: u8, moves: Vec<Move>) -> TeamPokemon {
+    TeamPokemon {
+        species,
+        level,
+        moves,
+        nickname: None,
+        item: None,
     }
 }
 
-#[async_trait]
-impl Db for MockDb {
-    async fn create_battle(&self, battle: &StoredBattle) -> Result<(), anyhow::Error> {
-        let mut battles = self.battles.lock().unwrap();
-        if battles.contains_key(&battle.battle_id) {
-            return Err(anyhow!("Battle already exists"));
-        }
-        battles.insert(battle.battle_id, battle.clone());
-        Ok(())
+/// Two-player battle request between "alice" (player 1) and "bob" (player 2)
+pub fn pvp_battle_request(player1_team: Vec<TeamPokemon>, player2_team: Vec<TeamPokemon>) -> CreateBattleRequest {
+    CreateBattleRequest {
+        player1_id: PlayerId("alice".to_string()),
+        player2_id: PlayerId("bob".to_string()),
+        player1_team,
+        player2_team,
+        switch_uses_turn: false,
+        level_budget: None,
+        observer_for: None,
+        battle_seed: None,
+        allow_illegal_moves: true, // Fixture teams pick moves for the scenario under test, not for legality
     }
+}
 
-    async fn get_battle(
-        &self,
-        battle_id: BattleId,
-    ) -> Result<Option<StoredBattle>, anyhow::Error> {
-        let battles = self.battles.lock().unwrap();
-        Ok(battles.get(&battle_id).cloned())
-    }
+/// The matchup most tests play: alice's level 25 Pikachu (Thunderbolt) against bob's level 25 Rattata (Tackle)
+pub fn default_pvp_request() -> CreateBattleRequest {
+    pvp_battle_request(
+        vec![team_pokemon(Species::Pikachu, 25, vec![Move::Thunderbolt])],
+        vec![team_pokemon(Species::Rattata, 25, vec![Move::Tackle])],
+    )
+}
 
-    async fn update_battle(&self, battle: &StoredBattle) -> Result<(), anyhow::Error> {
-        let mut battles = self.battles.lock().unwrap();
-        if !battles.contains_key(&battle.battle_id) {
-            return Err(anyhow!("Battle not found"));
-        }
-        battles.insert(battle.battle_id, battle.clone());
-        Ok(())
-    }
-}
\ No newline at end of file
+/// Create the default matchup battle and return its id
+pub async fn default_pvp_battle(handler: &BattleHandler) -> BattleId {
+    handler.create_battle(default_pvp_request()).await.unwrap().battle_id
+}
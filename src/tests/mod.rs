@@ -1,2 +1,10 @@
 mod test_battle_flow;
+mod test_battle_creation;
+mod test_database;
+mod test_battle_listing;
+mod test_pvp;
+mod test_router;
+mod test_serialization;
+mod test_turn_logs;
+mod test_validation;
 mod common;
\ No newline at end of file
@@ -0,0 +1,364 @@
+use pokemon_adventure::{moves::Move, player::PlayerAction, pokemon::PokemonType, species::Species};
+use std::sync::Arc;
+use crate::tests::common::{create_test_handler, default_pvp_battle, pvp_battle_request, team_pokemon};
+use crate::database::{Db, InMemoryDb};
+use crate::{
+    ApiError, BaseStatsInfo, BattleHandler, CreateMvpBattleRequest, DeleteBattleRequest, ForfeitRequest, GetBattleByNumberRequest, GetBattleSetupRequest,
+    GetBattleStateRequest, PlayerId, RematchRequest, TeamPreviewRequest,
+};
+
+
+#[tokio::test]
+async fn test_prefab_npc_with_level_cap() {
+    let handler = create_test_handler().unwrap();
+    let create_request = CreateMvpBattleRequest {
+        player_name: "Test Trainer".to_string(),
+        team_id: "venusaur_team".to_string(),
+        opponent_id: "gym_leader_medium".to_string(),
+        opponent_team_id: Some("blastoise_team".to_string()),
+        opponent_level_cap: Some(50),
+        switch_uses_turn: false,
+        grant_observer_token: false,
+        battle_seed: None,
+    };
+    let create_response = handler.create_mvp_battle(create_request).await.unwrap();
+
+    let opponent = &create_response.battle_state.opponent_info;
+    let active = opponent.active_pokemon.as_ref().unwrap();
+    assert_eq!(active.level, 50, "Level 60 prefab Pokemon should be scaled down to the cap");
+    assert_eq!(active.current_hp, active.max_hp, "Scaled Pokemon should start at full HP");
+}
+
+#[tokio::test]
+async fn test_create_response_includes_first_turn_actions() {
+    let handler = create_test_handler().unwrap();
+    let create_request = CreateMvpBattleRequest {
+        player_name: "Test Trainer".to_string(),
+        team_id: "venusaur_team".to_string(),
+        opponent_id: "gym_leader_easy".to_string(),
+        opponent_team_id: None,
+        opponent_level_cap: None,
+        switch_uses_turn: false,
+        grant_observer_token: false,
+        battle_seed: None,
+    };
+    let create_response = handler.create_mvp_battle(create_request).await.unwrap();
+
+    let actions = &create_response.valid_actions;
+    assert!(actions.iter().any(|a| matches!(a.action, PlayerAction::UseMove { move_index: 1 })
+        && a.description.starts_with("Use SolarBeam")));
+    assert!(actions.iter().any(|a| matches!(a.action, PlayerAction::SwitchPokemon { .. })));
+}
+
+#[tokio::test]
+async fn test_unknown_npc_prefab_is_rejected() {
+    let handler = create_test_handler().unwrap();
+    let create_request = CreateMvpBattleRequest {
+        player_name: "Test Trainer".to_string(),
+        team_id: "venusaur_team".to_string(),
+        opponent_id: "gym_leader_medium".to_string(),
+        opponent_team_id: Some("missingno_team".to_string()),
+        opponent_level_cap: None,
+        switch_uses_turn: false,
+        grant_observer_token: false,
+        battle_seed: None,
+    };
+    let result = handler.create_mvp_battle(create_request).await;
+    assert!(matches!(result, Err(ApiError::ValidationError { .. })));
+}
+
+#[tokio::test]
+async fn test_battle_setup_is_recorded() {
+    let handler = create_test_handler().unwrap();
+    let create_request = CreateMvpBattleRequest {
+        player_name: "Test Trainer".to_string(),
+        team_id: "venusaur_team".to_string(),
+        opponent_id: "gym_leader_easy".to_string(),
+        opponent_team_id: None,
+        opponent_level_cap: None,
+        switch_uses_turn: false,
+        grant_observer_token: false,
+        battle_seed: None,
+    };
+    let battle_id = handler.create_mvp_battle(create_request).await.unwrap().battle_id;
+
+    let setup_response = handler.get_battle_setup(GetBattleSetupRequest {
+        battle_id,
+        player_id: PlayerId("player_1".to_string()),
+    }).await.unwrap();
+    assert_eq!(setup_response.setup.title, "Venusaur Team vs Gym Leader Brock");
+    assert_eq!(setup_response.setup.team_id.as_deref(), Some("venusaur_team"));
+
+    let outsider = handler.get_battle_setup(GetBattleSetupRequest {
+        battle_id,
+        player_id: PlayerId("bob".to_string()),
+    }).await;
+    assert!(matches!(outsider, Err(ApiError::PlayerNotInBattle { .. })), "In-progress setup is participants-only");
+}
+
+#[tokio::test]
+async fn test_learnset_lists_each_move_once_in_level_order() {
+    let handler = create_test_handler().unwrap();
+    let response = handler.get_learnable_moves(Species::Pikachu).await.unwrap();
+
+    assert_eq!(response.species, Species::Pikachu);
+    assert!(!response.moves.is_empty(), "Pikachu should have learnable moves");
+
+    for (i, learnable) in response.moves.iter().enumerate() {
+        assert!(
+            !response.moves[..i].iter().any(|earlier| earlier.move_ == learnable.move_),
+            "{:?} should only be listed once", learnable.move_
+        );
+        assert_eq!(learnable.level.is_some(), learnable.method == "level_up");
+    }
+
+    let levels: Vec<u8> = response.moves.iter().filter_map(|m| m.level).collect();
+    assert!(levels.windows(2).all(|pair| pair[0] <= pair[1]), "Level-up moves should be in level order");
+}
+
+#[tokio::test]
+async fn test_battle_from_incompatible_engine_version_is_rejected_on_load() {
+    let db = Arc::new(InMemoryDb::new());
+    let handler = BattleHandler::new(db.clone());
+    let battle_id = handler.create_mvp_battle(CreateMvpBattleRequest {
+        player_name: "Test Trainer".to_string(),
+        team_id: "venusaur_team".to_string(),
+        opponent_id: "gym_leader_easy".to_string(),
+        opponent_team_id: None,
+        opponent_level_cap: None,
+        switch_uses_turn: false,
+        grant_observer_token: false,
+        battle_seed: None,
+    }).await.unwrap().battle_id;
+
+    // Simulate a battle stored under a different engine schema version
+    let mut stored = db.get_battle(battle_id).await.unwrap().unwrap();
+    stored.engine_schema_version = crate::engine::ENGINE_SCHEMA_VERSION + 1;
+    db.update_battle(&stored).await.unwrap();
+
+    let error = handler.get_battle_state(GetBattleStateRequest {
+        battle_id,
+        player_id: PlayerId("player_1".to_string()),
+        strict_info: false,
+        debug: false,
+        is_admin: false,
+        observer_token: None,
+    }).await.unwrap_err();
+    assert!(matches!(error, ApiError::CorruptedBattle { .. }));
+    assert!(error.to_string().contains(&format!(
+        "battle created with engine v{}, current is v{}",
+        crate::engine::ENGINE_SCHEMA_VERSION + 1,
+        crate::engine::ENGINE_SCHEMA_VERSION
+    )));
+}
+
+#[tokio::test]
+async fn test_effectiveness_lookup_outside_battle() {
+    let handler = create_test_handler().unwrap();
+
+    let fire_vs_venusaur = handler.get_effectiveness(PokemonType::Fire, Species::Venusaur).await.unwrap();
+    assert_eq!(fire_vs_venusaur.multiplier, 2.0, "Fire is super effective against Grass/Poison");
+    assert!(fire_vs_venusaur.defender_types.contains(&PokemonType::Grass));
+
+    let electric_vs_geodude = handler.get_effectiveness(PokemonType::Electric, Species::Geodude).await.unwrap();
+    assert_eq!(electric_vs_geodude.multiplier, 0.0, "Ground types are immune to Electric");
+}
+
+#[tokio::test]
+async fn test_delete_battle_requires_an_existing_battle_and_a_participant() {
+    let handler = create_test_handler().unwrap();
+    let battle_id = handler.create_mvp_battle(CreateMvpBattleRequest {
+        player_name: "Test Trainer".to_string(),
+        team_id: "venusaur_team".to_string(),
+        opponent_id: "gym_leader_easy".to_string(),
+        opponent_team_id: None,
+        opponent_level_cap: None,
+        switch_uses_turn: false,
+        grant_observer_token: false,
+        battle_seed: None,
+    }).await.unwrap().battle_id;
+
+    let delete = |player: &str| handler.delete_battle(DeleteBattleRequest {
+        battle_id,
+        player_id: PlayerId(player.to_string()),
+        authenticated_player_id: None,
+    });
+
+    assert!(matches!(delete("mallory").await, Err(ApiError::PlayerNotInBattle { .. })), "Only participants may delete");
+    delete("player_1").await.unwrap();
+    assert!(matches!(delete("player_1").await, Err(ApiError::BattleNotFound { .. })), "A second delete finds nothing");
+
+    let state = handler.get_battle_state(GetBattleStateRequest {
+        battle_id,
+        player_id: PlayerId("player_1".to_string()),
+        strict_info: false,
+        debug: false,
+        is_admin: false,
+        observer_token: None,
+    }).await;
+    assert!(matches!(state, Err(ApiError::BattleNotFound { .. })));
+}
+
+#[tokio::test]
+async fn test_battles_get_sequential_numbers_usable_for_lookup() {
+    let handler = create_test_handler().unwrap();
+    let mut created = Vec::new();
+    for _ in 0..2 {
+        created.push(handler.create_mvp_battle(CreateMvpBattleRequest {
+            player_name: "Test Trainer".to_string(),
+            team_id: "venusaur_team".to_string(),
+            opponent_id: "gym_leader_easy".to_string(),
+            opponent_team_id: None,
+            opponent_level_cap: None,
+            switch_uses_turn: false,
+            grant_observer_token: false,
+            battle_seed: None,
+        }).await.unwrap());
+    }
+    assert_eq!(created[1].battle_number, created[0].battle_number + 1);
+
+    let lookup = |battle_number: u64, player: Option<&str>, is_admin: bool| handler.get_battle_by_number(GetBattleByNumberRequest {
+        battle_number,
+        authenticated_player_id: player.map(|id| PlayerId(id.to_string())),
+        is_admin,
+    });
+
+    let found = lookup(created[1].battle_number, Some("player_1"), false).await.unwrap();
+    assert_eq!(found.battle_id, created[1].battle_id);
+    let as_admin = lookup(created[1].battle_number, None, true).await.unwrap();
+    assert_eq!(as_admin.battle_id, created[1].battle_id);
+
+    // Numbers are sequential, so anyone else guessing one must not learn the battle id
+    assert!(matches!(lookup(created[1].battle_number, None, false).await, Err(ApiError::AuthRequired)));
+    assert!(matches!(
+        lookup(created[1].battle_number, Some("someone_else"), false).await,
+        Err(ApiError::PlayerNotInBattle { .. })
+    ));
+
+    let missing = lookup(created[1].battle_number + 100, Some("player_1"), false).await;
+    assert!(matches!(missing, Err(ApiError::BattleNumberNotFound { .. })));
+}
+
+#[test]
+fn test_all_prefab_teams_validate() {
+    let failures = crate::engine::validate_prefab_teams();
+    assert!(failures.is_empty(), "Prefab teams failed validation: {:?}", failures);
+}
+
+#[tokio::test]
+async fn test_team_preview_matches_battle_stats() {
+    let handler = create_test_handler().unwrap();
+    let team = || vec![
+        team_pokemon(Species::Pikachu, 25, vec![Move::Thunderbolt, Move::QuickAttack]),
+        team_pokemon(Species::Rattata, 30, vec![Move::Tackle]),
+    ];
+
+    let preview = handler.preview_team(TeamPreviewRequest { team: team() }).await.unwrap();
+    assert_eq!(preview.pokemon.len(), 2);
+    assert_eq!(preview.pokemon[1].level, 30);
+    assert_eq!(preview.pokemon[0].moves.iter().flatten().count(), 2);
+
+    let battle_id = handler.create_battle(pvp_battle_request(
+        team(),
+        vec![team_pokemon(Species::Rattata, 25, vec![Move::Tackle])],
+    )).await.unwrap().battle_id;
+    let state = handler.get_battle_state(GetBattleStateRequest {
+        battle_id,
+        player_id: PlayerId("alice".to_string()),
+        strict_info: false,
+        debug: false,
+        is_admin: false,
+        observer_token: None,
+    }).await.unwrap();
+
+    let active = state.player_team.active_pokemon.unwrap();
+    let previewed = &preview.pokemon[0];
+    assert_eq!(previewed.max_hp, active.max_hp);
+    assert_eq!(
+        (previewed.attack, previewed.defense, previewed.sp_attack, previewed.sp_defense, previewed.speed),
+        (active.attack, active.defense, active.sp_attack, active.sp_defense, active.speed),
+        "Preview should use the same instantiation as battle creation"
+    );
+
+    let invalid = handler.preview_team(TeamPreviewRequest { team: Vec::new() }).await;
+    assert!(matches!(invalid, Err(ApiError::ValidationError { .. })));
+}
+
+#[tokio::test]
+async fn test_pokedex_lookups() {
+    let handler = create_test_handler().unwrap();
+
+    let venusaur = handler.get_species_info(Species::Venusaur).await.unwrap();
+    assert_eq!(venusaur.types, vec![PokemonType::Grass, PokemonType::Poison]);
+    assert_eq!(venusaur.base_stats, BaseStatsInfo {
+        hp: 80, attack: 82, defense: 83, sp_attack: 100, sp_defense: 100, speed: 80,
+    });
+
+    let tackle = handler.get_move_info(Move::Tackle).await.unwrap();
+    assert_eq!(tackle.max_pp, 35);
+    assert_eq!(tackle.move_type, PokemonType::Normal);
+    assert_eq!(tackle.priority, 0);
+}
+
+#[tokio::test]
+async fn test_rematch_reuses_a_finished_battles_setup() {
+    let db = Arc::new(InMemoryDb::new());
+    let handler = BattleHandler::new(db.clone());
+    let battle_id = handler.create_mvp_battle(CreateMvpBattleRequest {
+        player_name: "Test Trainer".to_string(),
+        team_id: "venusaur_team".to_string(),
+        opponent_id: "gym_leader_easy".to_string(),
+        opponent_team_id: None,
+        opponent_level_cap: Some(30),
+        switch_uses_turn: true,
+        grant_observer_token: false,
+        battle_seed: Some(42),
+    }).await.unwrap().battle_id;
+    let rematch = |battle_id| handler.rematch(RematchRequest {
+        battle_id,
+        player_id: PlayerId("player_1".to_string()),
+        authenticated_player_id: None,
+    });
+
+    let error = rematch(battle_id).await.unwrap_err();
+    assert!(matches!(error, ApiError::BattleInProgress { .. }));
+    assert_eq!(error.status_code(), 409, "Battles still in progress can't be rematched");
+
+    handler.forfeit(ForfeitRequest {
+        battle_id,
+        player_id: PlayerId("player_1".to_string()),
+        authenticated_player_id: None,
+    }).await.unwrap();
+
+    let response = rematch(battle_id).await.unwrap();
+    assert_ne!(response.battle_id, battle_id, "A rematch is a brand-new battle");
+    assert_eq!(response.battle_state.turn_number, 1);
+    assert!(response.battle_state.can_act);
+    assert!(!response.valid_actions.is_empty());
+
+    let setup = handler.get_battle_setup(GetBattleSetupRequest {
+        battle_id: response.battle_id,
+        player_id: PlayerId("player_1".to_string()),
+    }).await.unwrap().setup;
+    assert_eq!(setup.team_id.as_deref(), Some("venusaur_team"));
+    assert_eq!(setup.opponent_id.as_deref(), Some("gym_leader_easy"));
+    assert_eq!(setup.opponent_level_cap, Some(30));
+    let stored = db.get_battle(response.battle_id).await.unwrap().unwrap();
+    assert!(stored.rules.switch_uses_turn, "The rules carry over too");
+    let seed = stored.battle_seed.expect("A seeded battle's rematch is seeded, so it can be replayed");
+    assert_ne!(seed, 42, "The rematch draws a fresh seed rather than replaying the original's luck");
+    assert_eq!(stored.battle_state.players[0].player_name, "Test Trainer");
+
+    let pvp_battle = default_pvp_battle(&handler).await;
+    handler.forfeit(ForfeitRequest {
+        battle_id: pvp_battle,
+        player_id: PlayerId("alice".to_string()),
+        authenticated_player_id: None,
+    }).await.unwrap();
+    let error = handler.rematch(RematchRequest {
+        battle_id: pvp_battle,
+        player_id: PlayerId("alice".to_string()),
+        authenticated_player_id: None,
+    }).await.unwrap_err();
+    assert!(matches!(error, ApiError::ValidationError { .. }), "Two-player battles have no NPC setup to reuse");
+}
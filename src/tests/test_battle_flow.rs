@@ -1,6 +1,11 @@
-use pokemon_adventure::player::PlayerAction;
-use crate::tests::common::create_test_handler;
-use crate::{ApiError, BattleHandler, CreateMvpBattleRequest, GetBattleEventsRequest, GetBattleStateRequest, PlayerId, SubmitActionRequest};
+use pokemon_adventure::{moves::Move, player::PlayerAction, species::Species};
+use std::sync::Arc;
+use crate::tests::common::{create_test_handler, default_pvp_battle, default_pvp_request, pvp_battle_request, team_pokemon};
+use crate::database::{Db, InMemoryDb};
+use crate::{
+    ApiError, BattleHandler, CreateMvpBattleRequest, BATTLE_TTL_SECONDS, ForfeitRequest, GetBattleByNumberRequest, GetBattleEventsRequest,
+    GetBattleStateRequest, GetTickStatusRequest, GetValidActionsRequest, PlayerHistoryRequest, PlayerId, QuickplayRequest, SubmitActionRequest,
+};
 
 
 #[tokio::test]
@@ -13,6 +18,11 @@ async fn test_solar_beam_two_turn_flow() {
         player_name: "Test Trainer".to_string(),
         team_id: "venusaur_team".to_string(),
         opponent_id: "gym_leader_medium".to_string(),
+        opponent_team_id: None,
+        opponent_level_cap: None,
+        switch_uses_turn: false,
+        grant_observer_token: false,
+        battle_seed: None,
     };
     let create_response = handler.create_mvp_battle(create_request).await.unwrap();
     let battle_id = create_response.battle_id;
@@ -25,15 +35,24 @@ async fn test_solar_beam_two_turn_flow() {
         battle_id,
         player_id: player_id.clone(),
         action: PlayerAction::UseMove { move_index: 1 }, // Solar Beam
+        async_resolution: false,
+        authenticated_player_id: None,
     };
     let action_response = handler.submit_action(action_request).await.unwrap();
     assert!(action_response.success, "Action submission should succeed");
+    assert_eq!(action_response.turn_number, 2, "Response should report the advanced turn");
+    assert!(action_response.can_act, "Response should report that the player can act again");
 
     // 3. Verify Turn 1 State (Charging)
     let events_request = GetBattleEventsRequest {
         battle_id,
         player_id: player_id.clone(),
         last_turns: Some(1),
+        since_turn: None,
+        event_types: None,
+        include_markers: false,
+        offset: None,
+        limit: None,
     };
     let events_response = handler.get_battle_events(events_request).await.unwrap();
     let turn_1_events = &events_response.turn_logs[0].events;
@@ -50,11 +69,16 @@ async fn test_solar_beam_two_turn_flow() {
         "Solar Beam should not deal damage on the first turn"
     );
     
-    let state_request = GetBattleStateRequest { battle_id, player_id: player_id.clone() };
+    let state_request = GetBattleStateRequest { battle_id, player_id: player_id.clone(), strict_info: false, debug: false, is_admin: false, observer_token: None };
     let state_response = handler.get_battle_state(state_request.clone()).await.unwrap();
     assert_eq!(state_response.turn_number, 2, "Should have advanced to turn 2");
     assert_eq!(state_response.game_state, pokemon_adventure::battle::state::GameState::WaitingForActions, "Should be waiting for actions for next turn");
     assert!(state_response.can_act, "Player should be able to act on turn 2, as the forced move is handled by the engine.");
+    assert_eq!(
+        state_response.player_team.active_pokemon.as_ref().unwrap().charging_move,
+        Some(pokemon_adventure::moves::Move::SolarBeam),
+        "State should expose which move Venusaur is charging"
+    );
 
     // 4. Turn 2: Player's action is submitted, but the engine should force Solar Beam
     println!("\nTurn 2: Submitting a placeholder action to trigger turn resolution...");
@@ -62,6 +86,8 @@ async fn test_solar_beam_two_turn_flow() {
         battle_id,
         player_id: player_id.clone(),
         action: PlayerAction::UseMove { move_index: 0 }, // Engine will override this with Solar Beam
+        async_resolution: false,
+        authenticated_player_id: None,
     };
     handler.submit_action(placeholder_action).await.unwrap();
 
@@ -70,6 +96,11 @@ async fn test_solar_beam_two_turn_flow() {
         battle_id,
         player_id: player_id.clone(),
         last_turns: Some(1),
+        since_turn: None,
+        event_types: None,
+        include_markers: false,
+        offset: None,
+        limit: None,
     };
     let events_response_2 = handler.get_battle_events(events_request_2).await.unwrap();
     let turn_2_events = &events_response_2.turn_logs[0].events;
@@ -89,6 +120,7 @@ async fn test_solar_beam_two_turn_flow() {
     let final_state = handler.get_battle_state(state_request).await.unwrap();
     assert_eq!(final_state.turn_number, 3, "Should now be turn 3");
     assert!(final_state.can_act, "Player should be able to act again after Solar Beam executes");
+    assert_eq!(final_state.player_team.active_pokemon.as_ref().unwrap().charging_move, None);
     println!("\n✅ test_solar_beam_two_turn_flow PASSED");
 }
 
@@ -103,6 +135,11 @@ async fn test_fainting_and_npc_replacement_flow() {
         player_name: "Test Trainer".to_string(),
         team_id: "charizard_team".to_string(), // A strong offensive team
         opponent_id: "gym_leader_easy".to_string(),
+        opponent_team_id: None,
+        opponent_level_cap: None,
+        switch_uses_turn: false,
+        grant_observer_token: false,
+        battle_seed: None,
     };
     let create_response = handler.create_mvp_battle(create_request).await.unwrap();
     let battle_id = create_response.battle_id;
@@ -117,10 +154,12 @@ async fn test_fainting_and_npc_replacement_flow() {
             battle_id,
             player_id: player_id.clone(),
             action: PlayerAction::UseMove { move_index: 0 },
+            async_resolution: false,
+            authenticated_player_id: None,
         };
         handler.submit_action(action_request).await.unwrap();
 
-        let state_request = GetBattleStateRequest { battle_id, player_id: player_id.clone() };
+        let state_request = GetBattleStateRequest { battle_id, player_id: player_id.clone(), strict_info: false, debug: false, is_admin: false, observer_token: None };
         let state_response = handler.get_battle_state(state_request).await.unwrap();
         
         let opponent_hp = state_response.opponent_info.active_pokemon.as_ref().unwrap().current_hp;
@@ -131,7 +170,7 @@ async fn test_fainting_and_npc_replacement_flow() {
         }
 
         if opponent_hp == 0 {
-             let events_request = GetBattleEventsRequest { battle_id, player_id: player_id.clone(), last_turns: Some(1) };
+             let events_request = GetBattleEventsRequest { battle_id, player_id: player_id.clone(), last_turns: Some(1), since_turn: None, event_types: None, include_markers: false, offset: None, limit: None };
              let events_response = handler.get_battle_events(events_request).await.unwrap();
              let last_turn_events = &events_response.turn_logs.last().unwrap().events;
              
@@ -157,4 +196,449 @@ async fn test_fainting_and_npc_replacement_flow() {
             panic!("Test failed: No faint occurred after 10 turns.");
         }
     }
-}
\ No newline at end of file
+}
+#[tokio::test]
+async fn test_async_resolution_is_polled_via_tick() {
+    let handler = create_test_handler().unwrap();
+    let create_response = handler.create_mvp_battle(CreateMvpBattleRequest {
+        player_name: "Test Trainer".to_string(),
+        team_id: "venusaur_team".to_string(),
+        opponent_id: "gym_leader_medium".to_string(),
+        opponent_team_id: None,
+        opponent_level_cap: None,
+        switch_uses_turn: false,
+        grant_observer_token: false,
+        battle_seed: None,
+    }).await.unwrap();
+    let battle_id = create_response.battle_id;
+    let player_id = PlayerId("player_1".to_string());
+
+    let queued = handler.submit_action(SubmitActionRequest {
+        battle_id,
+        player_id: player_id.clone(),
+        action: PlayerAction::UseMove { move_index: 0 },
+        async_resolution: true,
+        authenticated_player_id: None,
+    }).await.unwrap();
+    assert!(queued.success);
+    assert!(!queued.battle_updated, "Async submission should respond before the turn resolves");
+    assert!(!queued.can_act);
+
+    // The first poll resolves the queued turn
+    let status = handler.get_tick_status(GetTickStatusRequest {
+        battle_id,
+        player_id: player_id.clone(),
+    }).await.unwrap();
+    assert!(!status.processing);
+    assert_eq!(status.turn_number, 2, "The queued turn should have been resolved");
+    assert!(status.can_act);
+}
+
+#[tokio::test]
+async fn test_failed_async_resolution_save_is_retried_by_the_next_tick() {
+    let db = Arc::new(InMemoryDb::new());
+    let handler = BattleHandler::new(db.clone());
+    let battle_id = handler.create_battle(pvp_battle_request(
+        vec![team_pokemon(Species::Snorlax, 50, vec![Move::Tackle])],
+        vec![team_pokemon(Species::Snorlax, 50, vec![Move::Tackle])],
+    )).await.unwrap().battle_id;
+    let submit = |player: &str, async_resolution: bool| handler.submit_action(SubmitActionRequest {
+        battle_id,
+        player_id: PlayerId(player.to_string()),
+        action: PlayerAction::UseMove { move_index: 0 },
+        async_resolution,
+        authenticated_player_id: None,
+    });
+    let tick = || handler.get_tick_status(GetTickStatusRequest {
+        battle_id,
+        player_id: PlayerId("alice".to_string()),
+    });
+    submit("alice", false).await.unwrap();
+    submit("bob", true).await.unwrap();
+
+    // The resolving save loses a version race, so nothing is recorded and the turn stays pending
+    db.simulate_concurrent_writes(1);
+    let first = tick().await.unwrap();
+    assert!(first.processing, "The lost save leaves the turn pending rather than half-applied");
+    assert_eq!(first.turns_resolved, 0);
+    assert!(db.get_battle(battle_id).await.unwrap().unwrap().resolution_pending);
+
+    let second = tick().await.unwrap();
+    assert!(!second.processing, "The next poll resolves the pending turn itself");
+    assert_eq!(second.turns_resolved, 1);
+    let stored = db.get_battle(battle_id).await.unwrap().unwrap();
+    assert!(!stored.resolution_pending);
+    assert_eq!(stored.turn_logs.len(), 1);
+}
+
+#[tokio::test]
+async fn test_turn_numbering_is_consistent_across_endpoints() {
+    let handler = create_test_handler().unwrap();
+    let battle_id = handler.create_battle(pvp_battle_request(
+        vec![team_pokemon(Species::Snorlax, 50, vec![Move::Tackle])],
+        vec![team_pokemon(Species::Snorlax, 50, vec![Move::Tackle])],
+    )).await.unwrap().battle_id;
+    let alice = PlayerId("alice".to_string());
+
+    for resolved in 1..=3u32 {
+        handler.submit_action(SubmitActionRequest {
+            battle_id,
+            player_id: alice.clone(),
+            action: PlayerAction::UseMove { move_index: 0 },
+            async_resolution: false,
+            authenticated_player_id: None,
+        }).await.unwrap();
+        let response = handler.submit_action(SubmitActionRequest {
+            battle_id,
+            player_id: PlayerId("bob".to_string()),
+            action: PlayerAction::UseMove { move_index: 0 },
+            async_resolution: false,
+            authenticated_player_id: None,
+        }).await.unwrap();
+        assert_eq!(response.turn_number, resolved + 1, "Submit response reports the turn now awaiting actions");
+        assert_eq!(response.turns_resolved, resolved);
+
+        let state = handler.get_battle_state(GetBattleStateRequest {
+            battle_id,
+            player_id: alice.clone(),
+            strict_info: false,
+            debug: false,
+            is_admin: false,
+            observer_token: None,
+        }).await.unwrap();
+        assert_eq!(state.turn_number, resolved + 1);
+        assert_eq!(state.turns_resolved, resolved);
+
+        let events = handler.get_battle_events(GetBattleEventsRequest {
+            battle_id,
+            player_id: alice.clone(),
+            last_turns: None,
+            since_turn: None,
+            event_types: None,
+            include_markers: false,
+            offset: None,
+            limit: None,
+        }).await.unwrap();
+        assert_eq!(events.current_turn, state.turn_number);
+        assert_eq!(events.total_turns, events.current_turn);
+        assert_eq!(events.turns_resolved, resolved);
+        assert_eq!(events.last_resolved_turn, Some(resolved), "The newest log is labelled with the turn it resolved");
+        let labels: Vec<u32> = events.turn_logs.iter().map(|log| log.turn_number).collect();
+        assert_eq!(labels, (1..=resolved).collect::<Vec<_>>());
+
+        let status = handler.get_tick_status(GetTickStatusRequest {
+            battle_id,
+            player_id: alice.clone(),
+        }).await.unwrap();
+        assert_eq!(status.turn_number, state.turn_number);
+        assert_eq!(status.turns_resolved, resolved);
+    }
+}
+
+#[tokio::test]
+async fn test_random_battle_replays_to_identical_state() {
+    let db = Arc::new(InMemoryDb::new());
+    let handler = BattleHandler::new(db.clone());
+    let battle_id = handler.create_mvp_battle(CreateMvpBattleRequest {
+        player_name: "Test Trainer".to_string(),
+        team_id: "venusaur_team".to_string(),
+        opponent_id: "gym_leader_medium".to_string(),
+        opponent_team_id: None,
+        opponent_level_cap: None,
+        switch_uses_turn: false,
+        grant_observer_token: false,
+        battle_seed: None,
+    }).await.unwrap().battle_id;
+    let player_id = PlayerId("player_1".to_string());
+
+    // Play random legal actions (never forfeiting) until the battle ends
+    for _ in 0..500 {
+        let stored = db.get_battle(battle_id).await.unwrap().unwrap();
+        if stored.is_finished() {
+            break;
+        }
+        let valid_actions: Vec<PlayerAction> = handler.get_valid_actions(GetValidActionsRequest {
+            battle_id,
+            player_id: player_id.clone(),
+        }).await.unwrap().valid_actions.into_iter()
+            .filter(|action| !matches!(action, PlayerAction::Forfeit))
+            .collect();
+        let action = valid_actions[rand::random_range(0..valid_actions.len())].clone();
+        handler.submit_action(SubmitActionRequest {
+            battle_id,
+            player_id: player_id.clone(),
+            action,
+            async_resolution: false,
+            authenticated_player_id: None,
+        }).await.unwrap();
+    }
+
+    let stored = db.get_battle(battle_id).await.unwrap().unwrap();
+    assert!(stored.is_finished(), "Random battle should finish within the action limit");
+    assert!(stored.turn_logs.iter().all(|log| !log.resolutions.is_empty()), "Every turn log should record its resolutions");
+
+    let replayed = crate::engine::replay_from_actions(stored.initial_state.as_ref().unwrap(), &stored.turn_logs);
+    assert_eq!(
+        serde_json::to_string(&replayed).unwrap(),
+        serde_json::to_string(&stored.battle_state).unwrap(),
+        "Replaying the recorded seeds and actions should reproduce the battle exactly"
+    );
+}
+
+#[tokio::test]
+async fn test_failed_tick_leaves_stored_battle_unchanged() {
+    let db = Arc::new(InMemoryDb::new());
+    let handler = BattleHandler::new(db.clone());
+    let battle_id = handler.create_mvp_battle(CreateMvpBattleRequest {
+        player_name: "Test Trainer".to_string(),
+        team_id: "venusaur_team".to_string(),
+        opponent_id: "gym_leader_easy".to_string(),
+        opponent_team_id: None,
+        opponent_level_cap: None,
+        switch_uses_turn: false,
+        grant_observer_token: false,
+        battle_seed: None,
+    }).await.unwrap().battle_id;
+    let before = db.get_battle(battle_id).await.unwrap().unwrap();
+    let action = |async_resolution| SubmitActionRequest {
+        battle_id,
+        player_id: PlayerId("player_1".to_string()),
+        action: PlayerAction::UseMove { move_index: 0 },
+        async_resolution,
+        authenticated_player_id: None,
+    };
+    let assert_unchanged = |after: &crate::StoredBattle| {
+        assert_eq!(
+            serde_json::to_string(&after.battle_state).unwrap(),
+            serde_json::to_string(&before.battle_state).unwrap(),
+            "Neither the queued action nor the NPC's choice should be persisted"
+        );
+        assert_eq!(after.turn_logs.len(), before.turn_logs.len());
+        assert!(!after.resolution_pending);
+    };
+
+    // Synchronous: the error surfaces and nothing is saved
+    crate::engine::FAIL_NEXT_TICK.with(|fail| fail.set(true));
+    assert!(handler.submit_action(action(false)).await.is_err());
+    assert_unchanged(&db.get_battle(battle_id).await.unwrap().unwrap());
+
+    // Asynchronous: the queued action is saved, then rolled back when resolution fails
+    crate::engine::FAIL_NEXT_TICK.with(|fail| fail.set(true));
+    handler.submit_action(action(true)).await.unwrap();
+    let mut after = db.get_battle(battle_id).await.unwrap().unwrap();
+    for _ in 0..100 {
+        if !after.resolution_pending {
+            break;
+        }
+        tokio::task::yield_now().await;
+        after = db.get_battle(battle_id).await.unwrap().unwrap();
+    }
+    assert_unchanged(&after);
+
+    // The battle is still playable afterwards
+    assert!(handler.submit_action(action(false)).await.unwrap().success);
+}
+
+#[tokio::test]
+async fn test_quickplay_saves_battle_with_first_turn() {
+    let db = Arc::new(InMemoryDb::new());
+    let handler = BattleHandler::new(db.clone());
+    let quickplay = |move_index| handler.quickplay(QuickplayRequest {
+        battle: CreateMvpBattleRequest {
+            player_name: "Test Trainer".to_string(),
+            team_id: "venusaur_team".to_string(),
+            opponent_id: "gym_leader_easy".to_string(),
+            opponent_team_id: None,
+            opponent_level_cap: None,
+            switch_uses_turn: false,
+            grant_observer_token: false,
+            battle_seed: None,
+        },
+        action: PlayerAction::UseMove { move_index },
+    });
+
+    let response = quickplay(0).await.unwrap();
+    assert_eq!(response.battle_state.turns_resolved, 1, "The first action resolved before the response");
+    assert!(!response.valid_actions.is_empty());
+
+    let stored = db.get_battle(response.battle_id).await.unwrap().unwrap();
+    assert_eq!(stored.turn_logs.len(), 1);
+    assert_eq!(stored.last_resolved_turn, Some(1));
+
+    assert!(matches!(quickplay(7).await.unwrap_err(), ApiError::InvalidAction { .. }));
+    let player_battles = db.list_player_battles(&PlayerId("player_1".to_string()), 10).await.unwrap();
+    assert_eq!(player_battles, vec![response.battle_id], "A rejected first action saves no battle");
+
+    let next = quickplay(0).await.unwrap();
+    let next_stored = db.get_battle(next.battle_id).await.unwrap().unwrap();
+    assert_eq!(
+        next_stored.battle_number,
+        stored.battle_number.map(|number| number + 1),
+        "A rejected first action uses up no battle number"
+    );
+}
+
+#[tokio::test]
+async fn test_battle_seed_fixes_every_turn_rng() {
+    let db = Arc::new(InMemoryDb::new());
+    let handler = BattleHandler::new(db.clone());
+    let battle_id = handler.create_mvp_battle(serde_json::from_value(serde_json::json!({
+        "player_name": "Test Trainer",
+        "team_id": "venusaur_team",
+        "opponent_id": "gym_leader_medium",
+        "battle_seed": 7,
+    })).unwrap()).await.unwrap().battle_id;
+
+    handler.submit_action(SubmitActionRequest {
+        battle_id,
+        player_id: PlayerId("player_1".to_string()),
+        action: PlayerAction::UseMove { move_index: 0 },
+        async_resolution: false,
+        authenticated_player_id: None,
+    }).await.unwrap();
+
+    let stored = db.get_battle(battle_id).await.unwrap().unwrap();
+    assert_eq!(stored.battle_seed, Some(7));
+    let first_resolution = &stored.turn_logs[0].resolutions[0];
+    assert_eq!(first_resolution.seed, Some(crate::engine::turn_seed(7, 1)), "The turn's seed depends only on the battle seed and turn number");
+}
+
+#[tokio::test]
+async fn test_submit_action_retries_version_conflicts() {
+    let db = Arc::new(InMemoryDb::new());
+    let handler = BattleHandler::new(db.clone());
+    let battle_id = handler.create_mvp_battle(CreateMvpBattleRequest {
+        player_name: "Test Trainer".to_string(),
+        team_id: "venusaur_team".to_string(),
+        opponent_id: "gym_leader_medium".to_string(),
+        opponent_team_id: None,
+        opponent_level_cap: None,
+        switch_uses_turn: false,
+        grant_observer_token: false,
+        battle_seed: None,
+    }).await.unwrap().battle_id;
+    let submit = || handler.submit_action(SubmitActionRequest {
+        battle_id,
+        player_id: PlayerId("player_1".to_string()),
+        action: PlayerAction::UseMove { move_index: 0 },
+        async_resolution: false,
+        authenticated_player_id: None,
+    });
+
+    // Two concurrent saves cost two retries; the third attempt lands
+    db.simulate_concurrent_writes(2);
+    submit().await.unwrap();
+    let stored = db.get_battle(battle_id).await.unwrap().unwrap();
+    assert_eq!(stored.turn_logs.len(), 1, "The action is applied exactly once");
+
+    // Contention on every attempt gives up with a 409 and saves nothing
+    db.simulate_concurrent_writes(3);
+    let error = submit().await.unwrap_err();
+    assert!(matches!(error, ApiError::Conflict { .. }));
+    assert_eq!(error.status_code(), 409);
+    assert_eq!(db.get_battle(battle_id).await.unwrap().unwrap().turn_logs.len(), 1);
+}
+
+#[tokio::test]
+async fn test_concurrent_duplicate_actions_apply_once() {
+    let db = Arc::new(InMemoryDb::new());
+    let handler = BattleHandler::new(db.clone());
+    let npc_battle = handler.create_mvp_battle(CreateMvpBattleRequest {
+        player_name: "Test Trainer".to_string(),
+        team_id: "venusaur_team".to_string(),
+        opponent_id: "gym_leader_medium".to_string(),
+        opponent_team_id: None,
+        opponent_level_cap: None,
+        switch_uses_turn: false,
+        grant_observer_token: false,
+        battle_seed: None,
+    }).await.unwrap().battle_id;
+    let pvp_battle = default_pvp_battle(&handler).await;
+    let submit = |battle_id, player: &str| handler.submit_action(SubmitActionRequest {
+        battle_id,
+        player_id: PlayerId(player.to_string()),
+        action: PlayerAction::UseMove { move_index: 0 },
+        async_resolution: false,
+        authenticated_player_id: None,
+    });
+
+    // Both requests load before either saves, as when a client double-submits
+    db.interleave_loads();
+
+    // Against an NPC the first save resolves the turn; the loser must not take the next one
+    let (first, second) = tokio::join!(submit(npc_battle, "player_1"), submit(npc_battle, "player_1"));
+    assert_eq!([&first, &second].iter().filter(|result| result.is_ok()).count(), 1, "Exactly one submission wins");
+    let stored = db.get_battle(npc_battle).await.unwrap().unwrap();
+    assert_eq!(stored.turn_logs.len(), 1, "The duplicate never reaches turn 2");
+    assert!(stored.battle_state.action_queue[0].is_none());
+
+    // Against a player the loser finds the winner's action queued for the same turn
+    let (first, second) = tokio::join!(submit(pvp_battle, "alice"), submit(pvp_battle, "alice"));
+    assert_eq!([&first, &second].iter().filter(|result| result.is_ok()).count(), 1);
+    assert!(matches!(first.and(second), Err(ApiError::InvalidAction { .. })));
+    let stored = db.get_battle(pvp_battle).await.unwrap().unwrap();
+    assert!(stored.battle_state.action_queue[0].is_some());
+    assert!(stored.turn_logs.is_empty(), "Bob has not acted, so nothing resolved");
+}
+
+#[tokio::test]
+async fn test_battles_expire_a_week_after_their_last_save() {
+    let db = Arc::new(InMemoryDb::new());
+    let handler = BattleHandler::new(db.clone());
+    let battle_id = default_pvp_battle(&handler).await;
+    let stored = db.get_battle(battle_id).await.unwrap().unwrap();
+
+    let expires_at = stored.last_updated + BATTLE_TTL_SECONDS;
+    assert_eq!(stored.expires_at(), expires_at);
+    assert!(!stored.is_expired(stored.last_updated));
+    assert!(!stored.is_expired(expires_at - 1), "Still live one second before expiry");
+    assert!(stored.is_expired(expires_at), "Expired from the expiry timestamp itself");
+    assert!(stored.is_expired(expires_at + 1));
+}
+
+#[tokio::test]
+async fn test_expired_battles_read_as_not_found() {
+    let db = Arc::new(InMemoryDb::new());
+    let handler = BattleHandler::new(db.clone());
+    let created = handler.create_battle(default_pvp_request()).await.unwrap();
+    let battle_id = created.battle_id;
+    handler.forfeit(ForfeitRequest {
+        battle_id,
+        player_id: PlayerId("bob".to_string()),
+        authenticated_player_id: None,
+    }).await.unwrap();
+    assert_eq!(handler.get_recent_battles(20).await.unwrap().battles.len(), 1, "Listed while still live");
+
+    // Last saved eight days ago: past the TTL, but not yet removed by DynamoDB's deleter
+    let mut stored = db.get_battle(battle_id).await.unwrap().unwrap();
+    stored.last_updated -= 8 * 24 * 60 * 60;
+    db.update_battle(&stored).await.unwrap();
+    let alice = PlayerId("alice".to_string());
+
+    let state = handler.get_battle_state(GetBattleStateRequest {
+        battle_id,
+        player_id: alice.clone(),
+        strict_info: false,
+        debug: false,
+        is_admin: false,
+        observer_token: None,
+    }).await;
+    assert!(matches!(state, Err(ApiError::BattleNotFound { .. })));
+    assert!(matches!(handler.get_battle_result(battle_id).await, Err(ApiError::BattleNotFound { .. })));
+
+    let by_number = handler.get_battle_by_number(GetBattleByNumberRequest {
+        battle_number: created.battle_number,
+        authenticated_player_id: Some(alice.clone()),
+        is_admin: false,
+    }).await;
+    assert!(matches!(by_number, Err(ApiError::BattleNumberNotFound { .. })));
+
+    let history = handler.get_player_history(PlayerHistoryRequest {
+        player_id: alice.clone(),
+        limit: 20,
+        cursor: None,
+        authenticated_player_id: Some(alice),
+    }).await.unwrap();
+    assert!(history.battles.is_empty());
+    assert!(handler.get_recent_battles(20).await.unwrap().battles.is_empty());
+}
@@ -0,0 +1,196 @@
+use pokemon_adventure::{moves::Move, player::PlayerAction, species::Species};
+use crate::tests::common::create_test_handler;
+use crate::tests::common::{pvp_battle_request, team_pokemon};
+use crate::{
+    AbandonAllRequest, ApiError, CreateMvpBattleRequest, ForfeitRequest, HistoryResult, ListPlayerBattlesRequest, PlayerHistoryRequest,
+    PlayerId, SubmitActionRequest,
+};
+
+
+#[tokio::test]
+async fn test_recent_battles_lists_only_finished() {
+    let handler = create_test_handler().unwrap();
+    let player_id = PlayerId("player_1".to_string());
+
+    let mut battle_ids = Vec::new();
+    for _ in 0..2 {
+        let create_request = CreateMvpBattleRequest {
+            player_name: "Test Trainer".to_string(),
+            team_id: "venusaur_team".to_string(),
+            opponent_id: "gym_leader_easy".to_string(),
+            opponent_team_id: None,
+            opponent_level_cap: None,
+            switch_uses_turn: false,
+            grant_observer_token: false,
+            battle_seed: None,
+        };
+        battle_ids.push(handler.create_mvp_battle(create_request).await.unwrap().battle_id);
+    }
+
+    // Finish only the first battle
+    handler.submit_action(SubmitActionRequest {
+        battle_id: battle_ids[0],
+        player_id: player_id.clone(),
+        action: PlayerAction::Forfeit,
+        async_resolution: false,
+        authenticated_player_id: None,
+    }).await.unwrap();
+
+    let recent = handler.get_recent_battles(20).await.unwrap();
+    assert_eq!(recent.battles.len(), 1, "Only the finished battle should appear in the feed");
+    let summary = &recent.battles[0];
+    assert_eq!(summary.battle_id, battle_ids[0]);
+    assert_eq!(summary.title.as_deref(), Some("Venusaur Team vs Gym Leader Brock"));
+    assert!(summary.winner_name.is_some(), "A forfeit should produce a winner");
+}
+
+#[tokio::test]
+async fn test_battle_result_is_public_once_finished() {
+    let handler = create_test_handler().unwrap();
+    let create_request = CreateMvpBattleRequest {
+        player_name: "Test Trainer".to_string(),
+        team_id: "venusaur_team".to_string(),
+        opponent_id: "gym_leader_easy".to_string(),
+        opponent_team_id: None,
+        opponent_level_cap: None,
+        switch_uses_turn: false,
+        grant_observer_token: false,
+        battle_seed: None,
+    };
+    let battle_id = handler.create_mvp_battle(create_request).await.unwrap().battle_id;
+
+    let in_progress = handler.get_battle_result(battle_id).await;
+    assert!(matches!(in_progress, Err(ApiError::BattleInProgress { .. })));
+
+    handler.submit_action(SubmitActionRequest {
+        battle_id,
+        player_id: PlayerId("player_1".to_string()),
+        action: PlayerAction::Forfeit,
+        async_resolution: false,
+        authenticated_player_id: None,
+    }).await.unwrap();
+
+    let result = handler.get_battle_result(battle_id).await.unwrap();
+    assert_eq!(result.result, pokemon_adventure::battle::state::GameState::Player2Win);
+    assert_ne!(result.winner_player_id, Some(PlayerId("player_1".to_string())));
+}
+
+#[tokio::test]
+async fn test_abandon_all_forfeits_only_unfinished_battles() {
+    let handler = create_test_handler().unwrap();
+    let player_id = PlayerId("alice".to_string());
+    let team = || vec![team_pokemon(Species::Pikachu, 25, vec![Move::Thunderbolt])];
+
+    let mut battle_ids = Vec::new();
+    for _ in 0..3 {
+        battle_ids.push(handler.create_battle(pvp_battle_request(team(), team())).await.unwrap().battle_id);
+    }
+
+    handler.forfeit(ForfeitRequest {
+        battle_id: battle_ids[0],
+        player_id: player_id.clone(),
+        authenticated_player_id: None,
+    }).await.unwrap();
+
+    let unauthenticated = handler.abandon_all_battles(AbandonAllRequest {
+        player_id: player_id.clone(),
+        authenticated_player_id: None,
+    }).await;
+    assert!(matches!(unauthenticated, Err(ApiError::AuthRequired)));
+
+    let impersonated = handler.abandon_all_battles(AbandonAllRequest {
+        player_id: player_id.clone(),
+        authenticated_player_id: Some(PlayerId("someone_else".to_string())),
+    }).await;
+    assert!(matches!(impersonated, Err(ApiError::PlayerNotAuthorized { .. })));
+
+    // Every MVP battle is filed under player_1, so abandoning as player_1 would reach other users' battles
+    let mvp_player = PlayerId("player_1".to_string());
+    let shared = handler.abandon_all_battles(AbandonAllRequest {
+        player_id: mvp_player.clone(),
+        authenticated_player_id: Some(mvp_player),
+    }).await;
+    assert!(matches!(shared, Err(ApiError::ValidationError { .. })));
+
+    let response = handler.abandon_all_battles(AbandonAllRequest {
+        player_id: player_id.clone(),
+        authenticated_player_id: Some(player_id.clone()),
+    }).await.unwrap();
+    assert_eq!(response.abandoned_count, 2, "The already-finished battle should be skipped");
+    assert!(response.failed_battle_ids.is_empty());
+
+    for battle_id in battle_ids {
+        assert!(handler.get_battle_result(battle_id).await.is_ok(), "Every battle should now be finished");
+    }
+}
+
+#[tokio::test]
+async fn test_player_battles_list_both_sides() {
+    let handler = create_test_handler().unwrap();
+    let team = || vec![team_pokemon(Species::Pikachu, 25, vec![Move::Thunderbolt])];
+    let first = handler.create_battle(pvp_battle_request(team(), team())).await.unwrap().battle_id;
+    let mut rematch = pvp_battle_request(team(), team());
+    std::mem::swap(&mut rematch.player1_id, &mut rematch.player2_id);
+    let second = handler.create_battle(rematch).await.unwrap().battle_id;
+    let list = |authenticated: Option<&str>, limit: usize| handler.list_player_battles(ListPlayerBattlesRequest {
+        player_id: PlayerId("bob".to_string()),
+        limit,
+        authenticated_player_id: authenticated.map(|id| PlayerId(id.to_string())),
+    });
+
+    assert!(matches!(list(None, 10).await, Err(ApiError::AuthRequired)));
+    assert!(matches!(list(Some("alice"), 10).await, Err(ApiError::PlayerNotAuthorized { .. })));
+    assert_eq!(list(Some("bob"), 1).await.unwrap().battles.len(), 1);
+
+    let response = list(Some("bob"), 10).await.unwrap();
+    let mut listed: Vec<_> = response.battles.iter().map(|entry| entry.battle_id).collect();
+    listed.sort_by_key(|id| id.0);
+    let mut expected = vec![first, second];
+    expected.sort_by_key(|id| id.0);
+    assert_eq!(listed, expected, "Bob is player 2 in one battle and player 1 in the other");
+    assert!(response.battles.iter().all(|entry| entry.opponent_id == PlayerId("alice".to_string())));
+    assert!(response.battles.iter().all(|entry| entry.created_at <= entry.last_updated));
+}
+
+#[tokio::test]
+async fn test_player_history_pages_finished_battles_newest_first() {
+    let handler = create_test_handler().unwrap();
+    let team = || vec![team_pokemon(Species::Pikachu, 25, vec![Move::Thunderbolt])];
+    let mut finished = Vec::new();
+    for loser in ["alice", "bob"] {
+        let battle_id = handler.create_battle(pvp_battle_request(team(), team())).await.unwrap().battle_id;
+        handler.forfeit(ForfeitRequest {
+            battle_id,
+            player_id: PlayerId(loser.to_string()),
+            authenticated_player_id: None,
+        }).await.unwrap();
+        finished.push(battle_id);
+    }
+    handler.create_battle(pvp_battle_request(team(), team())).await.unwrap();
+    let history = |cursor: Option<String>| handler.get_player_history(PlayerHistoryRequest {
+        player_id: PlayerId("bob".to_string()),
+        limit: 1,
+        cursor: cursor.map(|cursor| cursor.parse().unwrap()),
+        authenticated_player_id: Some(PlayerId("bob".to_string())),
+    });
+
+    // Both battles likely finish within the same second; the cursor's id tie-break keeps pages disjoint
+    let first_page = history(None).await.unwrap();
+    assert_eq!(first_page.battles.len(), 1);
+    assert!(first_page.next_cursor.is_some());
+    let second_page = history(first_page.next_cursor.clone()).await.unwrap();
+    assert_eq!(second_page.battles.len(), 1);
+    assert_eq!(second_page.next_cursor, None, "The unfinished battle is not part of the history");
+
+    let alice_forfeited = finished[0];
+    let entries = [&first_page.battles[0], &second_page.battles[0]];
+    let mut listed: Vec<_> = entries.iter().map(|entry| entry.battle_id).collect();
+    listed.sort_by_key(|id| id.0);
+    finished.sort_by_key(|id| id.0);
+    assert_eq!(listed, finished);
+    for entry in entries {
+        let expected = if entry.battle_id == alice_forfeited { HistoryResult::Win } else { HistoryResult::Loss };
+        assert_eq!(entry.result, expected);
+        assert_eq!(entry.opponent_name, "Player alice");
+    }
+}
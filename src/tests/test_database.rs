@@ -0,0 +1,13 @@
+use crate::database::Database;
+
+
+#[tokio::test]
+async fn test_databases_share_one_dynamodb_client() {
+    // Tests build the client from a fixed config and it makes no requests, so this runs without AWS access
+    let first = Database::new("pokemon-battles".to_string()).await.unwrap();
+    let second = Database::new("pokemon-battles".to_string()).await.unwrap();
+    let other_table = Database::new("pokemon-battles-staging".to_string()).await.unwrap();
+
+    assert!(first.shares_client_with(&second), "Warm invocations reuse the cold start's client");
+    assert!(first.shares_client_with(&other_table), "The client is per process, not per table");
+}
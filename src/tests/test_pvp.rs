@@ -0,0 +1,1171 @@
+use pokemon_adventure::{
+    battle::state::GameState, moves::Move, player::PlayerAction, pokemon::PokemonType, species::Species,
+};
+use serde_json::json;
+use crate::tests::common::{create_test_handler, default_pvp_battle, default_pvp_request, pvp_battle_request, team_pokemon};
+use crate::{
+    ApiError, BattleEventDto, CreateMatchRequest, ForfeitRequest, GetActionQueueRequest, GetBattleEventsRequest, GetBattleEventsResponse,
+    GetBattleChangesRequest, GetBattleEstimateRequest, GetBattleReplayRequest, GetBattleStateRequest, GetDamageReportRequest, GetMovePpRequest, GetSpectatorViewRequest,
+    GetValidActionsRequest, PlayerId, SetBattleStateRequest, SetPausedRequest, SubmitActionRequest,
+};
+
+
+#[tokio::test]
+async fn test_double_forfeit_is_a_draw() {
+    let handler = create_test_handler().unwrap();
+    let battle_id = default_pvp_battle(&handler).await;
+
+    let first = handler.submit_action(SubmitActionRequest {
+        battle_id,
+        player_id: PlayerId("alice".to_string()),
+        action: PlayerAction::Forfeit,
+        async_resolution: false,
+        authenticated_player_id: None,
+    }).await.unwrap();
+    assert_eq!(first.game_state, GameState::WaitingForActions, "Turn waits for bob's action");
+
+    let second = handler.submit_action(SubmitActionRequest {
+        battle_id,
+        player_id: PlayerId("bob".to_string()),
+        action: PlayerAction::Forfeit,
+        async_resolution: false,
+        authenticated_player_id: None,
+    }).await.unwrap();
+    assert_eq!(second.game_state, GameState::Draw);
+
+    let result = handler.get_battle_result(battle_id).await.unwrap();
+    assert_eq!(result.result, GameState::Draw);
+    assert_eq!(result.winner_player_id, None);
+    assert_eq!(result.draw_reason.as_deref(), Some("mutual forfeit"));
+}
+
+#[tokio::test]
+async fn test_move_pp_drops_after_use() {
+    let handler = create_test_handler().unwrap();
+    let battle_id = handler.create_battle(pvp_battle_request(
+        vec![team_pokemon(Species::Pikachu, 25, vec![Move::Thunderbolt, Move::QuickAttack])],
+        vec![team_pokemon(Species::Rattata, 25, vec![Move::Tackle])],
+    )).await.unwrap().battle_id;
+    let alice = PlayerId("alice".to_string());
+
+    let before = handler.get_move_pp(GetMovePpRequest { battle_id, player_id: alice.clone() }).await.unwrap();
+    assert_eq!(before.moves.len(), 2, "Only known moves should be listed");
+    assert!(before.moves.iter().all(|m| m.pp == m.max_pp));
+
+    for (player, move_index) in [("alice", 0), ("bob", 0)] {
+        handler.submit_action(SubmitActionRequest {
+            battle_id,
+            player_id: PlayerId(player.to_string()),
+            action: PlayerAction::UseMove { move_index },
+            async_resolution: false,
+            authenticated_player_id: None,
+        }).await.unwrap();
+    }
+
+    let after = handler.get_move_pp(GetMovePpRequest { battle_id, player_id: alice }).await.unwrap();
+    assert_eq!(after.moves[0].move_, Move::Thunderbolt);
+    assert_eq!(after.moves[0].pp, before.moves[0].pp - 1);
+    assert_eq!(after.moves[1].pp, before.moves[1].pp);
+}
+
+#[tokio::test]
+async fn test_switch_previews_cover_each_switch_option() {
+    let handler = create_test_handler().unwrap();
+    let battle_id = handler.create_battle(pvp_battle_request(
+        vec![
+            team_pokemon(Species::Rattata, 25, vec![Move::Tackle]),
+            team_pokemon(Species::Pikachu, 25, vec![Move::Thunderbolt]),
+        ],
+        vec![team_pokemon(Species::Squirtle, 25, vec![Move::Tackle])],
+    )).await.unwrap().battle_id;
+
+    let response = handler.get_valid_actions(GetValidActionsRequest {
+        battle_id,
+        player_id: PlayerId("alice".to_string()),
+    }).await.unwrap();
+
+    let switch_count = response.valid_actions.iter()
+        .filter(|a| matches!(a, PlayerAction::SwitchPokemon { .. }))
+        .count();
+    assert_eq!(response.switch_previews.len(), switch_count);
+
+    let pikachu = response.switch_previews.iter().find(|p| p.team_index == 1).unwrap();
+    assert_eq!(pikachu.species, Species::Pikachu);
+    assert_eq!(pikachu.opponent_species, Some(Species::Squirtle));
+    assert_eq!(pikachu.offensive_multiplier, Some(2.0), "Thunderbolt is super effective against Squirtle");
+    assert_eq!(pikachu.defensive_multiplier, Some(1.0), "Water is neutral against Pikachu");
+}
+
+#[tokio::test]
+async fn test_exact_damage_is_only_visible_to_the_target_owner() {
+    let handler = create_test_handler().unwrap();
+    // Seeded so Thunderbolt's 1/256 miss chance cannot leave the turn without damage
+    let mut request = default_pvp_request();
+    request.battle_seed = Some(42);
+    let battle_id = handler.create_battle(request).await.unwrap().battle_id;
+
+    for player in ["alice", "bob"] {
+        handler.submit_action(SubmitActionRequest {
+            battle_id,
+            player_id: PlayerId(player.to_string()),
+            action: PlayerAction::UseMove { move_index: 0 },
+            async_resolution: false,
+            authenticated_player_id: None,
+        }).await.unwrap();
+    }
+
+    let rattata_damage = |response: &GetBattleEventsResponse| -> Vec<Option<u16>> {
+        response.turn_logs.iter()
+            .flat_map(|log| &log.structured_events)
+            .filter_map(|event| match event {
+                BattleEventDto::Damage { target: Species::Rattata, damage, .. } => Some(*damage),
+                _ => None,
+            })
+            .collect()
+    };
+
+    let mut views = Vec::new();
+    for player in ["alice", "bob"] {
+        let response = handler.get_battle_events(GetBattleEventsRequest {
+            battle_id,
+            player_id: PlayerId(player.to_string()),
+            last_turns: None,
+            since_turn: None,
+            event_types: None,
+            include_markers: false,
+            offset: None,
+            limit: None,
+        }).await.unwrap();
+        assert!(response.turn_logs.iter().all(|log| log.events.len() == log.structured_events.len()));
+        views.push(rattata_damage(&response));
+    }
+
+    let (alice_view, bob_view) = (&views[0], &views[1]);
+    assert!(!alice_view.is_empty(), "Thunderbolt should damage Rattata");
+    assert!(alice_view.iter().all(|damage| damage.is_none()), "Alice should not see exact damage to Bob's Pokemon");
+    assert!(bob_view.iter().all(|damage| damage.is_some()), "Bob should see exact damage to his own Pokemon");
+}
+
+#[tokio::test]
+async fn test_mirror_match_damage_is_attributed_to_its_side() {
+    let handler = create_test_handler().unwrap();
+    let mut request = pvp_battle_request(
+        vec![team_pokemon(Species::Pikachu, 25, vec![Move::Thunderbolt])],
+        vec![team_pokemon(Species::Pikachu, 30, vec![Move::Thunderbolt])],
+    );
+    request.battle_seed = Some(7);
+    let battle_id = handler.create_battle(request).await.unwrap().battle_id;
+
+    for player in ["alice", "bob"] {
+        handler.submit_action(SubmitActionRequest {
+            battle_id,
+            player_id: PlayerId(player.to_string()),
+            action: PlayerAction::UseMove { move_index: 0 },
+            async_resolution: false,
+            authenticated_player_id: None,
+        }).await.unwrap();
+    }
+
+    let response = handler.get_battle_events(GetBattleEventsRequest {
+        battle_id,
+        player_id: PlayerId("alice".to_string()),
+        last_turns: None,
+        since_turn: None,
+        event_types: Some(vec!["damage".to_string()]),
+        include_markers: false,
+        offset: None,
+        limit: None,
+    }).await.unwrap();
+    let damage: Vec<(Option<usize>, Option<u16>)> = response.turn_logs.iter()
+        .flat_map(|log| &log.structured_events)
+        .filter_map(|event| match event {
+            BattleEventDto::Damage { target_player, damage, .. } => Some((*target_player, *damage)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(damage.len(), 2, "Both Pikachu are hit");
+    for (target_player, damage) in damage {
+        match target_player {
+            Some(0) => assert!(damage.is_some(), "Alice sees exact damage to her own Pikachu"),
+            Some(1) => assert!(damage.is_none(), "Bob's Pikachu's exact damage stays hidden"),
+            other => panic!("Damage should name its side, got {:?}", other),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_damage_events_carry_the_hp_lost() {
+    let handler = create_test_handler().unwrap();
+    // Seeded so Thunderbolt's 1/256 miss chance cannot leave the turn without damage
+    let mut request = pvp_battle_request(
+        vec![team_pokemon(Species::Pikachu, 10, vec![Move::Thunderbolt])],
+        vec![team_pokemon(Species::Snorlax, 50, vec![Move::Splash])],
+    );
+    request.battle_seed = Some(42);
+    let battle_id = handler.create_battle(request).await.unwrap().battle_id;
+
+    for player in ["alice", "bob"] {
+        handler.submit_action(SubmitActionRequest {
+            battle_id,
+            player_id: PlayerId(player.to_string()),
+            action: PlayerAction::UseMove { move_index: 0 },
+            async_resolution: false,
+            authenticated_player_id: None,
+        }).await.unwrap();
+    }
+
+    let response = handler.get_battle_events(GetBattleEventsRequest {
+        battle_id,
+        player_id: PlayerId("bob".to_string()),
+        last_turns: None,
+        since_turn: None,
+        event_types: Some(vec!["damage".to_string()]),
+        include_markers: false,
+        offset: None,
+        limit: None,
+    }).await.unwrap();
+    let damage: Vec<(u16, u16)> = response.turn_logs.iter()
+        .flat_map(|log| &log.structured_events)
+        .filter_map(|event| match event {
+            BattleEventDto::Damage { target: Species::Snorlax, damage, remaining_hp, .. } => Some((damage.unwrap(), remaining_hp.unwrap())),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(damage.len(), 1, "Thunderbolt hits Snorlax once");
+
+    let snorlax = handler.get_battle_state(GetBattleStateRequest {
+        battle_id,
+        player_id: PlayerId("bob".to_string()),
+        strict_info: false,
+        debug: false,
+        is_admin: false,
+        observer_token: None,
+    }).await.unwrap().player_team.active_pokemon.unwrap();
+    assert!(damage[0].0 > 0);
+    assert_eq!(damage[0].0, snorlax.max_hp - snorlax.current_hp, "The structured event reports the exact HP lost");
+    assert_eq!(damage[0].1, snorlax.current_hp);
+}
+
+#[tokio::test]
+async fn test_move_usage_is_aggregated_across_battles() {
+    let handler = create_test_handler().unwrap();
+
+    for _ in 0..2 {
+        let battle_id = default_pvp_battle(&handler).await;
+
+        for player in ["alice", "bob"] {
+            handler.submit_action(SubmitActionRequest {
+                battle_id,
+                player_id: PlayerId(player.to_string()),
+                action: PlayerAction::UseMove { move_index: 0 },
+                async_resolution: false,
+                authenticated_player_id: None,
+            }).await.unwrap();
+        }
+    }
+
+    let usage = handler.get_move_usage().await.unwrap();
+    let thunderbolt = usage.moves.iter().find(|m| m.move_name == "Thunderbolt").unwrap();
+    assert_eq!(thunderbolt.count, 2, "Pikachu moves first in both battles");
+    assert_eq!(usage.total_uses, usage.moves.iter().map(|m| m.count).sum::<u64>());
+    assert!(usage.moves.windows(2).all(|pair| pair[0].count >= pair[1].count));
+}
+
+#[tokio::test]
+async fn test_team_summaries_resolve_species_types() {
+    let handler = create_test_handler().unwrap();
+    let battle_id = default_pvp_battle(&handler).await;
+
+    let state = handler.get_battle_state(GetBattleStateRequest {
+        battle_id,
+        player_id: PlayerId("alice".to_string()),
+        strict_info: false,
+        debug: false,
+        is_admin: false,
+        observer_token: None,
+    }).await.unwrap();
+
+    let bench_entry = state.player_team.team_pokemon[0].as_ref().unwrap();
+    assert_eq!(bench_entry.types, vec![PokemonType::Electric]);
+    assert!(bench_entry.error.is_none(), "Resolvable species should not be flagged");
+
+    let opponent = state.opponent_info.active_pokemon.as_ref().unwrap();
+    assert_eq!(opponent.types, vec![PokemonType::Normal]);
+}
+
+#[tokio::test]
+async fn test_revealed_opponent_moves_count_pp_from_the_log() {
+    let handler = create_test_handler().unwrap();
+    let battle_id = handler.create_battle(pvp_battle_request(
+        vec![team_pokemon(Species::Pikachu, 25, vec![Move::Thunderbolt, Move::QuickAttack])],
+        vec![team_pokemon(Species::Rattata, 25, vec![Move::Tackle])],
+    )).await.unwrap().battle_id;
+    let bob = PlayerId("bob".to_string());
+
+    for player in ["alice", "bob"] {
+        handler.submit_action(SubmitActionRequest {
+            battle_id,
+            player_id: PlayerId(player.to_string()),
+            action: PlayerAction::UseMove { move_index: 0 },
+            async_resolution: false,
+            authenticated_player_id: None,
+        }).await.unwrap();
+    }
+
+    let casual = handler.get_battle_state(GetBattleStateRequest {
+        battle_id,
+        player_id: bob.clone(),
+        strict_info: false,
+        debug: false,
+        is_admin: false,
+        observer_token: None,
+    }).await.unwrap();
+    let alice_pp = handler.get_move_pp(GetMovePpRequest {
+        battle_id,
+        player_id: PlayerId("alice".to_string()),
+    }).await.unwrap();
+
+    let revealed = &casual.opponent_info.revealed_moves;
+    assert_eq!(revealed.len(), 1, "Unused Quick Attack should stay hidden");
+    assert_eq!(revealed[0].move_, Move::Thunderbolt);
+    assert_eq!(revealed[0].times_used, 1);
+    assert_eq!(revealed[0].revealed_move_pp, Some(alice_pp.moves[0].pp));
+
+    let strict = handler.get_battle_state(GetBattleStateRequest {
+        battle_id,
+        player_id: bob,
+        strict_info: true,
+        debug: false,
+        is_admin: false,
+        observer_token: None,
+    }).await.unwrap();
+    assert_eq!(strict.opponent_info.revealed_moves[0].times_used, 1);
+    assert!(strict.opponent_info.revealed_moves[0].revealed_move_pp.is_none(), "Strict mode leaves PP counting to the player");
+}
+
+#[tokio::test]
+async fn test_best_of_three_match_creates_games_until_decided() {
+    let handler = create_test_handler().unwrap();
+    let created = handler.create_match(CreateMatchRequest {
+        battle: pvp_battle_request(
+            vec![team_pokemon(Species::Pikachu, 50, vec![Move::Thunderbolt])],
+            vec![team_pokemon(Species::Rattata, 5, vec![Move::Tackle])],
+        ),
+        best_of: 3,
+    }).await.unwrap();
+
+    let mut battle_id = created.battle_id;
+    for game in 1..=2u32 {
+        for player in ["alice", "bob"] {
+            handler.submit_action(SubmitActionRequest {
+                battle_id,
+                player_id: PlayerId(player.to_string()),
+                action: PlayerAction::UseMove { move_index: 0 },
+                async_resolution: false,
+                authenticated_player_id: None,
+            }).await.unwrap();
+        }
+
+        let series = handler.get_match(created.match_id).await.unwrap();
+        assert_eq!(series.score, [game, 0], "Pikachu should win game {}", game);
+        assert_eq!(series.games[game as usize - 1].winner_player_id, Some(PlayerId("alice".to_string())));
+        if game == 1 {
+            assert!(!series.finished);
+            assert_eq!(series.games.len(), 2, "Finishing a game should create the next one");
+            battle_id = series.current_battle_id.unwrap();
+            assert_ne!(battle_id, created.battle_id);
+        } else {
+            assert!(series.finished, "Two wins decide a best of three");
+            assert_eq!(series.winner_player_id, Some(PlayerId("alice".to_string())));
+            assert_eq!(series.games.len(), 2, "No third game once the series is decided");
+            assert!(series.current_battle_id.is_none());
+        }
+    }
+
+    let even = handler.create_match(CreateMatchRequest {
+        battle: pvp_battle_request(
+            vec![team_pokemon(Species::Pikachu, 50, vec![Move::Thunderbolt])],
+            vec![team_pokemon(Species::Rattata, 5, vec![Move::Tackle])],
+        ),
+        best_of: 2,
+    }).await;
+    assert!(matches!(even, Err(ApiError::ValidationError { .. })));
+}
+
+#[tokio::test]
+async fn test_slot_ids_distinguish_same_species_team_members() {
+    let handler = create_test_handler().unwrap();
+    let battle_id = handler.create_battle(pvp_battle_request(
+        vec![
+            team_pokemon(Species::Pikachu, 25, vec![Move::Thunderbolt]),
+            team_pokemon(Species::Pikachu, 25, vec![Move::QuickAttack]),
+        ],
+        vec![team_pokemon(Species::Rattata, 25, vec![Move::Tackle])],
+    )).await.unwrap().battle_id;
+    let alice_state = || handler.get_battle_state(GetBattleStateRequest {
+        battle_id,
+        player_id: PlayerId("alice".to_string()),
+        strict_info: false,
+        debug: false,
+        is_admin: false,
+        observer_token: None,
+    });
+
+    let state = alice_state().await.unwrap();
+    let slot_ids: Vec<usize> = state.player_team.team_pokemon.iter().flatten().map(|p| p.slot_id).collect();
+    assert_eq!(slot_ids, vec![0, 1], "Same-named Pokemon should still have distinct slot ids");
+    assert_eq!(state.player_team.active_pokemon.as_ref().unwrap().slot_id, 0);
+
+    for (player, action) in [
+        ("alice", PlayerAction::SwitchPokemon { team_index: 1 }),
+        ("bob", PlayerAction::UseMove { move_index: 0 }),
+    ] {
+        handler.submit_action(SubmitActionRequest {
+            battle_id,
+            player_id: PlayerId(player.to_string()),
+            action,
+            async_resolution: false,
+            authenticated_player_id: None,
+        }).await.unwrap();
+    }
+
+    let state = alice_state().await.unwrap();
+    assert_eq!(state.player_team.active_pokemon.as_ref().unwrap().slot_id, 1, "Active slot should follow the switch");
+    let bob_view = handler.get_battle_state(GetBattleStateRequest {
+        battle_id,
+        player_id: PlayerId("bob".to_string()),
+        strict_info: false,
+        debug: false,
+        is_admin: false,
+        observer_token: None,
+    }).await.unwrap();
+    assert_eq!(bob_view.opponent_info.active_pokemon.as_ref().unwrap().slot_id, 1);
+}
+
+#[tokio::test]
+async fn test_opponent_status_is_structured_and_hides_sleep_turns_in_strict_mode() {
+    let handler = create_test_handler().unwrap();
+    let battle_id = handler.create_battle(pvp_battle_request(
+        vec![team_pokemon(Species::Parasect, 50, vec![Move::Spore])],
+        vec![team_pokemon(Species::Snorlax, 50, vec![Move::Tackle])],
+    )).await.unwrap().battle_id;
+
+    for player in ["alice", "bob"] {
+        handler.submit_action(SubmitActionRequest {
+            battle_id,
+            player_id: PlayerId(player.to_string()),
+            action: PlayerAction::UseMove { move_index: 0 },
+            async_resolution: false,
+            authenticated_player_id: None,
+        }).await.unwrap();
+    }
+
+    let view = |strict_info| handler.get_battle_state(GetBattleStateRequest {
+        battle_id,
+        player_id: PlayerId("alice".to_string()),
+        strict_info,
+        debug: false,
+        is_admin: false,
+        observer_token: None,
+    });
+
+    let casual = view(false).await.unwrap();
+    let status = casual.opponent_info.active_pokemon.as_ref().unwrap().status.clone()
+        .expect("Spore should put the opponent to sleep");
+    assert_eq!(status.kind, "sleep");
+    assert!(status.turns_remaining.is_some(), "Casual mode shows the sleep counter");
+
+    let strict = view(true).await.unwrap();
+    let status = strict.opponent_info.active_pokemon.as_ref().unwrap().status.clone().unwrap();
+    assert_eq!(status.kind, "sleep");
+    assert!(status.turns_remaining.is_none(), "Strict mode hides exact sleep turns");
+}
+
+#[tokio::test]
+async fn test_state_reports_whether_switching_is_possible() {
+    let handler = create_test_handler().unwrap();
+    let battle_id = handler.create_battle(pvp_battle_request(
+        vec![team_pokemon(Species::Arbok, 50, vec![Move::Wrap])],
+        vec![
+            team_pokemon(Species::Snorlax, 50, vec![Move::Splash]),
+            team_pokemon(Species::Pikachu, 50, vec![Move::Splash]),
+        ],
+    )).await.unwrap().battle_id;
+    let state = |player: &str| handler.get_battle_state(GetBattleStateRequest {
+        battle_id,
+        player_id: PlayerId(player.to_string()),
+        strict_info: false,
+        debug: false,
+        is_admin: false,
+        observer_token: None,
+    });
+
+    let alice = state("alice").await.unwrap();
+    assert!(!alice.can_switch, "A lone Pokemon has nothing to switch to");
+    assert!(alice.trapped_reason.is_none(), "Lacking a bench is not being trapped");
+
+    let bob = state("bob").await.unwrap();
+    assert!(bob.can_switch);
+    assert!(bob.trapped_reason.is_none());
+
+    // Wrap can miss, so keep using it until it holds Snorlax
+    for _ in 0..10 {
+        for player in ["alice", "bob"] {
+            handler.submit_action(SubmitActionRequest {
+                battle_id,
+                player_id: PlayerId(player.to_string()),
+                action: PlayerAction::UseMove { move_index: 0 },
+                async_resolution: false,
+                authenticated_player_id: None,
+            }).await.unwrap();
+        }
+        let bob = state("bob").await.unwrap();
+        if let Some(reason) = bob.trapped_reason {
+            assert!(!bob.can_switch, "A trapped Pokemon cannot switch");
+            assert!(reason.contains("trapped"));
+            let valid = handler.get_valid_actions(GetValidActionsRequest {
+                battle_id,
+                player_id: PlayerId("bob".to_string()),
+            }).await.unwrap();
+            assert!(!valid.valid_actions.iter().any(|a| matches!(a, PlayerAction::SwitchPokemon { .. })));
+            return;
+        }
+    }
+    panic!("Wrap should have trapped Snorlax within ten turns");
+}
+
+#[tokio::test]
+async fn test_debug_state_redacts_opponent_for_participants_only() {
+    let handler = create_test_handler().unwrap();
+    let battle_id = handler.create_battle(pvp_battle_request(
+        vec![
+            team_pokemon(Species::Pikachu, 25, vec![Move::Thunderbolt]),
+            team_pokemon(Species::Rattata, 25, vec![Move::Tackle]),
+        ],
+        vec![
+            team_pokemon(Species::Snorlax, 25, vec![Move::Tackle]),
+            team_pokemon(Species::Rattata, 25, vec![Move::Tackle]),
+        ],
+    )).await.unwrap().battle_id;
+    let state = |debug, is_admin| handler.get_battle_state(GetBattleStateRequest {
+        battle_id,
+        player_id: PlayerId("alice".to_string()),
+        strict_info: false,
+        debug,
+        is_admin,
+        observer_token: None,
+    });
+
+    assert!(state(false, false).await.unwrap().raw_state.is_none(), "Raw state is opt-in");
+
+    let participant = state(true, false).await.unwrap().raw_state.unwrap();
+    assert!(!participant["players"][0]["team"][1].is_null(), "Own bench stays visible");
+    assert!(participant["players"][1]["team"][1].is_null(), "Opponent bench is redacted");
+    assert!(participant["players"][1]["team"][0]["moves"].is_null(), "Opponent moveset is redacted");
+    assert!(!participant["players"][1]["team"][0].is_null(), "Opponent active Pokemon stays visible");
+
+    let admin = state(true, true).await.unwrap().raw_state.unwrap();
+    assert!(!admin["players"][1]["team"][1].is_null(), "Admins see the full engine state");
+    assert!(!admin["players"][1]["team"][0]["moves"].is_null());
+}
+
+#[tokio::test]
+async fn test_side_conditions_appear_from_each_players_perspective() {
+    let handler = create_test_handler().unwrap();
+    let battle_id = handler.create_battle(pvp_battle_request(
+        vec![team_pokemon(Species::Alakazam, 50, vec![Move::Reflect, Move::Splash])],
+        vec![team_pokemon(Species::Snorlax, 50, vec![Move::Splash])],
+    )).await.unwrap().battle_id;
+    let state = |player: &str| handler.get_battle_state(GetBattleStateRequest {
+        battle_id,
+        player_id: PlayerId(player.to_string()),
+        strict_info: false,
+        debug: false,
+        is_admin: false,
+        observer_token: None,
+    });
+
+    assert!(state("alice").await.unwrap().field_conditions.is_empty());
+
+    for player in ["alice", "bob"] {
+        handler.submit_action(SubmitActionRequest {
+            battle_id,
+            player_id: PlayerId(player.to_string()),
+            action: PlayerAction::UseMove { move_index: 0 },
+            async_resolution: false,
+            authenticated_player_id: None,
+        }).await.unwrap();
+    }
+
+    let alice = state("alice").await.unwrap();
+    let reflect = alice.field_conditions.iter().find(|c| c.kind == "reflect").expect("Reflect should be up");
+    assert_eq!(reflect.side.as_deref(), Some("player"));
+    let turns_remaining = reflect.turns_remaining.expect("Reflect is timed");
+
+    let bob = state("bob").await.unwrap();
+    assert_eq!(bob.field_conditions.iter().find(|c| c.kind == "reflect").unwrap().side.as_deref(), Some("opponent"));
+
+    // Both sides Splash, so the only change is one turn off the countdown
+    handler.submit_action(SubmitActionRequest {
+        battle_id,
+        player_id: PlayerId("bob".to_string()),
+        action: PlayerAction::UseMove { move_index: 0 },
+        async_resolution: false,
+        authenticated_player_id: None,
+    }).await.unwrap();
+    handler.submit_action(SubmitActionRequest {
+        battle_id,
+        player_id: PlayerId("alice".to_string()),
+        action: PlayerAction::UseMove { move_index: 1 },
+        async_resolution: false,
+        authenticated_player_id: None,
+    }).await.unwrap();
+    let reflect = state("alice").await.unwrap().field_conditions.into_iter().find(|c| c.kind == "reflect").unwrap();
+    assert_eq!(reflect.turns_remaining, Some(turns_remaining - 1), "The timer counts down each turn");
+}
+
+#[tokio::test]
+async fn test_damage_report_totals_damage_per_slot_after_the_battle() {
+    let handler = create_test_handler().unwrap();
+    let battle_id = handler.create_battle(pvp_battle_request(
+        vec![team_pokemon(Species::Pikachu, 50, vec![Move::Thunderbolt])],
+        vec![team_pokemon(Species::Rattata, 20, vec![Move::Tackle])],
+    )).await.unwrap().battle_id;
+    let report = |player: &str| handler.get_damage_report(GetDamageReportRequest {
+        battle_id,
+        player_id: PlayerId(player.to_string()),
+    });
+
+    assert!(matches!(report("alice").await.unwrap_err(), ApiError::BattleInProgress { .. }));
+
+    for _ in 0..20 {
+        for player in ["alice", "bob"] {
+            let _ = handler.submit_action(SubmitActionRequest {
+                battle_id,
+                player_id: PlayerId(player.to_string()),
+                action: PlayerAction::UseMove { move_index: 0 },
+                async_resolution: false,
+                authenticated_player_id: None,
+            }).await;
+        }
+        if report("alice").await.is_ok() {
+            break;
+        }
+    }
+
+    let alice = report("alice").await.unwrap();
+    let pikachu = &alice.player_team[0];
+    let rattata = &alice.opponent_team[0];
+    assert_eq!((pikachu.slot_id, pikachu.species), (0, Species::Pikachu));
+    assert!(rattata.damage_received > 0, "Rattata was knocked out");
+    assert_eq!(pikachu.damage_dealt, rattata.damage_received, "Only Pikachu attacked Rattata");
+    assert_eq!(rattata.damage_dealt, pikachu.damage_received);
+
+    let bob = report("bob").await.unwrap();
+    assert_eq!(bob.player_team, alice.opponent_team, "Bob sees the same totals from his side");
+    assert!(matches!(report("mallory").await.unwrap_err(), ApiError::PlayerNotInBattle { .. }));
+}
+
+#[tokio::test]
+async fn test_observer_token_reads_the_granted_players_view() {
+    let handler = create_test_handler().unwrap();
+    let mut request = default_pvp_request();
+    request.observer_for = Some(PlayerId("bob".to_string()));
+    let created = handler.create_battle(request).await.unwrap();
+    let token = created.observer_token.expect("observer_for grants a token");
+    let state = |observer_token: Option<String>| handler.get_battle_state(GetBattleStateRequest {
+        battle_id: created.battle_id,
+        player_id: PlayerId("coach".to_string()),
+        strict_info: false,
+        debug: false,
+        is_admin: false,
+        observer_token,
+    });
+
+    let observed = state(Some(token)).await.unwrap();
+    assert_eq!(observed.player_team.active_pokemon.unwrap().species, Species::Rattata, "The coach sees bob's side");
+
+    assert!(matches!(state(Some("guessed".to_string())).await.unwrap_err(), ApiError::InvalidObserverToken));
+    assert!(matches!(state(None).await.unwrap_err(), ApiError::PlayerNotInBattle { .. }), "Without the token the coach is a stranger");
+}
+
+#[tokio::test]
+async fn test_valid_actions_carry_move_analysis() {
+    let handler = create_test_handler().unwrap();
+    let battle_id = handler.create_battle(pvp_battle_request(
+        vec![team_pokemon(Species::Pikachu, 25, vec![Move::Thunderbolt, Move::QuickAttack])],
+        vec![team_pokemon(Species::Rattata, 25, vec![Move::Tackle])],
+    )).await.unwrap().battle_id;
+
+    let response = handler.get_valid_actions(GetValidActionsRequest {
+        battle_id,
+        player_id: PlayerId("alice".to_string()),
+    }).await.unwrap();
+
+    let priorities: Vec<_> = response.move_analysis.iter()
+        .map(|m| (m.move_index, m.move_, m.priority))
+        .collect();
+    assert_eq!(priorities, vec![(0, Move::Thunderbolt, 0), (1, Move::QuickAttack, 1)]);
+    assert!(
+        response.move_analysis.iter().all(|m| m.hit_chance == 255.0 / 256.0),
+        "Full-accuracy moves at neutral stages still miss 1/256 of the time in Gen 1"
+    );
+}
+
+#[tokio::test]
+async fn test_paused_battle_rejects_actions_until_resumed() {
+    let handler = create_test_handler().unwrap();
+    let battle_id = default_pvp_battle(&handler).await;
+    let set_paused = |player: &str, paused| handler.set_paused(SetPausedRequest {
+        battle_id,
+        player_id: PlayerId(player.to_string()),
+        paused,
+    });
+    let use_move = || handler.submit_action(SubmitActionRequest {
+        battle_id,
+        player_id: PlayerId("alice".to_string()),
+        action: PlayerAction::UseMove { move_index: 0 },
+        async_resolution: false,
+        authenticated_player_id: None,
+    });
+
+    assert!(matches!(set_paused("mallory", true).await.unwrap_err(), ApiError::PlayerNotInBattle { .. }));
+    assert!(set_paused("bob", true).await.unwrap().paused);
+
+    let error = use_move().await.unwrap_err();
+    assert!(matches!(&error, ApiError::InvalidBattleState { state } if state == "Paused"));
+    assert_eq!(error.status_code(), 409);
+
+    let state = handler.get_battle_state(GetBattleStateRequest {
+        battle_id,
+        player_id: PlayerId("alice".to_string()),
+        strict_info: false,
+        debug: false,
+        is_admin: false,
+        observer_token: None,
+    }).await.unwrap();
+    assert!(state.paused);
+    assert!(!state.can_act, "Nobody can act while paused");
+
+    assert!(!set_paused("alice", false).await.unwrap().paused);
+    assert!(use_move().await.unwrap().success);
+}
+
+#[tokio::test]
+async fn test_submit_action_returns_the_turn_outcome() {
+    let handler = create_test_handler().unwrap();
+    let battle_id = handler.create_battle(pvp_battle_request(
+        vec![team_pokemon(Species::Pikachu, 50, vec![Move::Thunderbolt])],
+        vec![team_pokemon(Species::Rattata, 20, vec![Move::Tackle])],
+    )).await.unwrap().battle_id;
+    let use_move = |player: &str| handler.submit_action(SubmitActionRequest {
+        battle_id,
+        player_id: PlayerId(player.to_string()),
+        action: PlayerAction::UseMove { move_index: 0 },
+        async_resolution: false,
+        authenticated_player_id: None,
+    });
+
+    let waiting = use_move("alice").await.unwrap().outcome.unwrap();
+    assert!(waiting.events.is_empty(), "Nothing resolves until bob acts");
+    assert!(waiting.your_valid_actions.is_empty());
+    assert_eq!(waiting.result, None);
+
+    let resolved = use_move("bob").await.unwrap().outcome.unwrap();
+    assert!(!resolved.events.is_empty());
+    assert_eq!(resolved.events.len(), resolved.structured_events.len());
+    assert!(resolved.structured_events.iter().all(|e| !e.is_marker()), "Pacing markers are not returned");
+    assert_eq!(resolved.new_state_summary.turns_resolved, 1);
+
+    let mut last = resolved;
+    for _ in 0..20 {
+        if last.result.is_some() {
+            break;
+        }
+        use_move("alice").await.unwrap();
+        last = use_move("bob").await.unwrap().outcome.unwrap();
+    }
+    assert_eq!(last.result.as_deref(), Some("loss"), "Bob's Rattata is outmatched");
+    assert_eq!(last.game_state, GameState::Player1Win);
+}
+
+#[tokio::test]
+async fn test_double_faint_waits_for_both_replacements() {
+    let handler = create_test_handler().unwrap();
+    let battle_id = handler.create_battle(pvp_battle_request(
+        vec![
+            team_pokemon(Species::Pikachu, 25, vec![Move::Thunderbolt]),
+            team_pokemon(Species::Snorlax, 25, vec![Move::Tackle]),
+        ],
+        vec![
+            team_pokemon(Species::Rattata, 25, vec![Move::Tackle]),
+            team_pokemon(Species::Pidgey, 25, vec![Move::Tackle]),
+        ],
+    )).await.unwrap().battle_id;
+    let both_replacing = serde_json::to_value(GameState::WaitingForBothReplacements).unwrap();
+    handler.set_battle_state(SetBattleStateRequest {
+        battle_id,
+        patch: json!({ "game_state": both_replacing }),
+    }).await.unwrap();
+
+    let handler = &handler;
+    let switch = |player: &str| handler.submit_action(SubmitActionRequest {
+        battle_id,
+        player_id: PlayerId(player.to_string()),
+        action: PlayerAction::SwitchPokemon { team_index: 1 },
+        async_resolution: false,
+        authenticated_player_id: None,
+    });
+    let can_act = |player: &str| {
+        let player_id = PlayerId(player.to_string());
+        async move {
+            handler.get_battle_state(GetBattleStateRequest {
+                battle_id,
+                player_id,
+                strict_info: false,
+                debug: false,
+                is_admin: false,
+                observer_token: None,
+            }).await.unwrap().can_act
+        }
+    };
+
+    let first = switch("alice").await.unwrap();
+    assert_eq!(first.game_state, GameState::WaitingForBothReplacements, "One replacement resolves nothing");
+    assert!(!first.can_act);
+    assert!(!can_act("alice").await, "Alice already chose her replacement");
+    assert!(can_act("bob").await);
+    assert!(matches!(switch("alice").await.unwrap_err(), ApiError::InvalidAction { .. }));
+
+    let second = switch("bob").await.unwrap();
+    assert_eq!(second.game_state, GameState::WaitingForActions, "Both replacements go in together");
+    assert!(can_act("alice").await && can_act("bob").await);
+}
+
+#[tokio::test]
+async fn test_estimate_turns_remaining_from_logged_damage() {
+    let handler = create_test_handler().unwrap();
+    let battle_id = handler.create_battle(pvp_battle_request(
+        vec![team_pokemon(Species::Snorlax, 30, vec![Move::Tackle])],
+        vec![team_pokemon(Species::Snorlax, 30, vec![Move::Tackle])],
+    )).await.unwrap().battle_id;
+    let estimate = |player: &str| handler.get_battle_estimate(GetBattleEstimateRequest {
+        battle_id,
+        player_id: PlayerId(player.to_string()),
+    });
+
+    assert_eq!(estimate("alice").await.unwrap().estimated_turns_remaining, None, "No damage yet to extrapolate from");
+    assert!(matches!(estimate("mallory").await.unwrap_err(), ApiError::PlayerNotInBattle { .. }));
+
+    for player in ["alice", "bob"] {
+        handler.submit_action(SubmitActionRequest {
+            battle_id,
+            player_id: PlayerId(player.to_string()),
+            action: PlayerAction::UseMove { move_index: 0 },
+            async_resolution: false,
+            authenticated_player_id: None,
+        }).await.unwrap();
+    }
+
+    let after_one_turn = estimate("alice").await.unwrap();
+    assert_eq!(after_one_turn.turns_resolved, 1);
+    let turns_left = after_one_turn.estimated_turns_remaining.expect("Tackles dealt damage");
+    assert!(turns_left > 1, "Snorlax trading Tackles lasts a while, got {}", turns_left);
+}
+
+#[tokio::test]
+async fn test_seeded_battles_replay_identically() {
+    let handler = create_test_handler().unwrap();
+    let play = |seed| {
+        let handler = &handler;
+        async move {
+            let mut request = pvp_battle_request(
+                vec![team_pokemon(Species::Pikachu, 25, vec![Move::Thunderbolt, Move::QuickAttack])],
+                vec![team_pokemon(Species::Rattata, 25, vec![Move::Tackle])],
+            );
+            request.battle_seed = Some(seed);
+            let battle_id = handler.create_battle(request).await.unwrap().battle_id;
+
+            'turns: for _ in 0..3 {
+                for (player, move_index) in [("alice", 1), ("bob", 0)] {
+                    let response = handler.submit_action(SubmitActionRequest {
+                        battle_id,
+                        player_id: PlayerId(player.to_string()),
+                        action: PlayerAction::UseMove { move_index },
+                        async_resolution: false,
+                        authenticated_player_id: None,
+                    }).await.unwrap();
+                    if response.outcome.is_some_and(|outcome| outcome.result.is_some()) {
+                        break 'turns;
+                    }
+                }
+            }
+
+            let mut turn_logs = handler.get_battle_events(GetBattleEventsRequest {
+                battle_id,
+                player_id: PlayerId("alice".to_string()),
+                last_turns: None,
+                since_turn: None,
+                event_types: None,
+                include_markers: true,
+                offset: None,
+                limit: None,
+            }).await.unwrap().turn_logs;
+            // Only the wall-clock time of processing may differ between runs
+            turn_logs.iter_mut().for_each(|log| log.timestamp = 0);
+            serde_json::to_string(&turn_logs).unwrap()
+        }
+    };
+
+    let first = play(42).await;
+    assert_eq!(first, play(42).await, "The same seed and actions produce byte-identical turn logs");
+    assert_ne!(first, "[]");
+    assert_ne!(first, play(7).await, "Another seed rolls differently, so the seed is what fixes the log");
+}
+
+#[tokio::test]
+async fn test_forfeit_endpoint_ends_the_battle_at_once() {
+    let handler = create_test_handler().unwrap();
+    let battle_id = default_pvp_battle(&handler).await;
+    let forfeit = |player: &str| handler.forfeit(ForfeitRequest {
+        battle_id,
+        player_id: PlayerId(player.to_string()),
+        authenticated_player_id: None,
+    });
+
+    let response = forfeit("alice").await.unwrap();
+    assert_eq!(response.game_state, GameState::Player2Win, "Bob wins without having to act");
+    assert_eq!(response.battle_state.game_state, GameState::Player2Win);
+    assert!(!response.battle_state.can_act);
+
+    let result = handler.get_battle_result(battle_id).await.unwrap();
+    assert_eq!(result.winner_player_id, Some(PlayerId("bob".to_string())));
+}
+
+#[tokio::test]
+async fn test_forfeit_rejects_finished_battles() {
+    let handler = create_test_handler().unwrap();
+    let battle_id = default_pvp_battle(&handler).await;
+    let forfeit = |player: &str| handler.forfeit(ForfeitRequest {
+        battle_id,
+        player_id: PlayerId(player.to_string()),
+        authenticated_player_id: None,
+    });
+
+    forfeit("bob").await.unwrap();
+    let error = forfeit("alice").await.unwrap_err();
+    assert!(matches!(error, ApiError::InvalidBattleState { .. }));
+    assert_eq!(error.status_code(), 409);
+}
+
+#[tokio::test]
+async fn test_action_queue_explains_an_unresolved_turn() {
+    let handler = create_test_handler().unwrap();
+    let battle_id = default_pvp_battle(&handler).await;
+
+    handler.submit_action(SubmitActionRequest {
+        battle_id,
+        player_id: PlayerId("alice".to_string()),
+        action: PlayerAction::UseMove { move_index: 0 },
+        async_resolution: false,
+        authenticated_player_id: None,
+    }).await.unwrap();
+
+    let queue = handler.get_action_queue(GetActionQueueRequest { battle_id }).await.unwrap();
+    assert_eq!(queue.game_state, GameState::WaitingForActions);
+    assert!(!queue.ready_for_turn_resolution, "Bob's action is still missing");
+    assert!(!queue.awaiting_second_replacement && !queue.resolution_pending && !queue.paused);
+    assert!(matches!(queue.slots[0].action, Some(PlayerAction::UseMove { move_index: 0 })));
+    assert!(queue.slots[0].action_submitted);
+    assert!(queue.slots[1].action.is_none() && !queue.slots[1].action_submitted);
+}
+
+#[tokio::test]
+async fn test_replay_reproduces_the_final_state() {
+    let handler = create_test_handler().unwrap();
+    let mut request = pvp_battle_request(
+        vec![team_pokemon(Species::Pikachu, 25, vec![Move::Thunderbolt, Move::QuickAttack])],
+        vec![team_pokemon(Species::Rattata, 25, vec![Move::Tackle])],
+    );
+    request.battle_seed = Some(42);
+    let battle_id = handler.create_battle(request).await.unwrap().battle_id;
+
+    for _ in 0..3 {
+        for (player, move_index) in [("alice", 1), ("bob", 0)] {
+            let _ = handler.submit_action(SubmitActionRequest {
+                battle_id,
+                player_id: PlayerId(player.to_string()),
+                action: PlayerAction::UseMove { move_index },
+                async_resolution: false,
+                authenticated_player_id: None,
+            }).await;
+        }
+    }
+    // Forfeit turns are resolved by the API rather than the engine and must replay too
+    let _ = handler.forfeit(ForfeitRequest {
+        battle_id,
+        player_id: PlayerId("bob".to_string()),
+        authenticated_player_id: None,
+    }).await;
+
+    let replay = handler.replay_battle(GetBattleReplayRequest {
+        battle_id,
+        player_id: PlayerId("alice".to_string()),
+    }).await.unwrap();
+    assert!(replay.matches_stored_state);
+    assert!(replay.turns.len() >= 2, "Several turns were logged");
+
+    let stored = handler.get_battle_state(GetBattleStateRequest {
+        battle_id,
+        player_id: PlayerId("alice".to_string()),
+        strict_info: false,
+        debug: false,
+        is_admin: false,
+        observer_token: None,
+    }).await.unwrap();
+    let replayed = &replay.turns.last().unwrap().battle_state;
+    assert_eq!(replayed.game_state, GameState::Player1Win);
+    assert_eq!(replayed.game_state, stored.game_state);
+    assert_eq!(replayed.turn_number, stored.turn_number);
+
+    // Unseeded battles resolve with the engine's own RNG, so there is nothing to replay
+    let unseeded = handler.create_battle(pvp_battle_request(
+        vec![team_pokemon(Species::Pikachu, 25, vec![Move::QuickAttack])],
+        vec![team_pokemon(Species::Rattata, 25, vec![Move::Tackle])],
+    )).await.unwrap().battle_id;
+    for player in ["alice", "bob"] {
+        handler.submit_action(SubmitActionRequest {
+            battle_id: unseeded,
+            player_id: PlayerId(player.to_string()),
+            action: PlayerAction::UseMove { move_index: 0 },
+            async_resolution: false,
+            authenticated_player_id: None,
+        }).await.unwrap();
+    }
+    let error = handler.replay_battle(GetBattleReplayRequest {
+        battle_id: unseeded,
+        player_id: PlayerId("alice".to_string()),
+    }).await.unwrap_err();
+    assert!(matches!(error, ApiError::ReplayUnavailable { .. }));
+}
+
+#[tokio::test]
+async fn test_changes_list_only_pokemon_touched_since_the_turn() {
+    let handler = create_test_handler().unwrap();
+    let mut request = pvp_battle_request(
+        vec![
+            team_pokemon(Species::Pikachu, 25, vec![Move::QuickAttack]),
+            team_pokemon(Species::Pikachu, 25, vec![Move::QuickAttack]),
+        ],
+        vec![
+            team_pokemon(Species::Rattata, 25, vec![Move::Tackle]),
+            team_pokemon(Species::Rattata, 25, vec![Move::Tackle]),
+        ],
+    );
+    request.battle_seed = Some(42); // Earlier states are rebuilt by replaying the seeded turns
+    let battle_id = handler.create_battle(request).await.unwrap().battle_id;
+    for player in ["alice", "bob"] {
+        handler.submit_action(SubmitActionRequest {
+            battle_id,
+            player_id: PlayerId(player.to_string()),
+            action: PlayerAction::UseMove { move_index: 0 },
+            async_resolution: false,
+            authenticated_player_id: None,
+        }).await.unwrap();
+    }
+    let changes = |since_turn| handler.get_battle_changes(GetBattleChangesRequest {
+        battle_id,
+        player_id: PlayerId("alice".to_string()),
+        since_turn,
+    });
+
+    let first_turn = changes(0).await.unwrap();
+    assert_eq!(first_turn.latest_turn, 1);
+    assert!(!first_turn.full_refresh);
+    // Both active Pokemon spent PP or took damage; neither bench moved
+    assert_eq!(first_turn.team.iter().map(|p| p.slot_id).collect::<Vec<_>>(), vec![0]);
+    assert_eq!(first_turn.opponent.iter().map(|p| p.slot_id).collect::<Vec<_>>(), vec![0]);
+    assert!(first_turn.team[0].move_pp.is_some());
+    assert!(first_turn.opponent[0].move_pp.is_none(), "Opponent PP stays hidden");
+
+    let caught_up = changes(first_turn.latest_turn).await.unwrap();
+    assert!(caught_up.team.is_empty() && caught_up.opponent.is_empty());
+}
+
+#[tokio::test]
+async fn test_spectator_view_shows_both_teams_once_finished() {
+    let handler = create_test_handler().unwrap();
+    let battle_id = default_pvp_battle(&handler).await;
+    let spectate = |player: &str| handler.get_spectator_view(GetSpectatorViewRequest {
+        battle_id,
+        player_id: PlayerId(player.to_string()),
+        is_admin: false,
+    });
+
+    let error = spectate("alice").await.unwrap_err();
+    assert!(matches!(error, ApiError::BattleInProgress { .. }), "Participants can't see the opponent's team mid-battle");
+
+    handler.forfeit(ForfeitRequest {
+        battle_id,
+        player_id: PlayerId("alice".to_string()),
+        authenticated_player_id: None,
+    }).await.unwrap();
+
+    let view = spectate("alice").await.unwrap();
+    assert_eq!(view.players.len(), 2);
+    for (player, (species, move_)) in view.players.iter().zip([(Species::Pikachu, Move::Thunderbolt), (Species::Rattata, Move::Tackle)]) {
+        let active = player.team.active_pokemon.as_ref().expect("Both active Pokemon are shown");
+        assert_eq!(active.species, species);
+        let move_view = active.moves[0].as_ref().unwrap();
+        assert_eq!(move_view.move_, move_);
+        assert!(move_view.pp > 0 && move_view.pp == move_view.max_pp, "Unused moves show full PP");
+    }
+    assert_eq!(view.players[1].player_id, PlayerId("bob".to_string()));
+
+    let error = spectate("mallory").await.unwrap_err();
+    assert!(matches!(error, ApiError::PlayerNotInBattle { .. }), "Outsiders still need admin access");
+}
+
+#[tokio::test]
+async fn test_showdown_log_renders_the_finished_battle() {
+    let handler = create_test_handler().unwrap();
+    // Seeded so Thunderbolt's 1/256 miss chance cannot leave the log without damage
+    let mut request = pvp_battle_request(
+        vec![team_pokemon(Species::Pikachu, 10, vec![Move::Thunderbolt])],
+        vec![team_pokemon(Species::Snorlax, 50, vec![Move::Splash])],
+    );
+    request.battle_seed = Some(42);
+    let battle_id = handler.create_battle(request).await.unwrap().battle_id;
+
+    for player in ["alice", "bob"] {
+        handler.submit_action(SubmitActionRequest {
+            battle_id,
+            player_id: PlayerId(player.to_string()),
+            action: PlayerAction::UseMove { move_index: 0 },
+            async_resolution: false,
+            authenticated_player_id: None,
+        }).await.unwrap();
+    }
+    let error = handler.get_showdown_log(battle_id).await.unwrap_err();
+    assert!(matches!(error, ApiError::BattleInProgress { .. }), "Like the result, the log is public only once the battle ends");
+
+    handler.forfeit(ForfeitRequest {
+        battle_id,
+        player_id: PlayerId("alice".to_string()),
+        authenticated_player_id: None,
+    }).await.unwrap();
+
+    let log = handler.get_showdown_log(battle_id).await.unwrap().log;
+    let lines: Vec<&str> = log.lines().collect();
+    assert_eq!(&lines[..6], [
+        "|player|p1|Player alice|", "|player|p2|Player bob|", "|teamsize|p1|1|", "|teamsize|p2|1|", "|gen|1|", "|start|",
+    ]);
+    assert!(lines.contains(&"|switch|p1a: Pikachu|Pikachu, L10|100/100"));
+    assert!(lines.contains(&"|switch|p2a: Snorlax|Snorlax, L50|100/100"));
+    assert!(lines.contains(&"|turn|1|"));
+    assert!(lines.contains(&"|move|p1a: Pikachu|Thunderbolt|p2a: Snorlax"));
+    assert!(lines.contains(&"|move|p2a: Snorlax|Splash|p1a: Pikachu"));
+    let damage = lines.iter().find(|line| line.starts_with("|-damage|p2a: Snorlax|")).expect("Thunderbolt's damage is shown");
+    assert!(damage.ends_with("/100"), "HP is a percentage, as in Showdown's own replays");
+    assert_eq!(lines.last(), Some(&"|win|Player bob|"));
+}
@@ -0,0 +1,411 @@
+use pokemon_adventure::{moves::Move, player::PlayerAction, species::Species};
+use serde_json::{json, Value};
+use crate::api::router::Router;
+use crate::api::routes::{find_route, match_path, Endpoint, ROUTES};
+use crate::tests::common::{create_test_handler, pvp_battle_request, team_pokemon};
+use crate::{PlayerId, SubmitActionRequest};
+
+fn request(method: &str, path: &str) -> Value {
+    json!({
+        "rawPath": path,
+        "requestContext": { "http": { "method": method } },
+    })
+}
+
+#[tokio::test]
+async fn test_unknown_path_returns_http_404() {
+    let router = Router::with_handler(create_test_handler().unwrap(), None);
+
+    let response = router.handle(request("GET", "/no/such/endpoint")).await.unwrap();
+    assert_eq!(response["statusCode"], 404, "The envelope itself should carry the 404");
+
+    let body: Value = serde_json::from_str(response["body"].as_str().unwrap()).unwrap();
+    assert_eq!(body["error"], "NOT_FOUND");
+    assert_eq!(body["status_code"], 404);
+}
+
+#[tokio::test]
+async fn test_known_path_returns_http_200() {
+    let router = Router::with_handler(create_test_handler().unwrap(), None);
+
+    let response = router.handle(request("GET", "/health")).await.unwrap();
+    assert_eq!(response["statusCode"], 200);
+}
+
+#[tokio::test]
+async fn test_root_lists_every_route() {
+    let router = Router::with_handler(create_test_handler().unwrap(), None);
+
+    let response = router.handle(request("GET", "/")).await.unwrap();
+    assert_eq!(response["statusCode"], 200);
+
+    let body: Value = serde_json::from_str(response["body"].as_str().unwrap()).unwrap();
+    let endpoints = body["endpoints"].as_array().unwrap();
+    assert_eq!(endpoints.len(), ROUTES.len());
+    assert!(endpoints.iter().any(|e| e["method"] == "GET" && e["path"] == "/battles/{id}/state"));
+    assert!(endpoints.iter().any(|e| e["path"] == "/admin/battles/{id}/set_state" && e["admin"] == true));
+}
+
+/// A concrete path for a route pattern, filling each parameter with a well-formed value
+fn sample_path(pattern: &str) -> String {
+    pattern.split('/')
+        .map(|segment| match segment {
+            s if s.ends_with(":uuid}") => "0b6f1a4e-3c2d-4e5f-8a9b-1c2d3e4f5a6b",
+            s if s.ends_with(":int}") => "1",
+            s if s.starts_with('{') => "Pikachu",
+            s => s,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[test]
+fn test_each_documented_path_routes_to_its_own_endpoint() {
+    for route in ROUTES {
+        let sample_path = sample_path(route.path);
+        let (matched, _) = find_route(route.method, &sample_path)
+            .unwrap_or_else(|| panic!("{} {} should match a route", route.method, sample_path));
+        assert_eq!(matched.endpoint, route.endpoint, "{} {} is shadowed by an earlier route", route.method, route.path);
+    }
+}
+
+#[test]
+fn test_routes_match_whole_segments_only() {
+    let battle = "0b6f1a4e-3c2d-4e5f-8a9b-1c2d3e4f5a6b";
+
+    let (route, params) = find_route("GET", &format!("/battles/{}/state", battle)).unwrap();
+    assert_eq!(route.endpoint, Endpoint::BattleState);
+    assert_eq!(params.get("id"), Some(battle));
+
+    assert!(find_route("GET", &format!("/battles/{}/state/extra", battle)).is_none(), "Extra segments should not match");
+    assert!(find_route("GET", &format!("/battles/{}/statex", battle)).is_none(), "Partial segments should not match");
+    assert!(find_route("GET", "/matches/").is_none(), "Parameters cannot be empty");
+
+    let params = match_path("/battles/{id:uuid}/turns/{n:int}", &format!("/battles/{}/turns/3", battle)).unwrap();
+    assert_eq!(params.get("id"), Some(battle));
+    assert_eq!(params.get("n"), Some("3"));
+}
+
+#[tokio::test]
+async fn test_adversarial_and_malformed_paths_return_404() {
+    let router = Router::with_handler(create_test_handler().unwrap(), None);
+    let battle = "0b6f1a4e-3c2d-4e5f-8a9b-1c2d3e4f5a6b";
+
+    let malformed = [
+        ("GET", "/battles/valid_actions/state".to_string()), // Keyword as the battle id
+        ("GET", "/battles/state/valid_actions".to_string()),
+        ("POST", "/battles/action/action".to_string()),
+        ("GET", format!("/battles/{}/state/", battle)), // Trailing slash
+        ("GET", format!("/battles/{}/", battle)),
+        ("GET", format!("/battles/{}//state", battle)),
+        ("GET", format!("/battles/{}/turns/latest", battle)), // Non-numeric turn
+        ("DELETE", "/battles/not-a-uuid".to_string()),
+        ("GET", "/health/".to_string()),
+    ];
+    for (method, path) in malformed {
+        let response = router.handle(request(method, &path)).await.unwrap();
+        assert_eq!(response["statusCode"], 404, "{} {} should not route", method, path);
+    }
+}
+
+#[tokio::test]
+async fn test_cors_headers_can_be_disabled() {
+    let with_cors = Router::with_handler(create_test_handler().unwrap(), None);
+    let response = with_cors.handle(request("GET", "/health")).await.unwrap();
+    assert_eq!(response["headers"]["Access-Control-Allow-Origin"], "*");
+
+    let without_cors = Router::with_handler(create_test_handler().unwrap(), None).with_cors(false);
+    let response = without_cors.handle(request("GET", "/health")).await.unwrap();
+    let headers = response["headers"].as_object().unwrap();
+    assert!(headers.keys().all(|name| !name.starts_with("Access-Control-")));
+    assert_eq!(headers["Content-Type"], "application/json");
+}
+
+#[tokio::test]
+async fn test_game_states_match_battle_response_strings() {
+    let router = Router::with_handler(create_test_handler().unwrap(), None);
+
+    let response = router.handle(request("GET", "/game_states")).await.unwrap();
+    assert_eq!(response["statusCode"], 200);
+
+    let body: Value = serde_json::from_str(response["body"].as_str().unwrap()).unwrap();
+    let states = body["states"].as_array().unwrap();
+    let waiting = serde_json::to_value(pokemon_adventure::battle::state::GameState::WaitingForActions).unwrap();
+    let entry = states.iter().find(|s| s["state"] == waiting).expect("WaitingForActions should be listed");
+    assert_eq!(entry["finished"], false);
+    assert!(!entry["description"].as_str().unwrap().is_empty());
+    assert_eq!(states.iter().filter(|s| s["finished"] == true).count(), 3, "Two wins and a draw end the battle");
+}
+
+fn post(path: &str, body: Value) -> Value {
+    json!({
+        "rawPath": path,
+        "requestContext": { "http": { "method": "POST" } },
+        "body": body.to_string(),
+    })
+}
+
+fn response_body(response: &Value) -> Value {
+    serde_json::from_str(response["body"].as_str().unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn test_custom_battle_is_created_through_the_router() {
+    let router = Router::with_handler(create_test_handler().unwrap(), None);
+    let team = |species: &str, moves: Value| json!([{ "species": species, "level": 25, "moves": moves }]);
+
+    let response = router.handle(post("/battles/custom", json!({
+        "player1_id": "alice",
+        "player2_id": "bob",
+        "player1_team": team("Pikachu", json!(["Thunderbolt", "QuickAttack"])),
+        "player2_team": team("Rattata", json!(["Tackle"])),
+    }))).await.unwrap();
+    assert_eq!(response["statusCode"], 200);
+    let battle_id = response_body(&response)["battle_id"].as_str().unwrap().to_string();
+
+    for (player, species) in [("alice", "Pikachu"), ("bob", "Rattata")] {
+        let mut state_request = request("GET", &format!("/battles/{}/state", battle_id));
+        state_request["queryStringParameters"] = json!({ "player_id": player });
+        let state = router.handle(state_request).await.unwrap();
+        assert_eq!(state["statusCode"], 200, "{} can read the battle", player);
+        assert_eq!(response_body(&state)["player_team"]["active_pokemon"]["species"], species);
+    }
+
+    let invalid = router.handle(post("/battles/custom", json!({
+        "player1_id": "alice",
+        "player2_id": "bob",
+        "player1_team": team("Pikachu", json!(["Thunderbolt", "Thunderbolt"])),
+        "player2_team": team("Rattata", json!(["Tackle"])),
+    }))).await.unwrap();
+    assert_eq!(invalid["statusCode"], 400, "Team validation errors are client errors");
+    assert_eq!(response_body(&invalid)["error"], "VALIDATION_ERROR");
+}
+
+#[tokio::test]
+async fn test_handler_errors_carry_their_status_code() {
+    let router = Router::with_handler(create_test_handler().unwrap(), None);
+    let created = router.handle(post("/battles/custom", json!({
+        "player1_id": "alice",
+        "player2_id": "bob",
+        "player1_team": [{ "species": "Pikachu", "level": 25, "moves": ["Thunderbolt"] }],
+        "player2_team": [{ "species": "Rattata", "level": 25, "moves": ["Tackle"] }],
+    }))).await.unwrap();
+    let battle_id = response_body(&created)["battle_id"].as_str().unwrap().to_string();
+    let get_state = |battle_id: &str, player: &str| {
+        let mut state_request = request("GET", &format!("/battles/{}/state", battle_id));
+        state_request["queryStringParameters"] = json!({ "player_id": player });
+        state_request
+    };
+
+    let missing = router.handle(get_state("0b6f1a4e-3c2d-4e5f-8a9b-1c2d3e4f5a6b", "alice")).await.unwrap();
+    assert_eq!(missing["statusCode"], 404);
+    assert_eq!(response_body(&missing)["error"], "BATTLE_NOT_FOUND");
+
+    let stranger = router.handle(get_state(&battle_id, "mallory")).await.unwrap();
+    assert_eq!(stranger["statusCode"], 403);
+    assert_eq!(response_body(&stranger)["status_code"], 403);
+    let message = response_body(&stranger)["message"].as_str().unwrap().to_string();
+    assert!(message.contains("al***, bo***") && !message.contains("alice"), "Strangers only see redacted ids, got: {}", message);
+
+    let mut participant = get_state(&battle_id, "mallory");
+    participant["headers"] = json!({ "x-player-id": "alice" });
+    let message = response_body(&router.handle(participant).await.unwrap())["message"].as_str().unwrap().to_string();
+    assert!(message.contains("alice, bob"), "A participant sees the full ids, got: {}", message);
+
+    let invalid = router.handle(post(&format!("/battles/{}/action", battle_id), json!({
+        "battle_id": battle_id,
+        "player_id": "alice",
+        "action": { "UseMove": { "move_index": 7 } },
+    }))).await.unwrap();
+    assert_eq!(invalid["statusCode"], 400);
+    assert_eq!(response_body(&invalid)["error"], "INVALID_ACTION");
+
+    let malformed = router.handle(post(&format!("/battles/{}/action", battle_id), json!({ "player_id": "alice" }))).await.unwrap();
+    assert_eq!(malformed["statusCode"], 400, "An unparseable body is the client's fault");
+}
+
+#[tokio::test]
+async fn test_delete_battle_status_codes() {
+    let router = Router::with_handler(create_test_handler().unwrap(), None);
+    let created = router.handle(post("/battles/custom", json!({
+        "player1_id": "alice",
+        "player2_id": "bob",
+        "player1_team": [{ "species": "Pikachu", "level": 25, "moves": ["Thunderbolt"] }],
+        "player2_team": [{ "species": "Rattata", "level": 25, "moves": ["Tackle"] }],
+    }))).await.unwrap();
+    let battle_id = response_body(&created)["battle_id"].as_str().unwrap().to_string();
+    let delete = |player: &str| {
+        let mut delete_request = request("DELETE", &format!("/battles/{}", battle_id));
+        delete_request["queryStringParameters"] = json!({ "player_id": player });
+        delete_request
+    };
+
+    let unnamed = request("DELETE", &format!("/battles/{}", battle_id));
+    assert_eq!(router.handle(unnamed).await.unwrap()["statusCode"], 400, "A bare delete must not default to the MVP player");
+    let mut impersonated = delete("bob");
+    impersonated["headers"] = json!({ "x-player-id": "mallory" });
+    assert_eq!(router.handle(impersonated).await.unwrap()["statusCode"], 403);
+
+    assert_eq!(router.handle(delete("mallory")).await.unwrap()["statusCode"], 403);
+    assert_eq!(router.handle(delete("bob")).await.unwrap()["statusCode"], 204);
+    assert_eq!(router.handle(delete("bob")).await.unwrap()["statusCode"], 404, "Deleting twice finds nothing the second time");
+}
+
+#[tokio::test]
+async fn test_optional_fields_accept_null_missing_and_empty() {
+    let router = Router::with_handler(create_test_handler().unwrap(), None);
+    let created = router.handle(post("/battles/custom", json!({
+        "player1_id": "alice",
+        "player2_id": "bob",
+        "player1_team": [{ "species": "Pikachu", "level": 25, "moves": ["Thunderbolt"], "nickname": null }],
+        "player2_team": [{ "species": "Rattata", "level": 25, "moves": ["Tackle"] }],
+        "switch_uses_turn": null,
+        "level_budget": null,
+        "observer_for": null,
+    }))).await.unwrap();
+    assert_eq!(created["statusCode"], 200, "Explicit nulls read as absent fields");
+    let battle_id = response_body(&created)["battle_id"].as_str().unwrap().to_string();
+    let events = |last_turns: Option<&str>| {
+        let mut events_request = request("GET", &format!("/battles/{}/events", battle_id));
+        events_request["queryStringParameters"] = match last_turns {
+            Some(value) => json!({ "player_id": "alice", "last_turns": value }),
+            None => json!({ "player_id": "alice" }),
+        };
+        events_request
+    };
+
+    assert_eq!(router.handle(events(None)).await.unwrap()["statusCode"], 200);
+    assert_eq!(router.handle(events(Some(""))).await.unwrap()["statusCode"], 200, "An empty value counts as absent");
+    assert_eq!(router.handle(events(Some("2"))).await.unwrap()["statusCode"], 200);
+
+    let malformed = router.handle(events(Some("two"))).await.unwrap();
+    assert_eq!(malformed["statusCode"], 400, "A malformed value is rejected rather than ignored");
+    assert_eq!(response_body(&malformed)["error"], "BAD_REQUEST");
+}
+
+#[tokio::test]
+async fn test_pokedex_routes_return_404_for_unknown_names() {
+    let router = Router::with_handler(create_test_handler().unwrap(), None);
+
+    let venusaur = router.handle(request("GET", "/species/venusaur")).await.unwrap();
+    assert_eq!(venusaur["statusCode"], 200);
+    assert_eq!(response_body(&venusaur)["base_stats"]["hp"], 80);
+
+    let tackle = router.handle(request("GET", "/moves/Tackle")).await.unwrap();
+    assert_eq!(response_body(&tackle)["max_pp"], 35);
+
+    let missing_species = router.handle(request("GET", "/species/Missingno")).await.unwrap();
+    assert_eq!(missing_species["statusCode"], 404);
+    assert_eq!(response_body(&missing_species)["error"], "SPECIES_NOT_FOUND");
+    let missing_learnset = router.handle(request("GET", "/species/Missingno/moves")).await.unwrap();
+    assert_eq!(missing_learnset["statusCode"], 404, "Both species endpoints agree on an unknown species");
+    assert_eq!(response_body(&missing_learnset)["error"], "SPECIES_NOT_FOUND");
+
+    let missing_move = router.handle(request("GET", "/moves/Splash2")).await.unwrap();
+    assert_eq!(missing_move["statusCode"], 404);
+    assert_eq!(response_body(&missing_move)["error"], "MOVE_NOT_FOUND");
+}
+
+#[tokio::test]
+async fn test_state_is_sent_as_msgpack_when_accepted() {
+    use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+
+    let router = Router::with_handler(create_test_handler().unwrap(), None);
+    let created = router.handle(post("/battles/custom", json!({
+        "player1_id": "alice",
+        "player2_id": "bob",
+        "player1_team": [{ "species": "Pikachu", "level": 25, "moves": ["Thunderbolt"] }],
+        "player2_team": [{ "species": "Rattata", "level": 25, "moves": ["Tackle"] }],
+    }))).await.unwrap();
+    let battle_id = response_body(&created)["battle_id"].as_str().unwrap().to_string();
+    let get_state = |accept: &str| {
+        let mut state_request = request("GET", &format!("/battles/{}/state", battle_id));
+        state_request["queryStringParameters"] = json!({ "player_id": "alice" });
+        state_request["headers"] = json!({ "accept": accept });
+        state_request
+    };
+
+    let as_json = router.handle(get_state("application/json")).await.unwrap();
+    let as_msgpack = router.handle(get_state("application/msgpack, application/json;q=0.5")).await.unwrap();
+    assert_eq!(as_msgpack["statusCode"], 200);
+    assert_eq!(as_msgpack["headers"]["Content-Type"], "application/msgpack");
+    assert_eq!(as_msgpack["isBase64Encoded"], true);
+
+    let bytes = BASE64.decode(as_msgpack["body"].as_str().unwrap()).unwrap();
+    let decoded: Value = rmp_serde::from_slice(&bytes).unwrap();
+    assert_eq!(decoded, response_body(&as_json), "Both encodings carry the same response");
+    assert!(bytes.len() < as_json["body"].as_str().unwrap().len());
+
+    // Endpoints outside the verbose state/events set keep answering in JSON
+    let mut teams = request("GET", "/available_teams");
+    teams["headers"] = json!({ "accept": "application/msgpack" });
+    assert_eq!(router.handle(teams).await.unwrap()["headers"]["Content-Type"], "application/json");
+}
+
+#[tokio::test]
+async fn test_playout_is_gated_and_deterministic() {
+    let playout = || post("/test/playout", json!({
+        "team_id": "charizard_team",
+        "opponent_id": "gym_leader_easy",
+        "opponent_team_id": "venusaur_team",
+        "seed": 7,
+    }));
+
+    let disabled = Router::with_handler(create_test_handler().unwrap(), None);
+    assert_eq!(disabled.handle(playout()).await.unwrap()["statusCode"], 404);
+
+    let router = Router::with_handler(create_test_handler().unwrap(), None).with_test_endpoints(true);
+    let play = || async {
+        let response = router.handle(playout()).await.unwrap();
+        assert_eq!(response["statusCode"], 200);
+        let mut body = response_body(&response);
+        // Only the wall-clock time of processing may differ between runs
+        for log in body["turn_logs"].as_array_mut().unwrap() {
+            log["timestamp"] = json!(0);
+        }
+        body
+    };
+
+    let first = play().await;
+    let waiting = serde_json::to_value(pokemon_adventure::battle::state::GameState::WaitingForActions).unwrap();
+    assert_ne!(first["result"], waiting, "The battle is played to the end");
+    assert!(first["turn_count"].as_u64().unwrap() > 0);
+    assert_eq!(first, play().await, "The same seed plays out identically");
+}
+
+#[tokio::test]
+async fn test_last_turns_is_validated_and_clamped() {
+    let handler = create_test_handler().unwrap();
+    let battle_id = handler.create_battle(pvp_battle_request(
+        vec![team_pokemon(Species::Pikachu, 25, vec![Move::QuickAttack])],
+        vec![team_pokemon(Species::Rattata, 25, vec![Move::Tackle])],
+    )).await.unwrap().battle_id;
+    for player in ["alice", "bob"] {
+        handler.submit_action(SubmitActionRequest {
+            battle_id,
+            player_id: PlayerId(player.to_string()),
+            action: PlayerAction::UseMove { move_index: 0 },
+            async_resolution: false,
+            authenticated_player_id: None,
+        }).await.unwrap();
+    }
+    let router = Router::with_handler(handler, None);
+    let events = |last_turns: &str| {
+        let mut events_request = request("GET", &format!("/battles/{}/events", battle_id));
+        events_request["queryStringParameters"] = json!({ "player_id": "alice", "last_turns": last_turns });
+        router.handle(events_request)
+    };
+
+    for malformed in ["abc", "-1"] {
+        let response = events(malformed).await.unwrap();
+        assert_eq!(response["statusCode"], 400, "last_turns={} is rejected", malformed);
+        assert_eq!(response_body(&response)["error"], "BAD_REQUEST");
+    }
+
+    let none = events("0").await.unwrap();
+    assert_eq!(response_body(&none)["turn_logs"].as_array().unwrap().len(), 0);
+
+    let all = events("999999").await.unwrap();
+    assert_eq!(all["statusCode"], 200);
+    assert_eq!(response_body(&all)["turn_logs"].as_array().unwrap().len(), 1, "Clamped to the one turn logged");
+}
@@ -0,0 +1,50 @@
+use pokemon_adventure::moves::Move;
+use serde_json::json;
+use crate::{ApiMoveView, CreateMvpBattleRequest, SubmitActionRequest};
+
+
+#[test]
+fn test_move_view_wire_shape() {
+    let move_view = ApiMoveView {
+        move_: Move::Tackle,
+        pp: 30,
+        max_pp: 35,
+        priority: 0,
+    };
+
+    let value = serde_json::to_value(&move_view).unwrap();
+    assert_eq!(value, json!({ "move": "Tackle", "pp": 30, "max_pp": 35, "priority": 0 }));
+
+    let round_trip: ApiMoveView = serde_json::from_value(value).unwrap();
+    assert_eq!(round_trip.move_, Move::Tackle);
+}
+
+#[test]
+fn test_request_nulls_read_as_absent() {
+    let explicit_null: SubmitActionRequest = serde_json::from_value(json!({
+        "battle_id": "0b6f1a4e-3c2d-4e5f-8a9b-1c2d3e4f5a6b",
+        "player_id": "alice",
+        "action": "Forfeit",
+        "async_resolution": null,
+    })).unwrap();
+    assert!(!explicit_null.async_resolution);
+
+    let mvp: CreateMvpBattleRequest = serde_json::from_value(json!({
+        "player_name": "alice",
+        "team_id": "starter",
+        "opponent_id": "gym_leader",
+        "opponent_team_id": null,
+        "switch_uses_turn": null,
+        "grant_observer_token": null,
+    })).unwrap();
+    assert_eq!(mvp.opponent_team_id, None);
+    assert!(!mvp.switch_uses_turn && !mvp.grant_observer_token);
+
+    let malformed = serde_json::from_value::<SubmitActionRequest>(json!({
+        "battle_id": "0b6f1a4e-3c2d-4e5f-8a9b-1c2d3e4f5a6b",
+        "player_id": "alice",
+        "action": "Forfeit",
+        "async_resolution": "yes",
+    }));
+    assert!(malformed.is_err(), "Null is the only stand-in for an absent field");
+}
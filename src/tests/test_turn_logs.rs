@@ -0,0 +1,388 @@
+use pokemon_adventure::player::PlayerAction;
+use std::sync::Arc;
+use crate::tests::common::{create_test_handler, default_pvp_battle};
+use crate::database::{Db, InMemoryDb};
+use crate::{
+    ApiError, BatchEventsRequest, BattleEventDto, BattleHandler, BattleId, CreateMvpBattleRequest, GetBattleEventsRequest,
+    GetBattleEventsResponse, GetTurnLogRequest, PlayerId, SubmitActionRequest, TurnLog,
+};
+
+
+#[tokio::test]
+async fn test_get_turn_log_by_number() {
+    let handler = create_test_handler().unwrap();
+    let create_request = CreateMvpBattleRequest {
+        player_name: "Test Trainer".to_string(),
+        team_id: "charizard_team".to_string(),
+        opponent_id: "gym_leader_easy".to_string(),
+        opponent_team_id: None,
+        opponent_level_cap: None,
+        switch_uses_turn: false,
+        grant_observer_token: false,
+        battle_seed: None,
+    };
+    let battle_id = handler.create_mvp_battle(create_request).await.unwrap().battle_id;
+    let player_id = PlayerId("player_1".to_string());
+
+    handler.submit_action(SubmitActionRequest {
+        battle_id,
+        player_id: player_id.clone(),
+        action: PlayerAction::UseMove { move_index: 0 },
+        async_resolution: false,
+        authenticated_player_id: None,
+    }).await.unwrap();
+
+    let events_response = handler.get_battle_events(GetBattleEventsRequest {
+        battle_id,
+        player_id: player_id.clone(),
+        last_turns: None,
+        since_turn: None,
+        event_types: None,
+        include_markers: false,
+        offset: None,
+        limit: None,
+    }).await.unwrap();
+    let logged = &events_response.turn_logs[0];
+
+    let turn_response = handler.get_turn_log(GetTurnLogRequest {
+        battle_id,
+        player_id: player_id.clone(),
+        turn_number: logged.turn_number,
+    }).await.unwrap();
+    assert_eq!(turn_response.turn_log.turn_number, logged.turn_number);
+    assert_eq!(turn_response.turn_log.events, logged.events);
+
+    let missing = handler.get_turn_log(GetTurnLogRequest {
+        battle_id,
+        player_id,
+        turn_number: 999,
+    }).await;
+    assert!(matches!(missing, Err(ApiError::TurnNotFound { turn_number: 999, .. })));
+}
+
+#[tokio::test]
+async fn test_event_type_filtering() {
+    let handler = create_test_handler().unwrap();
+    let create_request = CreateMvpBattleRequest {
+        player_name: "Test Trainer".to_string(),
+        team_id: "charizard_team".to_string(),
+        opponent_id: "gym_leader_easy".to_string(),
+        opponent_team_id: None,
+        opponent_level_cap: None,
+        switch_uses_turn: false,
+        grant_observer_token: false,
+        battle_seed: None,
+    };
+    let battle_id = handler.create_mvp_battle(create_request).await.unwrap().battle_id;
+    let player_id = PlayerId("player_1".to_string());
+
+    handler.submit_action(SubmitActionRequest {
+        battle_id,
+        player_id: player_id.clone(),
+        action: PlayerAction::UseMove { move_index: 0 },
+        async_resolution: false,
+        authenticated_player_id: None,
+    }).await.unwrap();
+
+    let filtered = handler.get_battle_events(GetBattleEventsRequest {
+        battle_id,
+        player_id: player_id.clone(),
+        last_turns: None,
+        since_turn: None,
+        event_types: Some(vec!["damage".to_string(), "faint".to_string()]),
+        include_markers: false,
+        offset: None,
+        limit: None,
+    }).await.unwrap();
+    assert!(filtered.turn_logs.iter()
+        .flat_map(|log| &log.structured_events)
+        .all(|e| matches!(e.kind(), "damage" | "faint")));
+    assert!(filtered.turn_logs.iter().all(|log| log.events.len() == log.structured_events.len()));
+
+    let unknown = handler.get_battle_events(GetBattleEventsRequest {
+        battle_id,
+        player_id,
+        last_turns: None,
+        since_turn: None,
+        event_types: Some(vec!["explosions".to_string()]),
+        include_markers: false,
+        offset: None,
+        limit: None,
+    }).await;
+    assert!(matches!(unknown, Err(ApiError::BadRequest { .. })));
+}
+
+#[tokio::test]
+async fn test_narrative_and_structured_events_are_index_aligned() {
+    let handler = create_test_handler().unwrap();
+    let create_request = CreateMvpBattleRequest {
+        player_name: "Test Trainer".to_string(),
+        team_id: "charizard_team".to_string(),
+        opponent_id: "gym_leader_easy".to_string(),
+        opponent_team_id: None,
+        opponent_level_cap: None,
+        switch_uses_turn: false,
+        grant_observer_token: false,
+        battle_seed: None,
+    };
+    let battle_id = handler.create_mvp_battle(create_request).await.unwrap().battle_id;
+    let player_id = PlayerId("player_1".to_string());
+
+    for _ in 0..3 {
+        let response = handler.submit_action(SubmitActionRequest {
+            battle_id,
+            player_id: player_id.clone(),
+            action: PlayerAction::UseMove { move_index: 0 },
+            async_resolution: false,
+            authenticated_player_id: None,
+        }).await.unwrap();
+        if !response.can_act {
+            break;
+        }
+    }
+
+    let events_response = handler.get_battle_events(GetBattleEventsRequest {
+        battle_id,
+        player_id,
+        last_turns: None,
+        since_turn: None,
+        event_types: None,
+        include_markers: false,
+        offset: None,
+        limit: None,
+    }).await.unwrap();
+    assert!(!events_response.turn_logs.is_empty());
+    for log in &events_response.turn_logs {
+        assert_eq!(
+            log.events.len(), log.structured_events.len(),
+            "Turn {} narrative and structured events should pair up", log.turn_number
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_pacing_markers_are_opt_in() {
+    let handler = create_test_handler().unwrap();
+    let create_request = CreateMvpBattleRequest {
+        player_name: "Test Trainer".to_string(),
+        team_id: "charizard_team".to_string(),
+        opponent_id: "gym_leader_easy".to_string(),
+        opponent_team_id: None,
+        opponent_level_cap: None,
+        switch_uses_turn: false,
+        grant_observer_token: false,
+        battle_seed: None,
+    };
+    let battle_id = handler.create_mvp_battle(create_request).await.unwrap().battle_id;
+    let player_id = PlayerId("player_1".to_string());
+
+    handler.submit_action(SubmitActionRequest {
+        battle_id,
+        player_id: player_id.clone(),
+        action: PlayerAction::UseMove { move_index: 0 },
+        async_resolution: false,
+        authenticated_player_id: None,
+    }).await.unwrap();
+
+    let plain = handler.get_battle_events(GetBattleEventsRequest {
+        battle_id,
+        player_id: player_id.clone(),
+        last_turns: None,
+        since_turn: None,
+        event_types: None,
+        include_markers: false,
+        offset: None,
+        limit: None,
+    }).await.unwrap();
+    assert!(plain.turn_logs.iter().flat_map(|log| &log.structured_events).all(|e| !e.is_marker()));
+    assert!(plain.turn_logs.iter().flat_map(|log| &log.events).all(|e| !e.is_empty()));
+
+    let paced = handler.get_battle_events(GetBattleEventsRequest {
+        battle_id,
+        player_id,
+        last_turns: None,
+        since_turn: None,
+        event_types: None,
+        include_markers: true,
+        offset: None,
+        limit: None,
+    }).await.unwrap();
+    let last_log = paced.turn_logs.last().unwrap();
+    assert!(matches!(last_log.structured_events.last(), Some(BattleEventDto::TurnBoundary { .. })));
+    assert_eq!(last_log.events.len(), last_log.structured_events.len());
+}
+
+#[tokio::test]
+async fn test_since_turn_returns_only_missed_turns() {
+    let handler = create_test_handler().unwrap();
+    let create_request = CreateMvpBattleRequest {
+        player_name: "Test Trainer".to_string(),
+        team_id: "charizard_team".to_string(),
+        opponent_id: "gym_leader_easy".to_string(),
+        opponent_team_id: None,
+        opponent_level_cap: None,
+        switch_uses_turn: false,
+        grant_observer_token: false,
+        battle_seed: None,
+    };
+    let battle_id = handler.create_mvp_battle(create_request).await.unwrap().battle_id;
+    let player_id = PlayerId("player_1".to_string());
+
+    for _ in 0..2 {
+        let response = handler.submit_action(SubmitActionRequest {
+            battle_id,
+            player_id: player_id.clone(),
+            action: PlayerAction::UseMove { move_index: 0 },
+            async_resolution: false,
+            authenticated_player_id: None,
+        }).await.unwrap();
+        if !response.can_act {
+            break;
+        }
+    }
+
+    let all = handler.get_battle_events(GetBattleEventsRequest {
+        battle_id,
+        player_id: player_id.clone(),
+        last_turns: None,
+        since_turn: None,
+        event_types: None,
+        include_markers: false,
+        offset: None,
+        limit: None,
+    }).await.unwrap();
+    let first_turn = all.turn_logs[0].turn_number;
+    assert_eq!(all.last_resolved_turn, all.turn_logs.last().map(|log| log.turn_number));
+
+    // A client that only saw the first turn catches up on everything after it
+    let missed = handler.get_battle_events(GetBattleEventsRequest {
+        battle_id,
+        player_id,
+        last_turns: None,
+        since_turn: Some(first_turn),
+        event_types: None,
+        include_markers: false,
+        offset: None,
+        limit: None,
+    }).await.unwrap();
+    assert_eq!(missed.turn_logs.len(), all.turn_logs.len() - 1);
+    assert!(missed.turn_logs.iter().all(|log| log.turn_number > first_turn));
+}
+
+#[tokio::test]
+async fn test_batch_events_match_single_battle_events() {
+    let handler = create_test_handler().unwrap();
+    let player_id = PlayerId("player_1".to_string());
+
+    let mut battle_ids = Vec::new();
+    for team_id in ["venusaur_team", "charizard_team"] {
+        let battle_id = handler.create_mvp_battle(CreateMvpBattleRequest {
+            player_name: "Test Trainer".to_string(),
+            team_id: team_id.to_string(),
+            opponent_id: "gym_leader_easy".to_string(),
+            opponent_team_id: None,
+            opponent_level_cap: None,
+            switch_uses_turn: false,
+            grant_observer_token: false,
+            battle_seed: None,
+        }).await.unwrap().battle_id;
+        handler.submit_action(SubmitActionRequest {
+            battle_id,
+            player_id: player_id.clone(),
+            action: PlayerAction::UseMove { move_index: 0 },
+            async_resolution: false,
+            authenticated_player_id: None,
+        }).await.unwrap();
+        battle_ids.push(battle_id);
+    }
+
+    let batch = handler.get_batch_events(BatchEventsRequest {
+        battle_ids: battle_ids.clone(),
+        player_id: player_id.clone(),
+        last_turns: Some(1),
+    }).await.unwrap();
+    assert_eq!(batch.battles.iter().map(|b| b.battle_id).collect::<Vec<_>>(), battle_ids, "Results follow request order");
+
+    for response in &batch.battles {
+        let single = handler.get_battle_events(GetBattleEventsRequest {
+            battle_id: response.battle_id,
+            player_id: player_id.clone(),
+            last_turns: Some(1),
+            since_turn: None,
+            event_types: None,
+            include_markers: false,
+            offset: None,
+            limit: None,
+        }).await.unwrap();
+        assert_eq!(response.turn_logs.len(), single.turn_logs.len());
+        assert_eq!(response.turn_logs[0].events, single.turn_logs[0].events);
+    }
+
+    let outsider = handler.get_batch_events(BatchEventsRequest {
+        battle_ids: battle_ids.clone(),
+        player_id: PlayerId("intruder".to_string()),
+        last_turns: None,
+    }).await;
+    assert!(matches!(outsider, Err(ApiError::PlayerNotInBattle { .. })), "Per-battle authorization still applies");
+
+    let missing = handler.get_batch_events(BatchEventsRequest {
+        battle_ids: vec![battle_ids[0], BattleId::new()],
+        player_id,
+        last_turns: None,
+    }).await;
+    assert!(matches!(missing, Err(ApiError::BattleNotFound { .. })));
+}
+
+#[tokio::test]
+async fn test_battle_events_paginate_with_offset_and_limit() {
+    let db = Arc::new(InMemoryDb::new());
+    let handler = BattleHandler::new(db.clone());
+    let battle_id = default_pvp_battle(&handler).await;
+
+    // Five logged turns, without having to play them out
+    let mut stored = db.get_battle(battle_id).await.unwrap().unwrap();
+    stored.turn_logs = (1..=5).map(|turn_number| TurnLog {
+        turn_number,
+        events: vec![format!("Turn {}", turn_number)],
+        structured_events: vec![BattleEventDto::Other],
+        private_events: Default::default(),
+        resolutions: Vec::new(),
+        timestamp: 0,
+    }).collect();
+    db.update_battle(&stored).await.unwrap();
+
+    let page = |offset: Option<usize>, limit: Option<usize>, last_turns: Option<u32>| handler.get_battle_events(GetBattleEventsRequest {
+        battle_id,
+        player_id: PlayerId("alice".to_string()),
+        last_turns,
+        since_turn: None,
+        event_types: None,
+        include_markers: false,
+        offset,
+        limit,
+    });
+    let turns = |response: &GetBattleEventsResponse| response.turn_logs.iter().map(|log| log.turn_number).collect::<Vec<_>>();
+
+    let first = page(None, Some(2), None).await.unwrap();
+    assert_eq!(turns(&first), vec![1, 2]);
+    assert!(first.has_more);
+    assert_eq!(first.next_offset, Some(2));
+
+    let middle = page(first.next_offset, Some(2), None).await.unwrap();
+    assert_eq!(turns(&middle), vec![3, 4]);
+    assert_eq!(middle.next_offset, Some(4));
+
+    let last = page(middle.next_offset, Some(2), None).await.unwrap();
+    assert_eq!(turns(&last), vec![5], "The last page may be short");
+    assert!(!last.has_more);
+    assert_eq!(last.next_offset, None);
+
+    let past_the_end = page(Some(10), Some(2), None).await.unwrap();
+    assert!(past_the_end.turn_logs.is_empty() && !past_the_end.has_more);
+
+    let legacy = page(None, None, Some(2)).await.unwrap();
+    assert_eq!(turns(&legacy), vec![4, 5], "last_turns keeps working on its own");
+
+    let mixed = page(Some(0), Some(2), Some(2)).await.unwrap_err();
+    assert!(matches!(mixed, ApiError::BadRequest { .. }), "last_turns cannot be combined with offset/limit");
+}
@@ -0,0 +1,359 @@
+use pokemon_adventure::{moves::Move, player::PlayerAction, species::Species};
+use crate::tests::common::{create_test_handler, default_pvp_battle, default_pvp_request, pvp_battle_request, team_pokemon};
+use serde_json::json;
+use crate::{
+    ApiError, CreateBattleRequest, CreateMvpBattleRequest, GetBattleStateRequest, PlayerId,
+    SetBattleStateRequest, SubmitActionRequest, TeamError, TeamPokemon, TeamValidationRequest,
+};
+
+
+#[tokio::test]
+async fn test_unknown_player_error_lists_participants() {
+    let handler = create_test_handler().unwrap();
+    let create_request = CreateMvpBattleRequest {
+        player_name: "Test Trainer".to_string(),
+        team_id: "venusaur_team".to_string(),
+        opponent_id: "gym_leader_easy".to_string(),
+        opponent_team_id: None,
+        opponent_level_cap: None,
+        switch_uses_turn: false,
+        grant_observer_token: false,
+        battle_seed: None,
+    };
+    let battle_id = handler.create_mvp_battle(create_request).await.unwrap().battle_id;
+
+    let result = handler.submit_action(SubmitActionRequest {
+        battle_id,
+        player_id: PlayerId("bob".to_string()),
+        action: PlayerAction::UseMove { move_index: 0 },
+        async_resolution: false,
+        authenticated_player_id: None,
+    }).await;
+
+    let error = result.unwrap_err();
+    assert!(matches!(error, ApiError::PlayerNotInBattle { .. }));
+    assert_eq!(error.status_code(), 403);
+    assert!(
+        error.to_string().starts_with("Player 'bob' is not in this battle (participants: player_1, "),
+        "Error should name the requesting id and both participants, got: {}", error
+    );
+}
+
+#[tokio::test]
+async fn test_duplicate_moves_are_rejected() {
+    let handler = create_test_handler().unwrap();
+    let request = CreateBattleRequest {
+        player1_id: PlayerId("alice".to_string()),
+        player2_id: PlayerId("bob".to_string()),
+        player1_team: vec![team_pokemon(Species::Pikachu, 25, vec![Move::Thunderbolt, Move::QuickAttack, Move::Thunderbolt])],
+        player2_team: vec![team_pokemon(Species::Rattata, 25, vec![Move::Tackle])],
+        switch_uses_turn: false,
+        level_budget: None,
+        observer_for: None,
+        battle_seed: None,
+        allow_illegal_moves: false,
+    };
+
+    let error = handler.create_battle(request).await.unwrap_err();
+    assert!(matches!(error, ApiError::ValidationError { .. }));
+    assert!(error.to_string().contains("Duplicate move Thunderbolt in slot 2"), "got: {}", error);
+}
+
+#[tokio::test]
+async fn test_switch_uses_turn_rejects_voluntary_switches() {
+    let handler = create_test_handler().unwrap();
+    let create_request = CreateMvpBattleRequest {
+        player_name: "Test Trainer".to_string(),
+        team_id: "venusaur_team".to_string(),
+        opponent_id: "gym_leader_easy".to_string(),
+        opponent_team_id: None,
+        opponent_level_cap: None,
+        switch_uses_turn: true,
+        grant_observer_token: false,
+        battle_seed: None,
+    };
+    let create_response = handler.create_mvp_battle(create_request).await.unwrap();
+    assert!(
+        !create_response.valid_actions.iter().any(|a| matches!(a.action, PlayerAction::SwitchPokemon { .. })),
+        "Voluntary switches should not be offered"
+    );
+
+    let result = handler.submit_action(SubmitActionRequest {
+        battle_id: create_response.battle_id,
+        player_id: PlayerId("player_1".to_string()),
+        action: PlayerAction::SwitchPokemon { team_index: 1 },
+        async_resolution: false,
+        authenticated_player_id: None,
+    }).await;
+    assert!(matches!(result, Err(ApiError::InvalidAction { .. })));
+}
+
+#[tokio::test]
+async fn test_level_budget_is_enforced() {
+    let handler = create_test_handler().unwrap();
+    let mut request = pvp_battle_request(
+        vec![
+            team_pokemon(Species::Pikachu, 60, vec![Move::Thunderbolt]),
+            team_pokemon(Species::Rattata, 50, vec![Move::Tackle]),
+        ],
+        vec![team_pokemon(Species::Rattata, 50, vec![Move::Tackle])],
+    );
+    request.level_budget = Some(100);
+
+    let error = handler.create_battle(request).await.unwrap_err();
+    assert!(matches!(error, ApiError::ValidationError { .. }));
+    assert!(error.to_string().contains("Team level total 110 exceeds level budget 100"), "got: {}", error);
+}
+
+#[tokio::test]
+async fn test_action_rejected_when_body_player_differs_from_authenticated_identity() {
+    let handler = create_test_handler().unwrap();
+    let battle_id = default_pvp_battle(&handler).await;
+
+    let result = handler.submit_action(SubmitActionRequest {
+        battle_id,
+        player_id: PlayerId("alice".to_string()),
+        action: PlayerAction::UseMove { move_index: 0 },
+        async_resolution: false,
+        authenticated_player_id: Some(PlayerId("bob".to_string())),
+    }).await;
+
+    let error = result.unwrap_err();
+    assert!(matches!(error, ApiError::PlayerNotAuthorized { .. }));
+    assert_eq!(error.status_code(), 403);
+}
+
+#[tokio::test]
+async fn test_admin_state_patch_is_applied_and_validated() {
+    let handler = create_test_handler().unwrap();
+    let battle_id = default_pvp_battle(&handler).await;
+
+    let patched = handler.set_battle_state(SetBattleStateRequest {
+        battle_id,
+        patch: json!({ "turn_number": 7 }),
+    }).await.unwrap();
+    assert_eq!(patched.turn_number, 7);
+    let state = handler.get_battle_state(GetBattleStateRequest {
+        battle_id,
+        player_id: PlayerId("alice".to_string()),
+        strict_info: false,
+        debug: false,
+        is_admin: false,
+        observer_token: None,
+    }).await.unwrap();
+    assert_eq!(state.turn_number, 7, "The patched state should be persisted");
+
+    let rename = handler.set_battle_state(SetBattleStateRequest {
+        battle_id,
+        patch: json!({ "players": { "0": { "player_id": "mallory" } } }),
+    }).await;
+    assert!(matches!(rename, Err(ApiError::ValidationError { .. })), "Player identity must not be patchable");
+
+    let malformed = handler.set_battle_state(SetBattleStateRequest {
+        battle_id,
+        patch: json!({ "turn_number": "soon" }),
+    }).await;
+    assert!(matches!(malformed, Err(ApiError::ValidationError { .. })));
+
+    let out_of_range = handler.set_battle_state(SetBattleStateRequest {
+        battle_id,
+        patch: json!({ "players": { "5": {} } }),
+    }).await;
+    assert!(matches!(out_of_range, Err(ApiError::ValidationError { .. })));
+}
+
+#[tokio::test]
+async fn test_empty_move_slot_is_rejected_with_a_clear_message() {
+    let handler = create_test_handler().unwrap();
+    let battle_id = handler.create_battle(pvp_battle_request(
+        vec![team_pokemon(Species::Pikachu, 25, vec![Move::Thunderbolt, Move::QuickAttack])],
+        vec![team_pokemon(Species::Rattata, 25, vec![Move::Tackle])],
+    )).await.unwrap().battle_id;
+    let use_move = |move_index| handler.submit_action(SubmitActionRequest {
+        battle_id,
+        player_id: PlayerId("alice".to_string()),
+        action: PlayerAction::UseMove { move_index },
+        async_resolution: false,
+        authenticated_player_id: None,
+    });
+
+    let error = use_move(3).await.unwrap_err();
+    assert!(matches!(error, ApiError::InvalidAction { .. }));
+    assert!(error.to_string().contains("Move slot 3 is empty"), "Unexpected message: {}", error);
+
+    let error = use_move(7).await.unwrap_err();
+    assert!(error.to_string().contains("Move slot 7 does not exist"), "Unexpected message: {}", error);
+
+    assert!(use_move(1).await.unwrap().success, "A filled slot is still accepted");
+}
+
+#[tokio::test]
+async fn test_switch_to_active_pokemon_is_rejected() {
+    let handler = create_test_handler().unwrap();
+    let battle_id = handler.create_battle(pvp_battle_request(
+        vec![
+            team_pokemon(Species::Pikachu, 25, vec![Move::Thunderbolt]),
+            team_pokemon(Species::Rattata, 25, vec![Move::Tackle]),
+        ],
+        vec![team_pokemon(Species::Rattata, 25, vec![Move::Tackle])],
+    )).await.unwrap().battle_id;
+    let switch_to = |team_index| handler.submit_action(SubmitActionRequest {
+        battle_id,
+        player_id: PlayerId("alice".to_string()),
+        action: PlayerAction::SwitchPokemon { team_index },
+        async_resolution: false,
+        authenticated_player_id: None,
+    });
+
+    let error = switch_to(0).await.unwrap_err();
+    assert!(matches!(error, ApiError::InvalidAction { .. }));
+    assert!(error.to_string().contains("That Pokémon is already active"), "Unexpected message: {}", error);
+
+    assert!(switch_to(1).await.unwrap().success, "Switching to a benched Pokemon is still accepted");
+}
+
+#[tokio::test]
+async fn test_nicknames_name_custom_pokemon_and_are_length_checked() {
+    let handler = create_test_handler().unwrap();
+    let nicknamed = |nickname: &str| {
+        let mut pokemon = team_pokemon(Species::Pikachu, 25, vec![Move::Thunderbolt]);
+        pokemon.nickname = Some(nickname.to_string());
+        pokemon
+    };
+    let battle_id = handler.create_battle(pvp_battle_request(
+        vec![nicknamed("Sparky")],
+        vec![team_pokemon(Species::Rattata, 25, vec![Move::Tackle])],
+    )).await.unwrap().battle_id;
+
+    let state = |player: &str| handler.get_battle_state(GetBattleStateRequest {
+        battle_id,
+        player_id: PlayerId(player.to_string()),
+        strict_info: false,
+        debug: false,
+        is_admin: false,
+        observer_token: None,
+    });
+    let alice = state("alice").await.unwrap();
+    assert_eq!(alice.player_team.active_pokemon.as_ref().unwrap().name, "Sparky");
+    let bob = state("bob").await.unwrap();
+    assert_eq!(bob.opponent_info.active_pokemon.as_ref().unwrap().name, "Sparky", "Opponents see the nickname too");
+    let rattata = &alice.opponent_info.active_pokemon.as_ref().unwrap().name;
+    assert!(rattata.to_lowercase().contains("rattata"), "No nickname keeps the species name, got {}", rattata);
+
+    for invalid in ["", "   ", "ThirteenChars"] {
+        let error = handler.create_battle(pvp_battle_request(
+            vec![nicknamed(invalid)],
+            vec![team_pokemon(Species::Rattata, 25, vec![Move::Tackle])],
+        )).await.unwrap_err();
+        assert!(matches!(error, ApiError::ValidationError { .. }), "{:?} should be rejected", invalid);
+    }
+}
+
+#[tokio::test]
+async fn test_team_validation_reports_every_problem() {
+    let handler = create_test_handler().unwrap();
+
+    let valid = handler.validate_team(TeamValidationRequest {
+        team: vec![
+            team_pokemon(Species::Pikachu, 25, vec![Move::Thunderbolt, Move::QuickAttack]),
+            team_pokemon(Species::Rattata, 100, vec![Move::Tackle]),
+        ],
+        allow_illegal_moves: true,
+    }).await.unwrap();
+    assert!(valid.valid);
+    assert!(valid.errors.is_empty());
+
+    let invalid = handler.validate_team(TeamValidationRequest {
+        team: vec![
+            team_pokemon(Species::Pikachu, 25, vec![Move::Thunderbolt]),
+            team_pokemon(Species::Pikachu, 0, vec![Move::Thunderbolt, Move::Thunderbolt]),
+            team_pokemon(Species::Rattata, 25, vec![]),
+        ],
+        allow_illegal_moves: true,
+    }).await.unwrap();
+    assert!(!invalid.valid);
+    let indexes: Vec<Option<usize>> = invalid.errors.iter().map(|error| error.pokemon_index).collect();
+    assert_eq!(indexes, vec![Some(1), Some(1), Some(2)], "Both problems with the second Pokemon and the third's are listed");
+    assert!(invalid.errors[0].reason.contains("Invalid level 0"));
+    assert!(invalid.errors[1].reason.contains("Duplicate move Thunderbolt"));
+    assert_eq!(invalid.errors[2].reason, "Pokemon must have 1-4 moves");
+
+    let oversized = handler.validate_team(TeamValidationRequest {
+        team: vec![team_pokemon(Species::Rattata, 25, vec![Move::Tackle]); 7],
+        allow_illegal_moves: true,
+    }).await.unwrap();
+    assert_eq!(oversized.errors, vec![TeamError {
+        pokemon_index: None,
+        reason: "Team cannot have more than 6 Pokemon".to_string(),
+    }]);
+}
+
+#[tokio::test]
+async fn test_held_items_are_rejected_until_supported() {
+    let handler = create_test_handler().unwrap();
+    let mut holder = team_pokemon(Species::Pikachu, 25, vec![Move::Thunderbolt]);
+    holder.item = Some("Leftovers".to_string());
+
+    let error = handler.create_battle(pvp_battle_request(
+        vec![holder],
+        vec![team_pokemon(Species::Rattata, 25, vec![Move::Tackle])],
+    )).await.unwrap_err();
+    assert!(matches!(error, ApiError::ValidationError { .. }));
+    assert!(error.to_string().contains("Held items are not supported"), "got: {}", error);
+}
+
+#[tokio::test]
+async fn test_moves_must_be_learnable_unless_sandboxed() {
+    let handler = create_test_handler().unwrap();
+    let learnable_moves = |species| handler.get_learnable_moves(species);
+    let pikachu_moves: Vec<Move> = learnable_moves(Species::Pikachu).await.unwrap().moves.iter()
+        .map(|learnable| learnable.move_)
+        .take(2)
+        .collect();
+    let rattata_moves: Vec<Move> = learnable_moves(Species::Rattata).await.unwrap().moves.iter()
+        .map(|learnable| learnable.move_)
+        .take(1)
+        .collect();
+    let battle = |player1_team: Vec<TeamPokemon>, allow_illegal_moves| {
+        let mut request = pvp_battle_request(player1_team, vec![team_pokemon(Species::Rattata, 25, rattata_moves.clone())]);
+        request.allow_illegal_moves = allow_illegal_moves;
+        handler.create_battle(request)
+    };
+
+    battle(vec![team_pokemon(Species::Pikachu, 25, pikachu_moves)], false).await
+        .expect("Moves from the learnset are legal");
+
+    let illegal = || vec![team_pokemon(Species::Magikarp, 25, vec![Move::SolarBeam])];
+    let error = battle(illegal(), false).await.unwrap_err();
+    assert!(matches!(error, ApiError::ValidationError { .. }));
+    assert!(error.to_string().contains("Magikarp cannot learn SolarBeam"), "got: {}", error);
+
+    battle(illegal(), true).await.expect("Sandbox battles skip the learnset check");
+}
+
+#[tokio::test]
+async fn test_long_or_control_character_player_names_are_rejected() {
+    let handler = create_test_handler().unwrap();
+    let create = |player_name: String| handler.create_mvp_battle(CreateMvpBattleRequest {
+        player_name,
+        team_id: "venusaur_team".to_string(),
+        opponent_id: "gym_leader_easy".to_string(),
+        opponent_team_id: None,
+        opponent_level_cap: None,
+        switch_uses_turn: false,
+        grant_observer_token: false,
+        battle_seed: None,
+    });
+
+    let error = create("A".repeat(1000)).await.unwrap_err();
+    assert!(matches!(error, ApiError::ValidationError { .. }), "1000-character names never reach the battle log");
+    let error = create("Red\u{7}\nBlue".to_string()).await.unwrap_err();
+    assert!(matches!(error, ApiError::ValidationError { .. }));
+    create("Ash Ketchum 🔥".to_string()).await.expect("Ordinary unicode names are fine");
+
+    let error = handler.create_battle(CreateBattleRequest {
+        player1_id: PlayerId("x".repeat(1000)),
+        ..default_pvp_request()
+    }).await.unwrap_err();
+    assert!(matches!(error, ApiError::ValidationError { .. }), "Player ids are checked too, as they name two-player battle players");
+}
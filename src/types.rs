@@ -2,6 +2,7 @@ use pokemon_adventure::{
     battle::state::{BattleState, GameState},
     moves::Move,
     player::PlayerAction,
+    pokemon::{PokemonType, StatusCondition},
     species::Species,
 };
 use serde::{Deserialize, Serialize};
@@ -23,33 +24,237 @@ impl std::fmt::Display for BattleId {
     }
 }
 
+/// Unique identifier for a best-of-N match
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct MatchId(pub Uuid);
+
+impl MatchId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl std::fmt::Display for MatchId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Deserialize an explicit JSON `null` as the field's default, like an absent field
+fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
 /// Player identifier  
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct PlayerId(pub String);
 
 /// Request to create a new battle
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateBattleRequest {
     pub player1_id: PlayerId,
     pub player2_id: PlayerId,
     pub player1_team: Vec<TeamPokemon>,
     pub player2_team: Vec<TeamPokemon>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub switch_uses_turn: bool, // See BattleRules::switch_uses_turn
+    #[serde(default)]
+    pub level_budget: Option<u32>, // Maximum sum of levels allowed per team
+    #[serde(default)]
+    pub observer_for: Option<PlayerId>, // Grant an observer token for this participant's view
+    #[serde(default, alias = "seed")]
+    pub battle_seed: Option<u64>, // Make the whole battle reproducible; see StoredBattle::battle_seed
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub allow_illegal_moves: bool, // Sandbox battles: skip the check that each move is in its species' learnset
 }
 
 /// Pokemon configuration for team setup
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TeamPokemon {
     pub species: Species,
     pub level: u8,
     pub moves: Vec<Move>,
+    #[serde(default)]
     pub nickname: Option<String>,
+    #[serde(default)]
+    pub item: Option<String>, // Held item; the engine does not model items yet, so any value is rejected
 }
 
 /// Response when creating a battle
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateBattleResponse {
     pub battle_id: BattleId,
+    pub battle_number: u64, // Short sequential id for sharing, e.g. #10234
     pub status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub observer_token: Option<String>, // Present when `observer_for` was requested
+}
+
+/// Request to create a best-of-N match; the first game is created immediately
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateMatchRequest {
+    #[serde(flatten)]
+    pub battle: CreateBattleRequest,
+    pub best_of: u32, // Odd number of games, e.g. 3 for first to two wins
+}
+
+/// Response when creating a match
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateMatchResponse {
+    pub match_id: MatchId,
+    pub best_of: u32,
+    pub battle_id: BattleId, // First game
+    pub battle_number: u64,
+}
+
+/// Series score and per-game results of a match
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetMatchResponse {
+    pub match_id: MatchId,
+    pub best_of: u32,
+    pub player1_id: PlayerId,
+    pub player2_id: PlayerId,
+    pub score: [u32; 2],
+    pub finished: bool,
+    pub winner_player_id: Option<PlayerId>,
+    pub current_battle_id: Option<BattleId>, // Game in progress; None once the match is finished
+    pub games: Vec<MatchGame>,
+}
+
+/// Result of a single game in a match
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MatchGame {
+    pub game_number: u32, // Starting at 1
+    pub battle_id: BattleId,
+    pub result: Option<GameState>, // None if the game's battle has been deleted
+    pub winner_player_id: Option<PlayerId>,
+}
+
+/// Request to forfeit every unfinished battle a player is in
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AbandonAllRequest {
+    pub player_id: PlayerId,
+    #[serde(skip)]
+    pub authenticated_player_id: Option<PlayerId>, // From the `x-player-id` header; must match `player_id`
+}
+
+/// Outcome of abandoning a player's battles
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AbandonAllResponse {
+    pub player_id: PlayerId,
+    pub abandoned_count: usize,
+    pub failed_battle_ids: Vec<BattleId>, // Battles whose forfeit was rejected, e.g. a turn still resolving
+}
+
+/// Request to list the battles a player takes part in
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListPlayerBattlesRequest {
+    pub player_id: PlayerId,
+    pub limit: usize,
+    #[serde(skip)]
+    pub authenticated_player_id: Option<PlayerId>, // From the `x-player-id` header; must match `player_id`
+}
+
+/// Request for a player's finished battles, newest first, one page at a time
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlayerHistoryRequest {
+    pub player_id: PlayerId,
+    pub limit: usize,
+    pub cursor: Option<HistoryCursor>, // `next_cursor` of the previous page
+    #[serde(skip)]
+    pub authenticated_player_id: Option<PlayerId>, // From the `x-player-id` header; must match `player_id`
+}
+
+/// Position in a newest-first battle history: the last battle of the previous page
+/// Battles finished in the same second are ordered by id, so pages never skip or repeat one
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryCursor {
+    pub last_updated: i64,
+    pub battle_id: BattleId,
+}
+
+impl HistoryCursor {
+    pub fn of(battle: &StoredBattle) -> Self {
+        Self { last_updated: battle.last_updated, battle_id: battle.battle_id }
+    }
+
+    /// Sort key; larger keys come first in a history
+    pub fn key(&self) -> (i64, String) {
+        (self.last_updated, self.battle_id.to_string())
+    }
+
+    /// Whether `battle` belongs on a page after this cursor
+    pub fn precedes(&self, battle: &StoredBattle) -> bool {
+        HistoryCursor::of(battle).key() < self.key()
+    }
+}
+
+/// Opaque to clients: `<last_updated>.<battle_id>`
+impl std::fmt::Display for HistoryCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.last_updated, self.battle_id)
+    }
+}
+
+impl std::str::FromStr for HistoryCursor {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (last_updated, battle_id) = s.split_once('.').ok_or("malformed cursor")?;
+        Ok(Self {
+            last_updated: last_updated.parse().map_err(|_| "malformed cursor")?,
+            battle_id: BattleId(battle_id.parse().map_err(|_| "malformed cursor")?),
+        })
+    }
+}
+
+/// One page of a player's finished battles
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlayerHistoryResponse {
+    pub player_id: PlayerId,
+    pub battles: Vec<PlayerHistoryEntry>,
+    pub next_cursor: Option<String>, // Pass as `cursor` for the next page; None on the last page
+}
+
+/// A finished battle from one player's side
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlayerHistoryEntry {
+    pub battle_id: BattleId,
+    pub result: HistoryResult,
+    pub opponent_name: String,
+    pub finished_at: i64, // Unix timestamp
+    pub turn_count: u32, // Turns resolved
+}
+
+/// A finished battle's outcome for the player whose history it is in
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryResult {
+    Win,
+    Loss,
+    Draw,
+}
+
+/// A player's battles, most recently updated first
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlayerBattlesResponse {
+    pub player_id: PlayerId,
+    pub battles: Vec<PlayerBattleEntry>,
+}
+
+/// One battle in a player's battle list
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlayerBattleEntry {
+    pub battle_id: BattleId,
+    pub battle_number: Option<u64>,
+    pub opponent_id: PlayerId,
+    pub game_state: GameState,
+    pub created_at: i64, // Unix timestamp
+    pub last_updated: i64, // Unix timestamp
 }
 
 /// Request to submit a player action
@@ -58,6 +263,10 @@ pub struct SubmitActionRequest {
     pub battle_id: BattleId,
     pub player_id: PlayerId,
     pub action: PlayerAction,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub async_resolution: bool, // Respond once queued; poll /tick for the resolved turn
+    #[serde(skip)]
+    pub authenticated_player_id: Option<PlayerId>, // Set by the router from auth headers, never from the body
 }
 
 /// Response after submitting an action
@@ -66,6 +275,23 @@ pub struct SubmitActionResponse {
     pub success: bool,
     pub message: String,
     pub battle_updated: bool,
+    pub game_state: GameState,
+    pub turn_number: u32, // Current turn, awaiting actions
+    pub turns_resolved: u32,
+    pub can_act: bool, // Whether the acting player can act again in the new state
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<TurnOutcome>, // None when the action was queued for asynchronous resolution
+}
+
+/// Everything a client needs to render the frame after its action, in one response
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TurnOutcome {
+    pub events: Vec<String>, // Turns resolved by this action, as the acting player sees them
+    pub structured_events: Vec<BattleEventDto>, // Index-aligned with `events`
+    pub new_state_summary: GetBattleStateResponse,
+    pub your_valid_actions: Vec<AnnotatedAction>, // Empty when the player must wait or the battle is over
+    pub game_state: GameState,
+    pub result: Option<String>, // "win", "loss" or "draw" for the acting player once the battle is over
 }
 
 /// Request to get current battle state
@@ -114,18 +340,191 @@ pub struct StoredBattle {
     pub player2_id: PlayerId,
     pub battle_state: BattleState,
     pub turn_logs: Vec<TurnLog>, // Events per turn for battle log
+    #[serde(default)]
+    pub initial_state: Option<BattleState>, // State at creation, the starting point for replays; None for older battles
+    #[serde(default)]
+    pub setup: Option<BattleSetup>, // Creation parameters; None for battles stored before setup was recorded
+    #[serde(default)]
+    pub rules: BattleRules,
+    #[serde(default)]
+    pub draw_reason: Option<String>, // e.g. "mutual forfeit"; None unless the API decided a draw
+    #[serde(default)]
+    pub resolution_pending: bool, // An asynchronously submitted turn is queued until a `/tick` poll resolves it
+    #[serde(default)]
+    pub paused: bool, // Participants agreed to a break; actions other than forfeit are rejected
+    #[serde(default)]
+    pub battle_number: Option<u64>, // Short sequential id; None for battles created before numbering
+    #[serde(default)]
+    pub match_id: Option<MatchId>, // Series this battle is a game of, if any
+    #[serde(default)]
+    pub last_resolved_turn: Option<u32>, // Turn number of the newest turn log; saved atomically with the state
+    #[serde(default = "crate::engine::legacy_engine_schema_version")]
+    pub engine_schema_version: u32, // `ENGINE_SCHEMA_VERSION` the battle state was serialized with
+    #[serde(default)]
+    pub observer_tokens: Vec<ObserverToken>, // Granted at creation; each reads one participant's view
+    #[serde(default)]
+    pub battle_seed: Option<u64>, // Caller-supplied at creation; every turn's RNG is derived from it and the turn number
+    #[serde(default)]
+    pub version: u64, // Bumped by every save; saving a copy loaded at an older version fails with `Conflict`
     pub created_at: i64, // Unix timestamp
     pub last_updated: i64, // Unix timestamp
 }
 
+/// Lets a non-participant read one participant's view of a battle, e.g. a coaching bot
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ObserverToken {
+    pub token: String,
+    pub player_id: PlayerId, // Whose view the token reads
+}
+
+impl ObserverToken {
+    /// Grant a fresh unguessable token for a participant's view
+    pub fn new(player_id: PlayerId) -> Self {
+        Self { token: Uuid::new_v4().simple().to_string(), player_id }
+    }
+}
+
+/// How long a battle is kept after its last save before it expires
+pub const BATTLE_TTL_SECONDS: i64 = 7 * 24 * 60 * 60;
+
+impl StoredBattle {
+    /// Whether the stored battle has reached a terminal state
+    pub fn is_finished(&self) -> bool {
+        crate::engine::is_battle_finished(&self.battle_state)
+    }
+
+    /// Unix timestamp at which the battle expires, `BATTLE_TTL_SECONDS` after its last save
+    pub fn expires_at(&self) -> i64 {
+        self.last_updated + BATTLE_TTL_SECONDS
+    }
+
+    /// Whether the battle has expired as of `now` (Unix timestamp)
+    pub fn is_expired(&self, now: i64) -> bool {
+        now >= self.expires_at()
+    }
+}
+
+/// Stored best-of-N series; each game is an ordinary stored battle
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StoredMatch {
+    pub match_id: MatchId,
+    pub best_of: u32,
+    pub config: CreateBattleRequest, // Players, teams and rules every game is created with
+    pub battle_ids: Vec<BattleId>, // One per game in play order; the last is the current game
+    pub score: [u32; 2], // Games won by player 1 and player 2; draws score for neither
+    pub winner_index: Option<usize>,
+    pub finished: bool, // Set once a player reaches a majority or all games are played
+    pub created_at: i64, // Unix timestamp
+    pub last_updated: i64, // Unix timestamp
+}
+
+/// Format rules chosen at creation and enforced on every action
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BattleRules {
+    /// No free switches: a voluntary switch is not offered or accepted while players are
+    /// choosing actions. Forced replacements after a faint are unaffected and remain free.
+    #[serde(default)]
+    pub switch_uses_turn: bool,
+}
+
+/// Parameters a battle was created with, kept for display
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BattleSetup {
+    pub title: String, // e.g. "Venusaur Team vs Gym Leader Brock"
+    pub team_id: Option<String>,
+    pub opponent_id: Option<String>,
+    pub opponent_team_id: Option<String>,
+    pub opponent_level_cap: Option<u8>,
+}
+
 /// Turn log entry storing events for a specific turn
+/// `events` and `structured_events` are the public log, parallel by index
+/// `private_events` hold the exact figures redacted from the public log
+/// (logs written before structured events existed have an empty `structured_events`)
+/// Pacing markers have an empty narrative entry and are stripped unless requested
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct TurnLog {
     pub turn_number: u32,
     pub events: Vec<String>, // Human-readable event messages
+    #[serde(default)]
+    pub structured_events: Vec<BattleEventDto>, // Machine-readable events for animation/filtering, index-aligned with `events`
+    #[serde(default, skip_serializing_if = "no_private_events")]
+    pub private_events: [Vec<PrivateEvent>; 2], // Per player; overlaid onto the public events for that player only
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resolutions: Vec<TurnResolution>, // Replay inputs; empty for logs written before they were recorded
     pub timestamp: i64, // When this turn was processed
 }
 
+/// Everything one engine turn resolution consumed, enough to replay it exactly
+/// NPC actions are recorded as decided, since the engine's AI draws from its own RNG
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TurnResolution {
+    pub seed: Option<u64>, // `TurnRng` seed; None when the API resolved the turn itself (forfeits) or the RNG was unseeded
+    pub actions: [Option<PlayerAction>; 2], // Action queue the turn resolved, NPC choices included
+    #[serde(default)]
+    pub unseeded: bool, // Resolved with the engine's own random RNG, so the turn cannot be replayed
+}
+
+/// Full-detail version of a redacted public event, visible to one player only
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PrivateEvent {
+    pub index: usize, // Position in the public `events`/`structured_events` this replaces
+    pub event: String,
+    pub structured_event: BattleEventDto,
+}
+
+fn no_private_events(private_events: &[Vec<PrivateEvent>; 2]) -> bool {
+    private_events.iter().all(|events| events.is_empty())
+}
+
+/// Structured battle event mirroring the engine's main event variants
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BattleEventDto {
+    MoveUsed { player_index: usize, pokemon: Species, move_used: Move },
+    Damage {
+        target: Species,
+        #[serde(default)]
+        target_player: Option<usize>, // Side the damaged Pokemon is on; None in older logs and when the sides could not be told apart
+        damage: Option<u16>, // Exact figures only in the target owner's view
+        remaining_hp: Option<u16>,
+        remaining_hp_percent: Option<u8>,
+    },
+    Faint { player_index: usize, pokemon: Species },
+    StatusApplied { target: Species, status: StatusCondition },
+    SwitchIn { player_index: usize, old_pokemon: Species, new_pokemon: Species },
+    PhaseBoundary { phase: String }, // Pacing marker: the events that follow belong to a new sub-phase ("action", "faint", "switch")
+    TurnBoundary { turn_number: u32 }, // Pacing marker: the turn resolution that just finished
+    Other, // Engine events without a structured mapping yet
+}
+
+impl BattleEventDto {
+    /// All event kinds accepted by the `event_types` filter
+    pub const KINDS: &'static [&'static str] = &[
+        "move_used", "damage", "faint", "status_applied", "switch_in",
+        "phase_boundary", "turn_boundary", "other",
+    ];
+
+    /// Wire name of this event's kind, matching the serde tag
+    pub fn kind(&self) -> &'static str {
+        match self {
+            BattleEventDto::MoveUsed { .. } => "move_used",
+            BattleEventDto::Damage { .. } => "damage",
+            BattleEventDto::Faint { .. } => "faint",
+            BattleEventDto::StatusApplied { .. } => "status_applied",
+            BattleEventDto::SwitchIn { .. } => "switch_in",
+            BattleEventDto::PhaseBoundary { .. } => "phase_boundary",
+            BattleEventDto::TurnBoundary { .. } => "turn_boundary",
+            BattleEventDto::Other => "other",
+        }
+    }
+
+    /// Whether this is a pacing marker rather than something that happened in battle
+    pub fn is_marker(&self) -> bool {
+        matches!(self, BattleEventDto::PhaseBoundary { .. } | BattleEventDto::TurnBoundary { .. })
+    }
+}
+
 /// New API request/response types for clean architecture
 
 /// Request to get battle state
@@ -133,6 +532,14 @@ pub struct TurnLog {
 pub struct GetBattleStateRequest {
     pub battle_id: BattleId,
     pub player_id: PlayerId,
+    #[serde(default)]
+    pub strict_info: bool, // Hide derived opponent PP so players have to count it themselves
+    #[serde(default)]
+    pub debug: bool, // Append the raw engine state for debugging client integrations
+    #[serde(skip)]
+    pub is_admin: bool, // Set by the router from the admin key; admins see the raw state unredacted
+    #[serde(default)]
+    pub observer_token: Option<String>, // Read the view of the player the token was granted for instead of `player_id`'s
 }
 
 /// Response containing battle state
@@ -140,10 +547,18 @@ pub struct GetBattleStateRequest {
 pub struct GetBattleStateResponse {
     pub battle_id: BattleId,
     pub game_state: GameState,
-    pub turn_number: u32,
+    pub turn_number: u32, // Current turn, awaiting actions
+    pub turns_resolved: u32,
     pub can_act: bool,
+    pub will_struggle: bool, // Every move is out of PP, so any move action becomes Struggle
+    pub can_switch: bool, // Switching is among the valid actions right now
+    pub trapped_reason: Option<String>, // Set when a trapping move is why switching is blocked
     pub player_team: ApiTeamView,
     pub opponent_info: ApiOpponentView,
+    pub field_conditions: Vec<ConditionDto>, // Side conditions per player; the engine models no weather or hazards
+    pub paused: bool, // Actions are rejected until a participant resumes the battle
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_state: Option<serde_json::Value>, // Engine `BattleState` JSON, only in debug mode
 }
 
 /// Request to get valid actions
@@ -158,6 +573,28 @@ pub struct GetValidActionsRequest {
 pub struct GetValidActionsResponse {
     pub battle_id: BattleId,
     pub valid_actions: Vec<PlayerAction>,
+    pub switch_previews: Vec<SwitchPreview>, // One per valid SwitchPokemon action
+    pub move_analysis: Vec<MoveAnalysis>, // One per valid UseMove action
+}
+
+/// Priority bracket and hit chance of the move a valid UseMove action would use
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MoveAnalysis {
+    pub move_index: usize,
+    #[serde(rename = "move")]
+    pub move_: Move,
+    pub priority: i8, // Higher brackets act first regardless of speed; most moves are 0
+    pub hit_chance: f32, // 0.0..=1.0 against the opponent's active Pokemon at its current evasion
+}
+
+/// Type matchup the incoming Pokemon would face for a valid switch
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SwitchPreview {
+    pub team_index: usize,
+    pub species: Species,
+    pub opponent_species: Option<Species>, // None when the opponent has no active Pokemon
+    pub offensive_multiplier: Option<f32>, // Best damaging-move multiplier against the opponent
+    pub defensive_multiplier: Option<f32>, // Best multiplier of the opponent's own types against the incoming Pokemon
 }
 
 /// Request to get team information
@@ -174,6 +611,33 @@ pub struct GetTeamInfoResponse {
     pub team: ApiTeamView,
 }
 
+/// Request for the unmasked spectator view of a battle
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetSpectatorViewRequest {
+    pub battle_id: BattleId,
+    pub player_id: PlayerId,
+    #[serde(skip)]
+    pub is_admin: bool, // Set by the router from the admin key; admins may spectate battles still in progress
+}
+
+/// Both teams in full, as a neutral spectator sees them
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SpectatorViewResponse {
+    pub battle_id: BattleId,
+    pub game_state: GameState,
+    pub turn_number: u32,
+    pub turns_resolved: u32,
+    pub players: Vec<ApiSpectatorPlayer>, // Player 1 then player 2
+}
+
+/// One side of the spectator view
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiSpectatorPlayer {
+    pub player_id: PlayerId,
+    pub player_name: String,
+    pub team: ApiTeamView,
+}
+
 /// API representation of team view
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ApiTeamView {
@@ -184,6 +648,7 @@ pub struct ApiTeamView {
 /// Detailed Pokemon information for API
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ApiPokemonDetail {
+    pub slot_id: usize, // Team slot index, matching `SwitchPokemon { team_index }`
     pub name: String,
     pub species: Species,
     pub level: u8,
@@ -195,27 +660,50 @@ pub struct ApiPokemonDetail {
     pub sp_defense: u16,
     pub speed: u16,
     pub moves: Vec<Option<ApiMoveView>>,
-    pub status: Option<String>,
+    pub status: Option<StatusDto>,
+    pub charging_move: Option<Move>,
 }
 
 /// Summary Pokemon information for API
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ApiPokemonSummary {
+    pub slot_id: usize, // Team slot index; use this rather than `name` to identify a Pokemon
     pub name: String,
     pub species: Species,
     pub level: u8,
     pub current_hp: u16,
     pub max_hp: u16,
     pub is_fainted: bool,
-    pub status: Option<String>,
+    pub status: Option<StatusDto>,
+    pub types: Vec<PokemonType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>, // "data unavailable" when species data could not be resolved
+}
+
+/// Structured status condition, e.g. `{"kind": "sleep", "turns_remaining": 2}`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct StatusDto {
+    pub kind: String, // "sleep", "poison", "burn", "freeze", "paralysis", ...
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub turns_remaining: Option<u8>, // Sleep turns left; omitted for other statuses and hidden opponent counters
+}
+
+/// Condition affecting the field or one side of it, e.g. `{"kind": "reflect", "side": "player", "turns_remaining": 4}`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ConditionDto {
+    pub kind: String, // "reflect", "light_screen", "mist", ...
+    pub side: Option<String>, // "player" or "opponent" from the viewer's perspective; None for field-wide conditions
+    pub turns_remaining: Option<u8>,
 }
 
 /// Move information for API
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ApiMoveView {
+    #[serde(rename = "move")]
     pub move_: Move,
     pub pp: u8,
     pub max_pp: u8,
+    pub priority: i8, // Priority bracket; higher acts first regardless of speed
 }
 
 /// Opponent information for API
@@ -224,6 +712,16 @@ pub struct ApiOpponentView {
     pub player_name: String,
     pub active_pokemon: Option<ApiPokemonSummary>,
     pub remaining_pokemon_count: usize,
+    pub revealed_moves: Vec<ApiRevealedMove>, // Moves the opponent's active Pokemon has used, in order of first use
+}
+
+/// An opponent move revealed by being used in battle
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiRevealedMove {
+    #[serde(rename = "move")]
+    pub move_: Move,
+    pub times_used: u32,
+    pub revealed_move_pp: Option<u8>, // max_pp minus times used; None in strict info mode
 }
 
 /// API types for MVP endpoints
@@ -244,6 +742,29 @@ pub struct PrefabTeamInfo {
     pub average_level: u8,
 }
 
+/// Response for the full-roster available teams endpoint
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AvailableTeamsFullResponse {
+    pub teams: Vec<PrefabTeamDetail>,
+}
+
+/// Prefab team with its complete roster
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PrefabTeamDetail {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub pokemon: Vec<PrefabPokemonInfo>,
+}
+
+/// Single roster entry of a prefab team
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PrefabPokemonInfo {
+    pub species: Species,
+    pub level: u8,
+    pub moves: Vec<Move>,
+}
+
 /// Response for NPC opponents endpoint
 #[derive(Debug, Serialize, Deserialize)]
 pub struct NpcOpponentsResponse {
@@ -259,20 +780,63 @@ pub struct NpcOpponentInfo {
     pub difficulty: String,
 }
 
+/// Response for the game states endpoint
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GameStatesResponse {
+    pub states: Vec<GameStateInfo>,
+}
+
+/// A game state clients may see in `game_state`, with what it means
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GameStateInfo {
+    pub state: GameState, // Serialized exactly as in battle responses
+    pub description: String,
+    pub finished: bool, // No further actions are accepted
+}
+
 /// MVP Create battle request (simplified)
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateMvpBattleRequest {
     pub player_name: String,
     pub team_id: String,
     pub opponent_id: String,
+    #[serde(default)]
+    pub opponent_team_id: Option<String>, // Prefab team for the NPC instead of a random one
+    #[serde(default)]
+    pub opponent_level_cap: Option<u8>, // Clamp every NPC Pokemon to at most this level
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub switch_uses_turn: bool, // See BattleRules::switch_uses_turn
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub grant_observer_token: bool, // Grant an observer token for the player's view
+    #[serde(default, alias = "seed")]
+    pub battle_seed: Option<u64>, // Make the whole battle reproducible; see StoredBattle::battle_seed
+}
+
+/// Create an MVP battle and submit the player's first action in one request
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QuickplayRequest {
+    #[serde(flatten)]
+    pub battle: CreateMvpBattleRequest,
+    pub action: PlayerAction, // The player's first-turn action
 }
 
 /// MVP Create battle response
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateMvpBattleResponse {
     pub battle_id: BattleId,
+    pub battle_number: u64, // Short sequential id for sharing, e.g. #10234
     pub status: String,
     pub battle_state: GetBattleStateResponse, // Include initial state
+    pub valid_actions: Vec<AnnotatedAction>, // First-turn actions for the player
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub observer_token: Option<String>, // Present when `grant_observer_token` was set
+}
+
+/// A valid action with a human-readable description for menus
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AnnotatedAction {
+    pub action: PlayerAction,
+    pub description: String,
 }
 
 /// Request to get battle events/log
@@ -280,7 +844,18 @@ pub struct CreateMvpBattleResponse {
 pub struct GetBattleEventsRequest {
     pub battle_id: BattleId,
     pub player_id: PlayerId,
+    #[serde(default)]
     pub last_turns: Option<u32>, // If specified, get only the last X turns; if None, get all
+    #[serde(default)]
+    pub since_turn: Option<u32>, // If specified, get only turns after this one (catch-up after a missed response)
+    #[serde(default)]
+    pub event_types: Option<Vec<String>>, // If specified, keep only structured events of these kinds
+    #[serde(default)]
+    pub include_markers: bool, // Keep phase/turn boundary markers for animation pacing
+    #[serde(default)]
+    pub offset: Option<usize>, // Page start, counted in turn logs after `since_turn` filtering; not with `last_turns`
+    #[serde(default)]
+    pub limit: Option<usize>, // Page size; None returns every log from `offset` on
 }
 
 /// Response containing battle events
@@ -288,5 +863,483 @@ pub struct GetBattleEventsRequest {
 pub struct GetBattleEventsResponse {
     pub battle_id: BattleId,
     pub turn_logs: Vec<TurnLog>,
-    pub total_turns: u32,
-}
\ No newline at end of file
+    pub total_turns: u32, // Legacy alias of `current_turn`
+    pub current_turn: u32,
+    pub turns_resolved: u32,
+    pub last_resolved_turn: Option<u32>, // Compare with the last turn seen to detect missed events
+    pub has_more: bool, // More turn logs follow this page
+    pub next_offset: Option<usize>, // `offset` for the next page, when there is one
+}
+
+/// Request to get events for several battles at once
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchEventsRequest {
+    pub battle_ids: Vec<BattleId>,
+    pub player_id: PlayerId, // Must be a participant in every battle
+    #[serde(default)]
+    pub last_turns: Option<u32>, // Applied to each battle
+}
+
+/// Events for each requested battle, in request order
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchEventsResponse {
+    pub battles: Vec<GetBattleEventsResponse>,
+}
+
+/// Request to get a single turn's log
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetTurnLogRequest {
+    pub battle_id: BattleId,
+    pub player_id: PlayerId,
+    pub turn_number: u32,
+}
+
+/// Response containing a single turn's log
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetTurnLogResponse {
+    pub battle_id: BattleId,
+    pub turn_log: TurnLog,
+}
+
+/// Request to compare two teams before battling
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TeamMatchupRequest {
+    pub team_a: Vec<TeamPokemon>,
+    pub team_b: Vec<TeamPokemon>,
+}
+
+/// Request to preview a custom team's computed stats before battling
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TeamPreviewRequest {
+    pub team: Vec<TeamPokemon>,
+}
+
+/// Each team member as it would enter battle, in team order
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TeamPreviewResponse {
+    pub pokemon: Vec<ApiPokemonDetail>,
+}
+
+/// Request to check a proposed team without creating anything
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TeamValidationRequest {
+    pub team: Vec<TeamPokemon>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub allow_illegal_moves: bool, // As on CreateBattleRequest
+}
+
+/// Every problem with a proposed team, rather than only the first
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TeamValidationResponse {
+    pub valid: bool,
+    pub errors: Vec<TeamError>,
+}
+
+/// One reason a team is not legal
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TeamError {
+    pub pokemon_index: Option<usize>, // Offending team member; None for team-wide problems like its size
+    pub reason: String,
+}
+
+/// Response containing the type-matchup grid between two teams
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TeamMatchupResponse {
+    pub pairings: Vec<MatchupPairing>,
+    pub team_a_advantages: usize,
+    pub team_b_advantages: usize,
+}
+
+/// Type matchup for a single pairing of Pokemon across two teams
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MatchupPairing {
+    pub team_a_index: usize,
+    pub team_b_index: usize,
+    pub team_a_species: Species,
+    pub team_b_species: Species,
+    pub team_a_best_multiplier: f32, // Best damaging-move multiplier team A's Pokemon has against team B's
+    pub team_b_best_multiplier: f32,
+    pub advantage: String, // "team_a", "team_b", or "even"
+}
+
+/// Admin request to inspect a battle's action queue
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetActionQueueRequest {
+    pub battle_id: BattleId,
+}
+
+/// Admin request to patch a battle's state for QA scenarios
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SetBattleStateRequest {
+    #[serde(skip, default = "BattleId::new")]
+    pub battle_id: BattleId, // Taken from the path
+    pub patch: serde_json::Value, // JSON merge patch over the serialized `BattleState`
+}
+
+/// Summary of a battle after an admin state patch
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SetBattleStateResponse {
+    pub battle_id: BattleId,
+    pub game_state: GameState,
+    pub turn_number: u32,
+}
+
+/// Admin diagnosis of a battle's action queue: what is queued and whether the turn can resolve
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetActionQueueResponse {
+    pub battle_id: BattleId,
+    pub game_state: GameState,
+    pub turn_number: u32,
+    pub slots: Vec<ActionQueueSlot>,
+    pub ready_for_turn_resolution: bool, // False while an action the engine needs is missing
+    pub awaiting_second_replacement: bool, // One replacement queued after a double faint; the other is still due
+    pub resolution_pending: bool, // An asynchronously submitted turn has not been resolved yet
+    pub paused: bool, // Actions other than forfeit are rejected until resumed
+}
+
+/// Fill status of a single player's action queue slot
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ActionQueueSlot {
+    pub player_index: usize,
+    pub player_id: PlayerId,
+    pub action_submitted: bool,
+    pub action: Option<PlayerAction>, // The queued action itself; admin-only
+}
+
+/// Request to get a battle's creation parameters
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetBattleSetupRequest {
+    pub battle_id: BattleId,
+    pub player_id: PlayerId,
+}
+
+/// Response containing a battle's creation parameters
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetBattleSetupResponse {
+    pub battle_id: BattleId,
+    pub setup: BattleSetup,
+}
+
+/// Response for the public recent battles feed
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecentBattlesResponse {
+    pub battles: Vec<PublicBattleSummary>,
+}
+
+/// Public summary of a finished battle with no hidden team information
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PublicBattleSummary {
+    pub battle_id: BattleId,
+    pub title: Option<String>,
+    pub result: GameState,
+    pub winner_name: Option<String>,
+    pub turn_count: u32, // Turns resolved
+    pub finished_at: i64, // Unix timestamp
+}
+
+/// A finished battle in Pokemon Showdown's protocol, shaped like a Showdown replay's JSON
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShowdownLogResponse {
+    pub battle_id: BattleId,
+    pub log: String, // Newline-separated protocol lines, e.g. "|move|p1a: Pikachu|Thunderbolt|p2a: Rattata"
+}
+
+/// Public outcome of a finished battle
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BattleResultResponse {
+    pub battle_id: BattleId,
+    pub result: GameState,
+    pub winner_player_id: Option<PlayerId>,
+    pub draw_reason: Option<String>,
+    pub turn_count: u32, // Turns resolved
+    pub finished_at: i64, // Unix timestamp
+}
+
+/// Test harness request to play an MVP battle through to the end in one call
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlayoutRequest {
+    pub team_id: String,
+    pub opponent_id: String,
+    pub opponent_team_id: String, // Required, since a random NPC team would make the playout nondeterministic
+    #[serde(default)]
+    pub opponent_level_cap: Option<u8>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub switch_uses_turn: bool,
+    #[serde(alias = "seed")]
+    pub battle_seed: u64,
+}
+
+/// A finished playout: its result and every turn log, for all players
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlayoutResponse {
+    pub result: GameState,
+    pub winner_player_id: Option<PlayerId>,
+    pub draw_reason: Option<String>,
+    pub turn_count: u32, // Turns resolved
+    pub turn_logs: Vec<TurnLog>, // Unredacted, including private events
+}
+
+/// Request to delete a battle
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeleteBattleRequest {
+    pub battle_id: BattleId,
+    pub player_id: PlayerId, // Must be a participant
+    #[serde(skip)]
+    pub authenticated_player_id: Option<PlayerId>, // From the `x-player-id` header; must match `player_id` when present
+}
+
+/// Request for a finished battle's per-Pokemon damage totals
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetDamageReportRequest {
+    pub battle_id: BattleId,
+    pub player_id: PlayerId,
+}
+
+/// Damage totals for each Pokemon of both teams, from the requester's perspective
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DamageReportResponse {
+    pub battle_id: BattleId,
+    pub player_team: Vec<PokemonDamage>, // One entry per team slot, in slot order
+    pub opponent_team: Vec<PokemonDamage>,
+}
+
+/// Request for a rough estimate of how long a battle has left
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetBattleEstimateRequest {
+    pub battle_id: BattleId,
+    pub player_id: PlayerId,
+}
+
+/// Heuristic turns-remaining estimate; not a guarantee
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BattleEstimateResponse {
+    pub battle_id: BattleId,
+    pub turns_resolved: u32,
+    pub estimated_turns_remaining: Option<u32>, // None until damage has been dealt, and once the battle is over
+}
+
+/// Damage one Pokemon dealt and received over a battle
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PokemonDamage {
+    pub slot_id: usize,
+    pub species: Species,
+    pub damage_dealt: u32, // Credited to the opposing Pokemon that last used a move that turn
+    pub damage_received: u32,
+}
+
+/// Request for the Pokemon that changed after a turn
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetBattleChangesRequest {
+    pub battle_id: BattleId,
+    pub player_id: PlayerId,
+    pub since_turn: u32, // Changes made by turns after this one
+}
+
+/// Only the Pokemon whose HP, status, PP or active status changed, for cheap live updates
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BattleChangesResponse {
+    pub battle_id: BattleId,
+    pub since_turn: u32,
+    pub latest_turn: u32, // Newest resolved turn; pass it back as the next `since_turn`
+    pub game_state: GameState,
+    pub team: Vec<ApiPokemonChange>,
+    pub opponent: Vec<ApiPokemonChange>, // Only Pokemon active at either end, so benched ones stay hidden
+    pub full_refresh: bool, // The earlier state could not be rebuilt, so every visible Pokemon is listed
+}
+
+/// A changed Pokemon's current HP, status, PP and active status
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiPokemonChange {
+    pub slot_id: usize,
+    pub name: String,
+    pub current_hp: u16,
+    pub max_hp: u16,
+    pub status: Option<StatusDto>,
+    pub is_active: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub move_pp: Option<Vec<Option<u8>>>, // Per move slot; own Pokemon only
+}
+
+/// Request to re-simulate a battle from its recorded turns
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetBattleReplayRequest {
+    pub battle_id: BattleId,
+    pub player_id: PlayerId, // Views are shown from this participant's side
+}
+
+/// A battle re-resolved from its initial state and recorded actions and seeds
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BattleReplayResponse {
+    pub battle_id: BattleId,
+    pub initial: GetBattleStateResponse,
+    pub turns: Vec<ReplayTurn>, // One per turn log, in order
+    pub matches_stored_state: bool, // False means the stored state and logs have diverged, e.g. after an admin patch
+}
+
+/// The replayed battle as it stood after one turn log
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReplayTurn {
+    pub turn_number: u32,
+    pub battle_state: GetBattleStateResponse,
+}
+
+/// Request to rematch a finished MVP battle with the same setup
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RematchRequest {
+    pub battle_id: BattleId,
+    pub player_id: PlayerId,
+    #[serde(skip)]
+    pub authenticated_player_id: Option<PlayerId>, // From the `x-player-id` header; must match `player_id` when present
+}
+
+/// Request to forfeit a battle through the dedicated endpoint
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ForfeitRequest {
+    pub battle_id: BattleId,
+    pub player_id: PlayerId,
+    #[serde(skip)]
+    pub authenticated_player_id: Option<PlayerId>, // From the `x-player-id` header; must match `player_id` when present
+}
+
+/// Final state of a forfeited battle
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ForfeitResponse {
+    pub battle_id: BattleId,
+    pub game_state: GameState, // Terminal: a win for the opponent, or a draw if they forfeited too
+    pub battle_state: GetBattleStateResponse, // Final view for the forfeiting player
+}
+
+/// Request to pause or resume a battle
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SetPausedRequest {
+    pub battle_id: BattleId,
+    pub player_id: PlayerId,
+    pub paused: bool,
+}
+
+/// Pause state of a battle after a pause or resume
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SetPausedResponse {
+    pub battle_id: BattleId,
+    pub paused: bool,
+}
+
+/// Request to poll for asynchronous turn resolution
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetTickStatusRequest {
+    pub battle_id: BattleId,
+    pub player_id: PlayerId,
+}
+
+/// Response describing whether a queued turn has been resolved
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetTickStatusResponse {
+    pub battle_id: BattleId,
+    pub processing: bool,
+    pub game_state: GameState,
+    pub turn_number: u32, // Current turn, awaiting actions
+    pub turns_resolved: u32,
+    pub can_act: bool,
+}
+
+/// Response listing every move a species can learn, for populating team builder move choices
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LearnsetResponse {
+    pub species: Species,
+    pub moves: Vec<LearnableMove>,
+}
+
+/// A single learnable move and how the species obtains it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LearnableMove {
+    #[serde(rename = "move")]
+    pub move_: Move,
+    pub method: String, // "level_up", "signature", or "teachable"
+    pub level: Option<u8>, // Only set for level-up moves
+}
+
+/// Pokedex entry for a species, for validating team choices before creating a battle
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SpeciesInfo {
+    pub species: Species,
+    pub types: Vec<PokemonType>,
+    pub base_stats: BaseStatsInfo,
+}
+
+/// A species' base stats, before level and IVs are applied
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BaseStatsInfo {
+    pub hp: u8,
+    pub attack: u8,
+    pub defense: u8,
+    pub sp_attack: u8,
+    pub sp_defense: u8,
+    pub speed: u8,
+}
+
+/// Static data for a move, as the team builder shows it
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MoveInfo {
+    #[serde(rename = "move")]
+    pub move_: Move,
+    pub move_type: PokemonType,
+    pub power: Option<u8>, // None for status moves
+    pub accuracy: Option<u8>, // Percent; None for moves that never miss
+    pub max_pp: u8,
+    pub priority: i8,
+}
+
+/// Request for the active Pokemon's move PP
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetMovePpRequest {
+    pub battle_id: BattleId,
+    pub player_id: PlayerId,
+}
+
+/// Response containing PP for each of the active Pokemon's known moves
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetMovePpResponse {
+    pub battle_id: BattleId,
+    pub moves: Vec<ApiMoveView>,
+}
+
+/// Type effectiveness of an attacking type against a species, outside any battle
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EffectivenessResponse {
+    pub move_type: PokemonType,
+    pub defender_species: Species,
+    pub defender_types: Vec<PokemonType>,
+    pub multiplier: f32,
+}
+
+/// Request resolving a short battle number to its battle id
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetBattleByNumberRequest {
+    pub battle_number: u64,
+    #[serde(skip)]
+    pub authenticated_player_id: Option<PlayerId>, // From the `x-player-id` header; must be a participant
+    #[serde(skip)]
+    pub is_admin: bool, // Set by the router from the admin key; admins may resolve any battle number
+}
+
+/// Response resolving a short battle number to its battle id
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BattleByNumberResponse {
+    pub battle_id: BattleId,
+    pub battle_number: u64,
+}
+
+/// Aggregate move usage across all battles
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MoveUsageResponse {
+    pub moves: Vec<MoveUsageCount>,
+    pub total_uses: u64,
+}
+
+/// Number of times a single move has been used
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MoveUsageCount {
+    #[serde(rename = "move")]
+    pub move_name: String,
+    pub count: u64,
+}